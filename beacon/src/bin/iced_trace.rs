@@ -0,0 +1,91 @@
+//! A local beacon server that exports the spans of an iced application
+//! to a Chrome Trace Event file, which can be opened with Perfetto.
+//!
+//! Events are streamed to the trace file as they arrive, and flushed every
+//! second as well as whenever an application disconnects; so it can be opened
+//! at any time. Press Ctrl+C to close the trace and stop tracing.
+//!
+//! Usage: `iced_trace [OUTPUT]` (defaults to `trace.json`)
+use iced_beacon::{Event, Trace};
+
+use futures::StreamExt;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::pin::pin;
+use std::time::Duration;
+
+/// How often the trace file is flushed while new events arrive.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    let output = env::args_os()
+        .nth(1)
+        .map_or_else(|| PathBuf::from("trace.json"), PathBuf::from);
+
+    let mut events = pin!(iced_beacon::run());
+    let mut trace = Trace::new(io::BufWriter::new(fs::File::create(&output)?))?;
+    let mut is_dirty = false;
+
+    let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+    let mut stop = pin!(tokio::signal::ctrl_c());
+
+    eprintln!("Waiting for an iced application to connect...");
+
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else {
+                    break;
+                };
+
+                match &event {
+                    Event::AlreadyRunning { .. } => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::AddrInUse,
+                            "another beacon server (e.g. comet) is already running",
+                        ));
+                    }
+                    Event::Connected { name, .. } => {
+                        eprintln!("Tracing {name}...");
+                    }
+                    Event::Disconnected { .. } => {
+                        trace.flush()?;
+                        is_dirty = false;
+
+                        eprintln!(
+                            "Trace written to {} (press Ctrl+C to stop)",
+                            output.display()
+                        );
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                trace.push(&event)?;
+                is_dirty = true;
+            }
+            _ = flush.tick() => {
+                if is_dirty {
+                    trace.flush()?;
+                    is_dirty = false;
+                }
+            }
+            _ = &mut stop => {
+                break;
+            }
+        }
+    }
+
+    let is_empty = trace.is_empty();
+    let _ = trace.finish()?;
+
+    if !is_empty {
+        eprintln!("Trace written to {}", output.display());
+    }
+
+    Ok(())
+}
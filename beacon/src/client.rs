@@ -1,4 +1,5 @@
 use crate::Error;
+use crate::Metric;
 use crate::core::time::{Duration, SystemTime};
 use crate::span;
 use crate::theme;
@@ -51,6 +52,7 @@ pub enum Event {
     CommandsSpawned(usize),
     SubscriptionsTracked(usize),
     LayersRendered(usize),
+    MetricRecorded { name: String, metric: Metric },
 }
 
 impl Client {
@@ -2,13 +2,19 @@ pub use iced_core as core;
 pub use semver::Version;
 
 pub mod client;
+pub mod metric;
+pub mod profile;
 pub mod span;
+pub mod trace;
 
 mod error;
 mod stream;
 
 pub use client::Client;
+pub use metric::Metric;
+pub use profile::{Budget, Profile, Profiler};
 pub use span::Span;
+pub use trace::Trace;
 
 use crate::core::theme;
 use crate::core::time::{Duration, SystemTime};
@@ -64,6 +70,11 @@ pub enum Event {
         at: SystemTime,
         palette: theme::Palette,
     },
+    MetricRecorded {
+        at: SystemTime,
+        name: String,
+        metric: Metric,
+    },
     SpanFinished {
         at: SystemTime,
         duration: Duration,
@@ -83,6 +94,7 @@ impl Event {
             Self::Connected { at, .. }
             | Self::Disconnected { at, .. }
             | Self::ThemeChanged { at, .. }
+            | Self::MetricRecorded { at, .. }
             | Self::SpanFinished { at, .. }
             | Self::QuitRequested { at }
             | Self::AlreadyRunning { at } => *at,
@@ -191,6 +203,18 @@ pub fn run() -> impl Stream<Item = Event> {
                                             })
                                             .await;
                                     }
+                                    client::Event::MetricRecorded {
+                                        name,
+                                        metric,
+                                    } => {
+                                        let _ = output
+                                            .send(Event::MetricRecorded {
+                                                at,
+                                                name,
+                                                metric,
+                                            })
+                                            .await;
+                                    }
                                     client::Event::SubscriptionsTracked(
                                         amount_alive,
                                     ) => {
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+/// A sample of some application-specific metric.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Metric {
+    /// The running total of a monotonic counter.
+    Counter(u64),
+    /// The current value of a gauge.
+    Gauge(f64),
+}
+
+impl Metric {
+    pub fn value(self) -> f64 {
+        match self {
+            Self::Counter(total) => total as f64,
+            Self::Gauge(value) => value,
+        }
+    }
+}
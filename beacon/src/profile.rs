@@ -0,0 +1,476 @@
+//! Aggregate span durations into percentiles and check them against budgets.
+//!
+//! A [`Profiler`] runs a beacon server in the background and collects every
+//! span of the connected application. The resulting [`Profile`] can then be
+//! checked against a [`Budget`], which makes it easy to catch performance
+//! regressions in automated tests.
+use crate::Event;
+use crate::core::time::{Duration, Instant};
+use crate::span::Span;
+
+use futures::StreamExt;
+use tokio::sync::oneshot;
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::thread;
+
+/// The durations of a set of spans, grouped by stage.
+///
+/// Spans of different windows are aggregated together.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    samples: BTreeMap<String, Samples>,
+}
+
+/// The durations of a stage, in the order they were recorded.
+///
+/// They are sorted lazily the first time they are read after a change.
+#[derive(Debug, Clone, Default)]
+struct Samples {
+    durations: Vec<Duration>,
+    sorted: OnceLock<Vec<Duration>>,
+}
+
+impl Samples {
+    fn push(&mut self, duration: Duration) {
+        let _ = self.sorted.take();
+
+        self.durations.push(duration);
+    }
+
+    fn sorted(&self) -> &[Duration] {
+        self.sorted.get_or_init(|| {
+            let mut durations = self.durations.clone();
+            durations.sort_unstable();
+            durations
+        })
+    }
+}
+
+impl PartialEq for Samples {
+    fn eq(&self, other: &Self) -> bool {
+        self.sorted() == other.sorted()
+    }
+}
+
+impl Profile {
+    /// Creates a new empty [`Profile`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if the [`Profile`] contains no spans.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Records the span of the given [`Event`], if any.
+    pub fn push(&mut self, event: &Event) {
+        if let Event::SpanFinished { span, duration, .. } = event {
+            self.record(span, *duration);
+        }
+    }
+
+    /// Records a [`Span`] with the given duration.
+    pub fn record(&mut self, span: &Span, duration: Duration) {
+        self.samples
+            .entry(span.name().to_owned())
+            .or_default()
+            .push(duration);
+    }
+
+    /// Returns the names of the stages in the [`Profile`].
+    pub fn stages(&self) -> impl Iterator<Item = &str> {
+        self.samples.keys().map(String::as_str)
+    }
+
+    /// Returns the durations recorded for the given stage, in ascending order.
+    pub fn samples(&self, stage: &str) -> &[Duration] {
+        self.samples
+            .get(stage)
+            .map(Samples::sorted)
+            .unwrap_or_default()
+    }
+
+    /// Returns the given percentile of the durations of a stage, using the
+    /// nearest-rank method.
+    ///
+    /// The percentile must be in the `0.0..=100.0` range.
+    pub fn percentile(&self, stage: &str, percentile: f32) -> Option<Duration> {
+        let samples = self.samples(stage);
+
+        if samples.is_empty() {
+            return None;
+        }
+
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * samples.len() as f32)
+            .ceil() as usize;
+
+        Some(samples[rank.clamp(1, samples.len()) - 1])
+    }
+
+    /// Checks the [`Profile`] against the given [`Budget`].
+    ///
+    /// A stage without any spans does not meet its budget.
+    pub fn check(&self, budget: &Budget) -> Result<(), Exceeded> {
+        let violations: Vec<_> = budget
+            .limits
+            .iter()
+            .filter_map(|limit| {
+                let actual = self.percentile(&limit.stage, limit.percentile);
+
+                match actual {
+                    Some(actual) if actual <= limit.max => None,
+                    _ => Some(Violation {
+                        limit: limit.clone(),
+                        actual,
+                    }),
+                }
+            })
+            .collect();
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Exceeded { violations })
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:>8} {:>12} {:>12} {:>12} {:>12}",
+            "stage", "count", "p50", "p95", "p99", "max"
+        )?;
+
+        for stage in self.stages() {
+            let samples = self.samples(stage);
+            let percentile = |p| {
+                format!("{:?}", self.percentile(stage, p).unwrap_or_default())
+            };
+
+            writeln!(
+                f,
+                "{stage:<16} {:>8} {:>12} {:>12} {:>12} {:>12}",
+                samples.len(),
+                percentile(50.0),
+                percentile(95.0),
+                percentile(99.0),
+                format!("{:?}", samples.last().copied().unwrap_or_default()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A set of maximum durations for the percentiles of some stages.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Budget {
+    limits: Vec<Limit>,
+}
+
+/// The maximum duration of a percentile of some stage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Limit {
+    /// The name of the stage.
+    pub stage: String,
+    /// The percentile, in the `0.0..=100.0` range.
+    pub percentile: f32,
+    /// The maximum duration.
+    pub max: Duration,
+}
+
+impl Budget {
+    /// Creates a new empty [`Budget`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the given percentile of a stage to the given duration.
+    pub fn percentile(
+        mut self,
+        stage: impl Into<String>,
+        percentile: f32,
+        max: Duration,
+    ) -> Self {
+        self.limits.push(Limit {
+            stage: stage.into(),
+            percentile,
+            max,
+        });
+        self
+    }
+
+    /// Limits the median of a stage to the given duration.
+    pub fn p50(self, stage: impl Into<String>, max: Duration) -> Self {
+        self.percentile(stage, 50.0, max)
+    }
+
+    /// Limits the 95th percentile of a stage to the given duration.
+    pub fn p95(self, stage: impl Into<String>, max: Duration) -> Self {
+        self.percentile(stage, 95.0, max)
+    }
+
+    /// Limits the 99th percentile of a stage to the given duration.
+    pub fn p99(self, stage: impl Into<String>, max: Duration) -> Self {
+        self.percentile(stage, 99.0, max)
+    }
+
+    /// Returns the limits of the [`Budget`].
+    pub fn limits(&self) -> &[Limit] {
+        &self.limits
+    }
+}
+
+/// A [`Limit`] that was not met by a [`Profile`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The [`Limit`] of the [`Budget`].
+    pub limit: Limit,
+    /// The actual duration of the percentile, if the stage had any spans.
+    pub actual: Option<Duration>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Limit {
+            stage,
+            percentile,
+            max,
+        } = &self.limit;
+
+        match self.actual {
+            Some(actual) => write!(
+                f,
+                "p{percentile} of {stage} took {actual:?} (budget: {max:?})"
+            ),
+            None => write!(f, "{stage} has no spans (budget: {max:?})"),
+        }
+    }
+}
+
+/// The error returned when a [`Profile`] exceeds its [`Budget`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exceeded {
+    /// The limits that were not met.
+    pub violations: Vec<Violation>,
+}
+
+impl fmt::Display for Exceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "performance budget exceeded")?;
+
+        for violation in &self.violations {
+            write!(f, "\n  - {violation}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Exceeded {}
+
+/// A beacon server running in the background that collects spans.
+///
+/// Since there can only be a single beacon server per machine, a [`Profiler`]
+/// should be shared by every test of a process. Custom spans can be used as
+/// markers to delimit the spans of a specific run with [`Profiler::until`].
+#[derive(Debug)]
+pub struct Profiler {
+    log: Arc<(Mutex<Log>, Condvar)>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+#[derive(Debug, Default)]
+struct Log {
+    spans: Vec<(Span, Duration)>,
+    is_connected: bool,
+}
+
+impl Profiler {
+    /// Starts a new [`Profiler`].
+    pub fn start() -> Self {
+        let log = Arc::new((Mutex::new(Log::default()), Condvar::new()));
+        let (shutdown, stop) = oneshot::channel();
+
+        let thread = {
+            let log = log.clone();
+
+            thread::spawn(move || serve(log, stop))
+        };
+
+        Self {
+            log,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        }
+    }
+
+    /// Returns true if an application is currently connected.
+    pub fn is_connected(&self) -> bool {
+        self.lock().is_connected
+    }
+
+    /// Returns a [`Profile`] of every span collected so far and clears it.
+    pub fn take(&self) -> Profile {
+        profile(std::mem::take(&mut self.lock().spans))
+    }
+
+    /// Waits until a custom span with the given name is collected, and then
+    /// returns a [`Profile`] of every span collected before it.
+    ///
+    /// The returned spans and the marker are cleared, while any spans after
+    /// the marker are kept.
+    ///
+    /// Returns `None` if the marker is not collected before the timeout.
+    pub fn until(&self, marker: &str, timeout: Duration) -> Option<Profile> {
+        let (_, condvar) = &*self.log;
+        let deadline = Instant::now() + timeout;
+        let mut log = self.lock();
+
+        loop {
+            let position = log.spans.iter().position(|(span, _)| {
+                matches!(span, Span::Custom { name } if name == marker)
+            });
+
+            if let Some(position) = position {
+                let spans: Vec<_> = log.spans.drain(..=position).collect();
+
+                return Some(profile(spans[..position].iter().cloned()));
+            }
+
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+
+            log = condvar
+                .wait_timeout(log, remaining)
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Log> {
+        self.log
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Drop for Profiler {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn profile(spans: impl IntoIterator<Item = (Span, Duration)>) -> Profile {
+    let mut profile = Profile::new();
+
+    for (span, duration) in spans {
+        profile.record(&span, duration);
+    }
+
+    profile
+}
+
+#[tokio::main]
+async fn serve(log: Arc<(Mutex<Log>, Condvar)>, stop: oneshot::Receiver<()>) {
+    let events = crate::run().for_each(|event| {
+        let (log, condvar) = &*log;
+        let mut log = log
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        match event {
+            Event::Connected { .. } => {
+                log.is_connected = true;
+            }
+            Event::Disconnected { .. } => {
+                log.is_connected = false;
+            }
+            Event::SpanFinished { span, duration, .. } => {
+                log.spans.push((span, duration));
+            }
+            Event::AlreadyRunning { .. } => {
+                log::warn!("Another beacon server is already running");
+            }
+            Event::ThemeChanged { .. }
+            | Event::MetricRecorded { .. }
+            | Event::QuitRequested { .. } => {}
+        }
+
+        condvar.notify_all();
+
+        async {}
+    });
+
+    tokio::select! {
+        _ = events => {}
+        _ = stop => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_and_budgets() {
+        let mut profile = Profile::new();
+
+        for millis in (1..=100).rev() {
+            profile.record(&Span::Boot, Duration::from_millis(millis));
+        }
+
+        assert_eq!(
+            profile.percentile("Boot", 50.0),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(
+            profile.percentile("Boot", 95.0),
+            Some(Duration::from_millis(95))
+        );
+        assert_eq!(
+            profile.percentile("Boot", 0.0),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(profile.percentile("Layout", 50.0), None);
+
+        assert!(
+            profile
+                .check(&Budget::new().p95("Boot", Duration::from_millis(95)))
+                .is_ok()
+        );
+
+        let exceeded = profile
+            .check(
+                &Budget::new()
+                    .p99("Boot", Duration::from_millis(90))
+                    .p50("Layout", Duration::from_millis(1)),
+            )
+            .unwrap_err();
+
+        assert_eq!(exceeded.violations.len(), 2);
+        assert_eq!(
+            exceeded.violations[0].actual,
+            Some(Duration::from_millis(99))
+        );
+        assert_eq!(exceeded.violations[1].actual, None);
+
+        profile.record(&Span::Boot, Duration::ZERO);
+
+        assert_eq!(profile.samples("Boot").len(), 101);
+        assert_eq!(profile.samples("Boot")[0], Duration::ZERO);
+    }
+}
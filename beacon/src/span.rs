@@ -34,6 +34,23 @@ pub enum Span {
     },
 }
 
+impl Span {
+    /// Returns the name of the stage of the [`Span`], which is also the
+    /// name given to custom spans.
+    pub fn name(&self) -> &str {
+        match self {
+            Span::Boot => "Boot",
+            Span::Update { .. } => "Update",
+            Span::View { .. } => "View",
+            Span::Layout { .. } => "Layout",
+            Span::Interact { .. } => "Interact",
+            Span::Draw { .. } => "Draw",
+            Span::Present { .. } => "Present",
+            Span::Custom { name } => name,
+        }
+    }
+}
+
 #[derive(
     Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
 )]
@@ -0,0 +1,456 @@
+//! Export spans in the Chrome Trace Event format.
+//!
+//! The resulting traces can be opened with [Perfetto] or `chrome://tracing`.
+//!
+//! [Perfetto]: https://ui.perfetto.dev
+use crate::Event;
+use crate::core::time::{Duration, SystemTime};
+use crate::core::window;
+use crate::span::{Span, present};
+
+use serde_json::{Value, json};
+
+use std::collections::BTreeMap;
+use std::io;
+
+/// A trace that streams the [`Event`]s produced by a beacon server to a writer.
+///
+/// Every connected application is exported as a separate process. Spans
+/// that belong to a window are placed in their own track, while the rest
+/// (boot, updates, and custom spans) share a runtime track. The prepare and
+/// render stages of a present are nested inside of it, split by primitive.
+/// Metrics are exported as counters.
+///
+/// Events are written as soon as they are pushed, in the JSON Array Format of
+/// the Chrome Trace Event specification. The array is closed by [`Trace::finish`];
+/// but Perfetto and `chrome://tracing` also open traces that were never finished.
+#[derive(Debug)]
+pub struct Trace<W: io::Write> {
+    writer: W,
+    start: Option<SystemTime>,
+    process: u64,
+    tracks: BTreeMap<(u64, Track), u64>,
+    events: usize,
+    spans: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Track {
+    Runtime,
+    Window(window::Id),
+}
+
+impl<W: io::Write> Trace<W> {
+    /// Creates a new empty [`Trace`] that writes to the given writer.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(b"[")?;
+
+        Ok(Self {
+            writer,
+            start: None,
+            process: 0,
+            tracks: BTreeMap::new(),
+            events: 0,
+            spans: 0,
+        })
+    }
+
+    /// Returns true if the [`Trace`] contains no spans.
+    pub fn is_empty(&self) -> bool {
+        self.spans == 0
+    }
+
+    /// Writes the given [`Event`] to the [`Trace`].
+    pub fn push(&mut self, event: &Event) -> io::Result<()> {
+        match event {
+            Event::Connected { at, name, .. } => {
+                self.process += 1;
+
+                let ts = self.timestamp(*at);
+
+                self.write(&json!({
+                    "name": "process_name",
+                    "ph": "M",
+                    "pid": self.process,
+                    "ts": ts,
+                    "args": { "name": name },
+                }))?;
+            }
+            Event::SpanFinished { at, duration, span } => {
+                let track = match span {
+                    Span::Boot | Span::Update { .. } | Span::Custom { .. } => {
+                        Track::Runtime
+                    }
+                    Span::View { window }
+                    | Span::Layout { window }
+                    | Span::Interact { window }
+                    | Span::Draw { window }
+                    | Span::Present { window, .. } => Track::Window(*window),
+                };
+
+                let tid = self.track(track)?;
+
+                let start =
+                    at.checked_sub(*duration).unwrap_or(SystemTime::UNIX_EPOCH);
+
+                let ts = self.timestamp(start);
+
+                self.write(&json!({
+                    "name": span.name(),
+                    "cat": "iced",
+                    "ph": "X",
+                    "pid": self.process,
+                    "tid": tid,
+                    "ts": ts,
+                    "dur": micros(*duration),
+                    "args": args(span),
+                }))?;
+
+                self.spans += 1;
+
+                if let Span::Present {
+                    prepare, render, ..
+                } = span
+                {
+                    let end = ts + micros(*duration);
+                    let ts = self.nest("Prepare", prepare, tid, ts, end)?;
+                    let _ = self.nest("Render", render, tid, ts, end)?;
+                }
+            }
+            Event::MetricRecorded { at, name, metric } => {
+                let ts = self.timestamp(*at);
+
+                self.write(&json!({
+                    "name": name,
+                    "cat": "iced",
+                    "ph": "C",
+                    "pid": self.process,
+                    "ts": ts,
+                    "args": { name: metric.value() },
+                }))?;
+            }
+            Event::Disconnected { .. }
+            | Event::ThemeChanged { .. }
+            | Event::QuitRequested { .. }
+            | Event::AlreadyRunning { .. } => {}
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the events written so far.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Closes the [`Trace`] and returns its writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.write_all(b"\n]\n")?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+
+    /// Returns the thread of the given [`Track`] of the current process,
+    /// naming it the first time it is used.
+    fn track(&mut self, track: Track) -> io::Result<u64> {
+        if let Some(tid) = self.tracks.get(&(self.process, track)) {
+            return Ok(*tid);
+        }
+
+        let tid = self.tracks.len() as u64;
+        let _ = self.tracks.insert((self.process, track), tid);
+
+        let name = match track {
+            Track::Runtime => "Runtime".to_owned(),
+            Track::Window(window) => format!("Window {window}"),
+        };
+
+        self.write(&json!({
+            "name": "thread_name",
+            "ph": "M",
+            "pid": self.process,
+            "tid": tid,
+            "args": { "name": name },
+        }))?;
+
+        Ok(tid)
+    }
+
+    /// Writes a slice for the given [`present::Stage`] starting at `ts`,
+    /// with a child slice for each of its primitives; all of them clamped
+    /// to `end`.
+    ///
+    /// Returns the timestamp where the slice ends.
+    fn nest(
+        &mut self,
+        name: &str,
+        stage: &present::Stage,
+        tid: u64,
+        ts: f64,
+        end: f64,
+    ) -> io::Result<f64> {
+        let primitives = [
+            ("Quads", stage.quads),
+            ("Triangles", stage.triangles),
+            ("Shaders", stage.shaders),
+            ("Text", stage.text),
+            ("Images", stage.images),
+        ];
+
+        let total: Duration =
+            primitives.iter().map(|(_, duration)| *duration).sum();
+
+        if total.is_zero() {
+            return Ok(ts);
+        }
+
+        let stage_end = (ts + micros(total)).min(end);
+
+        self.write(&self.slice(name, tid, ts, stage_end - ts))?;
+
+        let mut start = ts;
+
+        for (name, duration) in primitives {
+            if duration.is_zero() || start >= stage_end {
+                continue;
+            }
+
+            let finish = (start + micros(duration)).min(stage_end);
+
+            self.write(&self.slice(name, tid, start, finish - start))?;
+
+            start = finish;
+        }
+
+        Ok(stage_end)
+    }
+
+    fn slice(&self, name: &str, tid: u64, ts: f64, dur: f64) -> Value {
+        json!({
+            "name": name,
+            "cat": "iced",
+            "ph": "X",
+            "pid": self.process,
+            "tid": tid,
+            "ts": ts,
+            "dur": dur,
+        })
+    }
+
+    fn write(&mut self, event: &Value) -> io::Result<()> {
+        let separator: &[u8] = if self.events == 0 { b"\n" } else { b",\n" };
+
+        self.writer.write_all(separator)?;
+        serde_json::to_writer(&mut self.writer, event)?;
+        self.events += 1;
+
+        Ok(())
+    }
+
+    fn timestamp(&mut self, at: SystemTime) -> f64 {
+        let start = *self.start.get_or_insert(at);
+
+        at.duration_since(start).map(micros).unwrap_or_default()
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn args(span: &Span) -> Value {
+    match span {
+        Span::Update {
+            number,
+            message,
+            tasks,
+            subscriptions,
+        } => json!({
+            "number": number,
+            "message": message,
+            "tasks": tasks,
+            "subscriptions": subscriptions,
+        }),
+        Span::Present { layers, .. } => json!({
+            "layers": layers,
+        }),
+        Span::Boot
+        | Span::View { .. }
+        | Span::Layout { .. }
+        | Span::Interact { .. }
+        | Span::Draw { .. }
+        | Span::Custom { .. } => json!({}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Connection, Version};
+
+    use tokio::sync::mpsc;
+
+    fn connected(at: SystemTime, name: &str) -> Event {
+        Event::Connected {
+            connection: Connection {
+                commands: mpsc::channel(1).0,
+            },
+            at,
+            name: name.to_owned(),
+            version: Version::new(0, 14, 0),
+            theme: None,
+            can_time_travel: false,
+        }
+    }
+
+    fn events(trace: Trace<Vec<u8>>) -> Vec<Value> {
+        let bytes = trace.finish().expect("Finish trace");
+
+        serde_json::from_slice(&bytes).expect("Trace must be valid JSON")
+    }
+
+    #[test]
+    fn spans_are_placed_in_tracks() -> io::Result<()> {
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let window = window::Id::unique();
+
+        let mut trace = Trace::new(Vec::new())?;
+        assert!(trace.is_empty());
+
+        trace.push(&connected(start, "counter"))?;
+        assert!(trace.is_empty());
+
+        trace.push(&Event::SpanFinished {
+            at: start + Duration::from_millis(3),
+            duration: Duration::from_millis(2),
+            span: Span::Update {
+                number: 1,
+                message: "Increment".to_owned(),
+                tasks: 0,
+                subscriptions: 1,
+            },
+        })?;
+        assert!(!trace.is_empty());
+
+        trace.push(&Event::SpanFinished {
+            at: start + Duration::from_millis(5),
+            duration: Duration::from_millis(1),
+            span: Span::Layout { window },
+        })?;
+        trace.push(&Event::MetricRecorded {
+            at: start + Duration::from_millis(6),
+            name: "clicks".to_owned(),
+            metric: crate::Metric::Counter(2),
+        })?;
+
+        let events = events(trace);
+
+        assert_eq!(events.len(), 6);
+
+        assert_eq!(events[0]["name"], "process_name");
+        assert_eq!(events[0]["pid"], 1);
+        assert_eq!(events[0]["args"]["name"], "counter");
+
+        assert_eq!(events[1]["name"], "thread_name");
+        assert_eq!(events[1]["args"]["name"], "Runtime");
+
+        assert_eq!(events[2]["name"], "Update");
+        assert_eq!(events[2]["ph"], "X");
+        assert_eq!(events[2]["tid"], 0);
+        assert_eq!(events[2]["ts"], 1_000.0);
+        assert_eq!(events[2]["dur"], 2_000.0);
+        assert_eq!(events[2]["args"]["message"], "Increment");
+
+        assert_eq!(events[3]["args"]["name"], format!("Window {window}"));
+
+        assert_eq!(events[4]["name"], "Layout");
+        assert_eq!(events[4]["tid"], 1);
+        assert_eq!(events[4]["ts"], 4_000.0);
+
+        assert_eq!(events[5]["ph"], "C");
+        assert_eq!(events[5]["args"]["clicks"], 2.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn present_stages_are_nested() -> io::Result<()> {
+        let start = SystemTime::UNIX_EPOCH;
+        let window = window::Id::unique();
+
+        let mut trace = Trace::new(Vec::new())?;
+
+        trace.push(&Event::SpanFinished {
+            at: start + Duration::from_millis(10),
+            duration: Duration::from_millis(10),
+            span: Span::Present {
+                window,
+                prepare: present::Stage {
+                    quads: Duration::from_millis(1),
+                    text: Duration::from_millis(2),
+                    ..present::Stage::default()
+                },
+                render: present::Stage {
+                    quads: Duration::from_millis(4),
+                    images: Duration::from_millis(8),
+                    ..present::Stage::default()
+                },
+                layers: 2,
+            },
+        })?;
+
+        let slices: Vec<_> = events(trace)
+            .into_iter()
+            .filter(|event| event["ph"] == "X")
+            .map(|event| {
+                (
+                    event["name"].as_str().unwrap_or_default().to_owned(),
+                    event["ts"].as_f64().unwrap_or_default() / 1_000.0,
+                    event["dur"].as_f64().unwrap_or_default() / 1_000.0,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            slices,
+            [
+                ("Present".to_owned(), 0.0, 10.0),
+                ("Prepare".to_owned(), 0.0, 3.0),
+                ("Quads".to_owned(), 0.0, 1.0),
+                ("Text".to_owned(), 1.0, 2.0),
+                ("Render".to_owned(), 3.0, 7.0),
+                ("Quads".to_owned(), 3.0, 4.0),
+                ("Images".to_owned(), 7.0, 3.0),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn traces_are_streamed() -> io::Result<()> {
+        let mut trace = Trace::new(Vec::new())?;
+
+        trace.push(&Event::SpanFinished {
+            at: SystemTime::UNIX_EPOCH + Duration::from_secs(1),
+            duration: Duration::from_secs(1),
+            span: Span::Boot,
+        })?;
+
+        // An unfinished trace is an unterminated JSON array
+        let unfinished = String::from_utf8(trace.writer.clone())
+            .expect("Trace must be UTF-8");
+
+        assert!(unfinished.starts_with('['));
+        assert!(unfinished.contains("\"Boot\""));
+
+        let events = events(trace);
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["name"], "Boot");
+        assert_eq!(events[1]["pid"], 0);
+
+        Ok(())
+    }
+}
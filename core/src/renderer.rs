@@ -8,6 +8,8 @@ use crate::{
     Transformation, Vector,
 };
 
+use std::path::Path;
+
 /// A component that can be used by widgets to draw themselves on a screen.
 pub trait Renderer {
     /// Starts recording a new layer.
@@ -75,6 +77,10 @@ pub trait Renderer {
         + Send
         + 'static,
     );
+
+    /// Evicts any cached data loaded from the file at the given path; so
+    /// it is loaded again the next time it is drawn.
+    fn invalidate_asset(&mut self, _path: &Path) {}
 }
 
 /// A polygon with four sides.
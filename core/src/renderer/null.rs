@@ -171,10 +171,29 @@ impl text::Editor for () {
         (0, 0)
     }
 
+    fn cursor_at(
+        &self,
+        _position: (usize, usize),
+        _anchor: Option<(usize, usize)>,
+    ) -> Option<text::editor::Cursor> {
+        None
+    }
+
     fn selection(&self) -> Option<String> {
         None
     }
 
+    fn selection_anchor(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    fn set_cursor(
+        &mut self,
+        _position: (usize, usize),
+        _anchor: Option<(usize, usize)>,
+    ) {
+    }
+
     fn line(&self, _index: usize) -> Option<text::editor::Line<'_>> {
         None
     }
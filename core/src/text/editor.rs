@@ -4,6 +4,7 @@ use crate::text::{LineHeight, Wrapping};
 use crate::{Pixels, Point, Rectangle, Size};
 
 use std::borrow::Cow;
+use std::ops::Range;
 use std::sync::Arc;
 
 /// A component that can be used by widgets to edit multi-line text.
@@ -25,15 +26,73 @@ pub trait Editor: Sized + Default {
     /// Line and column, respectively.
     fn cursor_position(&self) -> (usize, usize);
 
+    /// Returns the [`Cursor`] of the [`Editor`] if it was moved to the given
+    /// position, with the given selection anchor.
+    ///
+    /// Returns `None` if the position is not laid out yet; which can
+    /// happen when it is outside of the visible area.
+    ///
+    /// By default, only the current [`Cursor`] is known.
+    fn cursor_at(
+        &self,
+        position: (usize, usize),
+        anchor: Option<(usize, usize)>,
+    ) -> Option<Cursor> {
+        (position == self.cursor_position()
+            && anchor == self.selection_anchor())
+        .then(|| self.cursor())
+    }
+
     /// Returns the current selected text of the [`Editor`].
     fn selection(&self) -> Option<String>;
 
+    /// Returns the position of the anchor of the current selection of the
+    /// [`Editor`], if any.
+    ///
+    /// Line and column, respectively.
+    ///
+    /// By default, no anchor is known.
+    fn selection_anchor(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    /// Moves the cursor of the [`Editor`] to the given position, selecting
+    /// the text between it and the given anchor, if any.
+    ///
+    /// Positions are line and column, respectively.
+    ///
+    /// By default, the cursor is moved by performing one [`Motion`] at a
+    /// time; which is slow, but only relies on [`Editor::perform`].
+    fn set_cursor(
+        &mut self,
+        position: (usize, usize),
+        anchor: Option<(usize, usize)>,
+    ) {
+        if let Some(anchor) = anchor {
+            move_to(self, anchor, false);
+            move_to(self, position, true);
+        } else {
+            move_to(self, position, false);
+        }
+    }
+
     /// Returns the text of the given line in the [`Editor`], if it exists.
     fn line(&self, index: usize) -> Option<Line<'_>>;
 
     /// Returns the amount of lines in the [`Editor`].
     fn line_count(&self) -> usize;
 
+    /// Returns the index and the bounds of the lines of the [`Editor`] that
+    /// are currently visible, in order.
+    ///
+    /// The bounds of a wrapped line span all of its visual lines.
+    ///
+    /// By default, no lines are reported; which disables any features
+    /// that need them, like box selection.
+    fn visible_lines(&self) -> Vec<(usize, Rectangle)> {
+        Vec::new()
+    }
+
     /// Performs an [`Action`] on the [`Editor`].
     fn perform(&mut self, action: Action);
 
@@ -83,17 +142,43 @@ pub enum Action {
     Click(Point),
     /// Drag the mouse on the [`Editor`] to the given [`Point`].
     Drag(Point),
+    /// Add a new cursor to the [`Editor`] at the given [`Point`].
+    AddCursor(Point),
+    /// Remove all the cursors of the [`Editor`] but the main one.
+    ClearCursors,
+    /// Select the next occurrence of the current selection with a new
+    /// cursor; or the word at the cursor, if nothing is selected.
+    SelectNextOccurrence,
+    /// Select a rectangular area of text between two [`Point`]s, with a
+    /// cursor per line.
+    SelectBox {
+        /// The [`Point`] where the selection starts.
+        from: Point,
+        /// The [`Point`] where the selection ends.
+        to: Point,
+    },
     /// Scroll the [`Editor`] a certain amount of lines.
     Scroll {
         /// The amount of lines to scroll.
         lines: i32,
     },
+    /// Undo the last group of edits.
+    Undo,
+    /// Redo the last undone group of edits.
+    Redo,
+    /// Fold the given range of lines, hiding all of them but the first.
+    Fold(Range<usize>),
+    /// Unfold the lines hidden by the fold at the given line.
+    Unfold(usize),
 }
 
 impl Action {
     /// Returns whether the [`Action`] is an editing action.
+    ///
+    /// [`Action::Undo`] and [`Action::Redo`] are considered editing
+    /// actions, since they may change the text.
     pub fn is_edit(&self) -> bool {
-        matches!(self, Self::Edit(_))
+        matches!(self, Self::Edit(_) | Self::Undo | Self::Redo)
     }
 }
 
@@ -143,6 +228,10 @@ pub enum Motion {
     DocumentStart,
     /// Move to the end of the text.
     DocumentEnd,
+    /// Move to the next match of the active search.
+    NextMatch,
+    /// Move to the previous match of the active search.
+    PreviousMatch,
 }
 
 impl Motion {
@@ -165,13 +254,15 @@ impl Motion {
             | Self::WordLeft
             | Self::Home
             | Self::PageUp
-            | Self::DocumentStart => Direction::Left,
+            | Self::DocumentStart
+            | Self::PreviousMatch => Direction::Left,
             Self::Right
             | Self::Down
             | Self::WordRight
             | Self::End
             | Self::PageDown
-            | Self::DocumentEnd => Direction::Right,
+            | Self::DocumentEnd
+            | Self::NextMatch => Direction::Right,
         }
     }
 }
@@ -232,3 +323,42 @@ impl LineEnding {
         }
     }
 }
+
+/// Moves the cursor of the given [`Editor`] to the given position one
+/// [`Motion`] at a time, stopping if it gets stuck.
+fn move_to<E: Editor>(
+    editor: &mut E,
+    position: (usize, usize),
+    is_selecting: bool,
+) {
+    let action = |motion| {
+        if is_selecting {
+            Action::Select(motion)
+        } else {
+            Action::Move(motion)
+        }
+    };
+
+    let step = |editor: &mut E, motion| {
+        let previous = editor.cursor_position();
+
+        editor.perform(action(motion));
+        editor.cursor_position() != previous
+    };
+
+    let _ = step(editor, Motion::DocumentStart);
+
+    while editor.cursor_position().0 < position.0 {
+        if !step(editor, Motion::Down) {
+            break;
+        }
+    }
+
+    let _ = step(editor, Motion::Home);
+
+    while editor.cursor_position() < position {
+        if !step(editor, Motion::Right) {
+            break;
+        }
+    }
+}
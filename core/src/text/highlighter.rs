@@ -40,6 +40,14 @@ pub trait Highlighter: 'static {
     /// If `change_line` has been called, this will normally be the least index
     /// that changed.
     fn current_line(&self) -> usize;
+
+    /// Returns the ranges of lines that can be folded, as found in the lines
+    /// highlighted so far.
+    ///
+    /// The first line of a range stays visible when it is folded.
+    fn fold_ranges(&self) -> Vec<Range<usize>> {
+        Vec::new()
+    }
 }
 
 /// A highlighter that highlights nothing.
@@ -270,6 +270,18 @@ pub trait Base {
     /// debugging purposes; like displaying performance
     /// metrics or devtools.
     fn palette(&self) -> Option<Palette>;
+
+    /// Returns a variant of the theme that follows the given, already
+    /// generated [`Theme`], if the theme supports it.
+    ///
+    /// This may be used by the runtime to preview palette changes
+    /// live; like a theme editor in devtools.
+    fn preview(&self, _theme: &Theme) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 impl Base for Theme {
@@ -313,6 +325,10 @@ impl Base for Theme {
     fn palette(&self) -> Option<Palette> {
         Some(self.palette())
     }
+
+    fn preview(&self, theme: &Theme) -> Option<Self> {
+        Some(theme.clone())
+    }
 }
 
 /// The default [`Style`] of a built-in [`Theme`].
@@ -21,6 +21,15 @@ impl Id {
 
         Self(Internal::Unique(id))
     }
+
+    /// Returns the name of the [`Id`], if it was not created with
+    /// [`Id::unique`].
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.0 {
+            Internal::Unique(_) => None,
+            Internal::Custom(name) => Some(name),
+        }
+    }
 }
 
 impl From<&'static str> for Id {
@@ -7,6 +7,8 @@ use crate::futures::Subscription;
 
 pub use internal::Span;
 
+use std::path::PathBuf;
+
 #[derive(Debug, Clone, Copy)]
 pub struct Metadata {
     pub name: &'static str,
@@ -23,6 +25,23 @@ pub enum Primitive {
     Text,
 }
 
+/// The kind of a metric recorded with [`counter`] or [`gauge`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    /// A running total, incremented by every recorded amount.
+    Counter,
+    /// A value that is replaced by every recorded sample.
+    Gauge,
+}
+
+/// The most recent values of a metric, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub kind: MetricKind,
+    pub values: Vec<f64>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Command {
     RewindTo { message: usize },
@@ -62,6 +81,42 @@ pub fn layers_rendered(amount: impl FnOnce() -> usize) {
     internal::layers_rendered(amount);
 }
 
+/// Increments the counter with the given name.
+///
+/// A name belongs to the kind of metric it was first recorded as;
+/// incrementing a gauge is ignored.
+pub fn counter(name: &str, amount: u64) {
+    internal::counter(name, amount);
+}
+
+/// Sets the current value of the gauge with the given name.
+///
+/// A name belongs to the kind of metric it was first recorded as;
+/// setting a counter is ignored.
+pub fn gauge(name: &str, value: f64) {
+    internal::gauge(name, value);
+}
+
+/// Returns the recent values of every metric, including the
+/// built-in ones (tasks, subscriptions, and layers).
+pub fn metrics() -> Vec<Series> {
+    internal::metrics()
+}
+
+/// Requests the layer stack of the next frame to be captured and written
+/// to the given path.
+///
+/// The renderer must be built with its `capture` feature for this to
+/// have any effect.
+pub fn capture_frame(path: impl Into<PathBuf>) {
+    internal::capture_frame(path.into());
+}
+
+/// Delivers the capture of the current frame, if it was requested.
+pub fn frame_captured(capture: impl FnOnce() -> String) {
+    internal::frame_captured(capture);
+}
+
 pub fn boot() -> Span {
     internal::boot()
 }
@@ -133,16 +188,20 @@ mod internal {
     use crate::core::window;
     use crate::futures::Subscription;
     use crate::futures::futures::Stream;
-    use crate::{Command, Metadata, Primitive};
+    use crate::{Command, Metadata, MetricKind, Primitive, Series};
 
     use iced_beacon as beacon;
 
+    use beacon::Metric;
     use beacon::client::{self, Client};
     use beacon::span;
     use beacon::span::present;
 
+    use std::collections::{BTreeMap, VecDeque};
+    use std::fs;
+    use std::path::PathBuf;
     use std::sync::atomic::{self, AtomicBool, AtomicUsize};
-    use std::sync::{LazyLock, RwLock};
+    use std::sync::{LazyLock, Mutex, RwLock};
 
     pub fn init(metadata: Metadata) {
         let name = metadata.name.split("::").next().unwrap_or(metadata.name);
@@ -180,15 +239,161 @@ mod internal {
     }
 
     pub fn tasks_spawned(amount: usize) {
+        let _ = record("iced::tasks", Metric::Counter(amount as u64));
+
         log(client::Event::CommandsSpawned(amount));
     }
 
     pub fn subscriptions_tracked(amount: usize) {
+        let _ = record("iced::subscriptions", Metric::Gauge(amount as f64));
+
         log(client::Event::SubscriptionsTracked(amount));
     }
 
     pub fn layers_rendered(amount: impl FnOnce() -> usize) {
-        log(client::Event::LayersRendered(amount()));
+        let amount = amount();
+        let _ = record("iced::layers", Metric::Gauge(amount as f64));
+
+        log(client::Event::LayersRendered(amount));
+    }
+
+    pub fn counter(name: &str, amount: u64) {
+        if let Some(metric) = record(name, Metric::Counter(amount)) {
+            log(client::Event::MetricRecorded {
+                name: name.to_owned(),
+                metric,
+            });
+        }
+    }
+
+    pub fn gauge(name: &str, value: f64) {
+        if let Some(metric) = record(name, Metric::Gauge(value)) {
+            log(client::Event::MetricRecorded {
+                name: name.to_owned(),
+                metric,
+            });
+        }
+    }
+
+    pub fn metrics() -> Vec<Series> {
+        METRICS
+            .lock()
+            .expect("Lock metrics")
+            .iter()
+            .filter(|(_, samples)| !samples.values.is_empty())
+            .map(|(name, samples)| Series {
+                name: name.clone(),
+                kind: samples.kind,
+                values: samples.values.iter().copied().collect(),
+            })
+            .collect()
+    }
+
+    /// Records a new value of a metric and returns it; counters are
+    /// incremented by the given amount.
+    fn record(name: &str, metric: Metric) -> Option<Metric> {
+        aggregate(
+            &mut METRICS.lock().expect("Lock metrics"),
+            name,
+            metric,
+            ENABLED.load(atomic::Ordering::Relaxed),
+        )
+    }
+
+    /// Aggregates a new value of a metric and, if enabled, stores it as a
+    /// sample and returns it.
+    ///
+    /// Counters keep their running total while disabled; so they are
+    /// still accurate once enabled again.
+    ///
+    /// Values of a different kind than the one a metric was first
+    /// recorded as are ignored.
+    fn aggregate(
+        metrics: &mut BTreeMap<String, Samples>,
+        name: &str,
+        metric: Metric,
+        is_enabled: bool,
+    ) -> Option<Metric> {
+        let kind = match metric {
+            Metric::Counter(_) => MetricKind::Counter,
+            Metric::Gauge(_) => MetricKind::Gauge,
+        };
+
+        if !is_enabled && kind == MetricKind::Gauge {
+            return None;
+        }
+
+        if !metrics.contains_key(name) {
+            let _ = metrics.insert(
+                name.to_owned(),
+                Samples {
+                    kind,
+                    total: 0,
+                    values: VecDeque::with_capacity(MAX_SAMPLES),
+                },
+            );
+        }
+
+        let samples = metrics.get_mut(name)?;
+
+        if samples.kind != kind {
+            return None;
+        }
+
+        let metric = match metric {
+            Metric::Counter(amount) => {
+                samples.total = samples.total.saturating_add(amount);
+
+                Metric::Counter(samples.total)
+            }
+            Metric::Gauge(value) => Metric::Gauge(value),
+        };
+
+        if !is_enabled {
+            return None;
+        }
+
+        if samples.values.len() == MAX_SAMPLES {
+            let _ = samples.values.pop_front();
+        }
+
+        samples.values.push_back(metric.value());
+
+        Some(metric)
+    }
+
+    /// The recorded samples of a metric.
+    #[derive(Debug)]
+    struct Samples {
+        kind: MetricKind,
+        total: u64,
+        values: VecDeque<f64>,
+    }
+
+    pub fn capture_frame(path: PathBuf) {
+        *CAPTURE.lock().expect("Lock frame capture") = Some(path);
+        IS_CAPTURING.store(true, atomic::Ordering::Relaxed);
+    }
+
+    pub fn frame_captured(capture: impl FnOnce() -> String) {
+        if !IS_CAPTURING.swap(false, atomic::Ordering::Relaxed) {
+            return;
+        }
+
+        let Some(path) = CAPTURE.lock().expect("Lock frame capture").take()
+        else {
+            return;
+        };
+
+        match fs::write(&path, capture()) {
+            Ok(()) => log::info!("Frame captured to {}", path.display()),
+            Err(error) => {
+                log::error!(
+                    "Failed to write frame capture to {}: {error}",
+                    path.display()
+                );
+            }
+        }
     }
 
     pub fn boot() -> Span {
@@ -333,8 +538,135 @@ mod internal {
         can_time_travel: false,
     });
 
+    static CAPTURE: Mutex<Option<PathBuf>> = Mutex::new(None);
+    static IS_CAPTURING: AtomicBool = AtomicBool::new(false);
+
+    static METRICS: Mutex<BTreeMap<String, Samples>> =
+        Mutex::new(BTreeMap::new());
+
+    /// The maximum amount of values kept for every metric.
+    const MAX_SAMPLES: usize = 120;
+
     static LAST_UPDATE: AtomicUsize = AtomicUsize::new(0);
     static ENABLED: AtomicBool = AtomicBool::new(true);
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn values(metrics: &BTreeMap<String, Samples>, name: &str) -> Vec<f64> {
+            metrics[name].values.iter().copied().collect()
+        }
+
+        #[test]
+        fn counters_are_accumulated() {
+            let mut metrics = BTreeMap::new();
+
+            assert_eq!(
+                aggregate(&mut metrics, "hits", Metric::Counter(2), true),
+                Some(Metric::Counter(2))
+            );
+            assert_eq!(
+                aggregate(&mut metrics, "hits", Metric::Counter(3), true),
+                Some(Metric::Counter(5))
+            );
+
+            // Disabled counters keep counting, without samples
+            assert_eq!(
+                aggregate(&mut metrics, "hits", Metric::Counter(4), false),
+                None
+            );
+            assert_eq!(
+                aggregate(&mut metrics, "hits", Metric::Counter(1), true),
+                Some(Metric::Counter(10))
+            );
+
+            assert_eq!(values(&metrics, "hits"), [2.0, 5.0, 10.0]);
+        }
+
+        #[test]
+        fn gauges_keep_the_last_value() {
+            let mut metrics = BTreeMap::new();
+
+            assert_eq!(
+                aggregate(&mut metrics, "load", Metric::Gauge(0.5), true),
+                Some(Metric::Gauge(0.5))
+            );
+            assert_eq!(
+                aggregate(&mut metrics, "load", Metric::Gauge(0.2), false),
+                None
+            );
+            assert_eq!(
+                aggregate(&mut metrics, "load", Metric::Gauge(0.8), true),
+                Some(Metric::Gauge(0.8))
+            );
+
+            assert_eq!(values(&metrics, "load"), [0.5, 0.8]);
+        }
+
+        #[test]
+        fn counter_totals_are_exact() {
+            let mut metrics = BTreeMap::new();
+            let large = (1 << 53) + 1;
+
+            let _ =
+                aggregate(&mut metrics, "bytes", Metric::Counter(large), true);
+
+            assert_eq!(
+                aggregate(&mut metrics, "bytes", Metric::Counter(1), true),
+                Some(Metric::Counter(large + 1))
+            );
+        }
+
+        #[test]
+        fn kinds_are_not_mixed() {
+            let mut metrics = BTreeMap::new();
+
+            let _ = aggregate(&mut metrics, "hits", Metric::Counter(2), true);
+            let _ = aggregate(&mut metrics, "load", Metric::Gauge(0.5), true);
+
+            assert_eq!(
+                aggregate(&mut metrics, "hits", Metric::Gauge(9.0), true),
+                None
+            );
+            assert_eq!(
+                aggregate(&mut metrics, "load", Metric::Counter(9), true),
+                None
+            );
+
+            assert_eq!(values(&metrics, "hits"), [2.0]);
+            assert_eq!(values(&metrics, "load"), [0.5]);
+            assert_eq!(metrics["hits"].kind, MetricKind::Counter);
+            assert_eq!(metrics["load"].kind, MetricKind::Gauge);
+        }
+
+        #[test]
+        fn samples_are_bounded() {
+            let mut metrics = BTreeMap::new();
+
+            for i in 0..MAX_SAMPLES + 10 {
+                let _ =
+                    aggregate(&mut metrics, "frames", Metric::Counter(1), true);
+
+                let _ = aggregate(
+                    &mut metrics,
+                    "index",
+                    Metric::Gauge(i as f64),
+                    true,
+                );
+            }
+
+            let frames = values(&metrics, "frames");
+            let index = values(&metrics, "index");
+
+            assert_eq!(frames.len(), MAX_SAMPLES);
+            assert_eq!(frames.first(), Some(&11.0));
+            assert_eq!(frames.last(), Some(&(MAX_SAMPLES as f64 + 10.0)));
+
+            assert_eq!(index.len(), MAX_SAMPLES);
+            assert_eq!(index.first(), Some(&10.0));
+        }
+    }
 }
 
 #[cfg(any(not(feature = "enable"), target_arch = "wasm32"))]
@@ -342,7 +674,9 @@ mod internal {
     use crate::core::theme;
     use crate::core::window;
     use crate::futures::Subscription;
-    use crate::{Command, Metadata, Primitive};
+    use crate::{Command, Metadata, Primitive, Series};
+
+    use std::path::PathBuf;
 
     pub fn enable() {}
     pub fn disable() {}
@@ -361,6 +695,18 @@ mod internal {
 
     pub fn layers_rendered(_amount: impl FnOnce() -> usize) {}
 
+    pub fn counter(_name: &str, _amount: u64) {}
+
+    pub fn gauge(_name: &str, _value: f64) {}
+
+    pub fn metrics() -> Vec<Series> {
+        Vec::new()
+    }
+
+    pub fn capture_frame(_path: PathBuf) {}
+
+    pub fn frame_captured(_capture: impl FnOnce() -> String) {}
+
     pub fn boot() -> Span {
         Span
     }
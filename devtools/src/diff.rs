@@ -0,0 +1,138 @@
+//! Diff the pretty [`Debug`] representations of some state.
+//!
+//! Since pretty [`Debug`] output places every field in its own line,
+//! a line diff is enough to find out which fields have changed.
+pub use crate::selector::diff::Line;
+
+use crate::selector;
+
+/// The differences between two states.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diff {
+    pub lines: Vec<Line>,
+    pub fields: Vec<String>,
+}
+
+impl Diff {
+    /// Diffs the `before` and `after` representations of some state.
+    pub fn new(before: &str, after: &str) -> Self {
+        let lines = selector::diff::lines(before, after);
+        let fields = fields(&lines);
+
+        Self { lines, fields }
+    }
+
+    /// Returns true if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        !self.lines.iter().any(Line::is_change)
+    }
+}
+
+/// Finds the paths of the fields containing the changed lines; like
+/// `tasks.0.completed`.
+fn fields(lines: &[Line]) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    let mut indices: Vec<usize> = Vec::new();
+
+    for line in lines {
+        let text = line.text();
+        let content = text.trim_start();
+
+        if content.is_empty() {
+            continue;
+        }
+
+        let indent = text.len() - content.len();
+
+        while stack.last().is_some_and(|(depth, _)| *depth >= indent) {
+            let _ = stack.pop();
+            let _ = indices.pop();
+        }
+
+        let name = match content.split_once(": ") {
+            Some((name, _))
+                if !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_') =>
+            {
+                name.to_owned()
+            }
+            _ if content.starts_with([')', ']', '}']) => String::new(),
+            _ => {
+                // An unnamed element; like an item of a list
+                indices.last_mut().map_or_else(String::new, |index| {
+                    *index += 1;
+                    (*index - 1).to_string()
+                })
+            }
+        };
+
+        if line.is_change() && !name.is_empty() {
+            let path = stack
+                .iter()
+                .map(|(_, name)| name.as_str())
+                .filter(|name| !name.is_empty())
+                .chain([name.as_str()])
+                .collect::<Vec<_>>()
+                .join(".");
+
+            if !fields.contains(&path) {
+                fields.push(path);
+            }
+        }
+
+        if content.ends_with(['{', '[', '(']) {
+            stack.push((indent, name));
+            indices.push(0);
+        }
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BEFORE: &str = r#"State {
+    input: "Buy milk",
+    tasks: [
+        Task {
+            description: "Walk",
+            completed: false,
+        },
+        Task {
+            description: "Cook",
+            completed: false,
+        },
+    ],
+}"#;
+
+    const AFTER: &str = r#"State {
+    input: "",
+    tasks: [
+        Task {
+            description: "Walk",
+            completed: false,
+        },
+        Task {
+            description: "Cook",
+            completed: true,
+        },
+    ],
+}"#;
+
+    #[test]
+    fn it_finds_changed_fields() {
+        let diff = Diff::new(BEFORE, AFTER);
+
+        assert_eq!(diff.fields, ["input", "tasks.1.completed"]);
+        assert_eq!(
+            diff.lines.iter().filter(|line| line.is_change()).count(),
+            4
+        );
+        assert!(Diff::new(AFTER, AFTER).is_empty());
+    }
+}
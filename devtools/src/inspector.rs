@@ -0,0 +1,942 @@
+use crate::core::border;
+use crate::core::layout;
+use crate::core::mouse;
+use crate::core::overlay;
+use crate::core::renderer;
+use crate::core::widget::{self, Operation, Tree, operation, tree};
+use crate::core::window;
+use crate::core::{
+    Background, Border, Clipboard, Color, Element, Event, Layout, Length,
+    Padding, Point, Rectangle, Shell, Size, Vector, Widget,
+};
+use crate::selector::outline::Kind;
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The position of a [`Node`] in a [`Snapshot`], as a list of child indices.
+pub type Path = Vec<usize>;
+
+/// The state of the inspector of every window.
+#[derive(Debug, Default)]
+pub struct Inspector {
+    windows: BTreeMap<window::Id, Inspection>,
+}
+
+/// The state of the inspector of a single window.
+#[derive(Debug, Default)]
+pub struct Inspection {
+    pub snapshot: Option<Snapshot>,
+    pub hovered: Option<Path>,
+    pub selected: Option<Path>,
+}
+
+impl Inspector {
+    pub fn get(&self, window: window::Id) -> Option<&Inspection> {
+        self.windows.get(&window)
+    }
+
+    pub fn get_mut(&mut self, window: window::Id) -> &mut Inspection {
+        self.windows.entry(window).or_default()
+    }
+}
+
+impl Inspection {
+    /// Returns the [`Node`] that should be detailed: the hovered one or,
+    /// otherwise, the selected one.
+    pub fn focus(&self) -> Option<(&Path, &Node)> {
+        let snapshot = self.snapshot.as_ref()?;
+        let path = self.hovered.as_ref().or(self.selected.as_ref())?;
+
+        Some((path, snapshot.root.find(path)?))
+    }
+}
+
+/// The layout of a user interface, annotated with the widget data reported
+/// by an [`Operation`] traversal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub root: Node,
+}
+
+/// A layout node of a [`Snapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// The layout bounds of the widget, translated by any scrollables
+    /// containing it.
+    pub bounds: Rectangle,
+    /// The part of the bounds that is visible, if any.
+    pub visible_bounds: Option<Rectangle>,
+    /// Whether the widget fills the available room along each axis.
+    ///
+    /// The root reports the fluidity of its [`Widget::size`]. Nested widgets
+    /// have it inferred from a probe layout with more room.
+    pub fluid: Option<Size<bool>>,
+    pub kind: Option<Kind>,
+    pub id: Option<String>,
+    pub text: Option<String>,
+    pub children: Vec<Node>,
+}
+
+impl Snapshot {
+    fn capture<Message, Theme, Renderer>(
+        content: &mut Element<'_, Message, Theme, Renderer>,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        grown: Option<&layout::Node>,
+        renderer: &Renderer,
+    ) -> Self
+    where
+        Renderer: renderer::Renderer,
+    {
+        // Widgets only report their layout bounds to an operation; so we
+        // operate on a probe layout where the bounds of every node are unique
+        // and can be traced back to the node unambiguously
+        let mut probe = Probe::default();
+        let node = probe.node(layout, Point::ORIGIN);
+
+        content.as_widget_mut().operate(
+            tree,
+            Layout::new(&node),
+            renderer,
+            &mut operation::black_box(&mut probe),
+        );
+
+        let mut entries = probe.entries.into_iter();
+
+        let mut root = Node::annotate(
+            layout,
+            grown,
+            &mut entries,
+            Vector::ZERO,
+            Rectangle::INFINITE,
+        );
+
+        let size = content.as_widget().size();
+
+        root.fluid =
+            Some(Size::new(size.width.is_fill(), size.height.is_fill()));
+
+        Self { root }
+    }
+}
+
+/// How much room is added to the [`layout::Limits`] of a probe layout.
+const GROWTH: f32 = 100.0;
+
+/// Returns the [`layout::Limits`] of the probe layout used to infer the
+/// fluidity of widgets.
+fn grow(limits: &layout::Limits) -> layout::Limits {
+    layout::Limits::with_compression(
+        limits.min(),
+        limits.max() + Size::new(GROWTH, GROWTH),
+        limits.compression(),
+    )
+}
+
+impl Node {
+    fn annotate(
+        layout: Layout<'_>,
+        grown: Option<&layout::Node>,
+        entries: &mut impl Iterator<Item = Entry>,
+        translation: Vector,
+        viewport: Rectangle,
+    ) -> Self {
+        let entry = entries.next().unwrap_or_default();
+
+        // Fluid widgets take any additional room of the probe layout
+        let fluid = grown.map(|grown| {
+            let fills = |actual: f32, grown: f32| grown - actual >= 1.0;
+
+            Size::new(
+                fills(layout.bounds().width, grown.size().width),
+                fills(layout.bounds().height, grown.size().height),
+            )
+        });
+
+        let bounds = layout.bounds() + translation;
+        let visible_bounds = viewport.intersection(&bounds);
+
+        let (translation, viewport) = match entry.translation {
+            Some(scroll) => {
+                (translation - scroll, visible_bounds.unwrap_or_default())
+            }
+            None => (translation, viewport),
+        };
+
+        let children = layout
+            .children()
+            .enumerate()
+            .map(|(index, child)| {
+                Self::annotate(
+                    child,
+                    grown.and_then(|grown| grown.children().get(index)),
+                    entries,
+                    translation,
+                    viewport,
+                )
+            })
+            .collect();
+
+        Self {
+            bounds,
+            visible_bounds,
+            fluid,
+            kind: entry.kind,
+            id: entry.id,
+            text: entry.text,
+            children,
+        }
+    }
+
+    /// Returns the [`Node`] at the given [`Path`].
+    pub fn find(&self, path: &[usize]) -> Option<&Node> {
+        match path.split_first() {
+            Some((index, rest)) => self.children.get(*index)?.find(rest),
+            None => Some(self),
+        }
+    }
+
+    /// Returns the [`Path`] of the innermost visible [`Node`] containing
+    /// the point.
+    fn hit(&self, point: Point) -> Option<Path> {
+        let contains = |node: &Node| {
+            node.visible_bounds
+                .is_some_and(|bounds| bounds.contains(point))
+        };
+
+        if !contains(self) {
+            return None;
+        }
+
+        let mut path = Vec::new();
+        let mut node = self;
+
+        while let Some((index, child)) = node
+            .children
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, child)| contains(child))
+        {
+            path.push(index);
+            node = child;
+        }
+
+        Some(path)
+    }
+
+    /// Returns the visible bounds of the [`Node`], if they are clipped.
+    pub fn clip(&self) -> Option<Rectangle> {
+        self.visible_bounds
+            .filter(|clip| clip.size() != self.bounds.size())
+    }
+
+    /// Returns the inset of the only child of the [`Node`], if any.
+    pub fn padding(&self) -> Option<Padding> {
+        let [child] = self.children.as_slice() else {
+            return None;
+        };
+
+        // The content of a scrollable is translated
+        if self.kind == Some(Kind::Scrollable) {
+            return None;
+        }
+
+        let padding = Padding {
+            top: child.bounds.y - self.bounds.y,
+            right: self.bounds.x + self.bounds.width
+                - child.bounds.x
+                - child.bounds.width,
+            bottom: self.bounds.y + self.bounds.height
+                - child.bounds.y
+                - child.bounds.height,
+            left: child.bounds.x - self.bounds.x,
+        };
+
+        (padding != Padding::ZERO).then_some(padding)
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Some(kind) => write!(f, "{kind}")?,
+            None => write!(f, "Widget")?,
+        }
+
+        if let Some(id) = &self.id {
+            write!(f, " #{id}")?;
+        }
+
+        write!(f, " {}x{}", self.bounds.width, self.bounds.height)?;
+
+        if let Some(fluid) = self.fluid {
+            write!(
+                f,
+                " [{} x {}]",
+                fluidity(fluid.width),
+                fluidity(fluid.height)
+            )?;
+        }
+
+        if let Some(text) = &self.text {
+            write!(f, " {text:?}")?;
+        }
+
+        if self.visible_bounds.is_none() {
+            write!(f, " hidden")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a compact representation of the fluidity of a [`Node`].
+pub fn fluidity(fluid: bool) -> &'static str {
+    if fluid { "fluid" } else { "fixed" }
+}
+
+/// The widget data reported for a layout node.
+#[derive(Debug, Clone, Default)]
+struct Entry {
+    kind: Option<Kind>,
+    id: Option<String>,
+    text: Option<String>,
+    translation: Option<Vector>,
+}
+
+/// An [`Operation`] that collects the [`Entry`] of every node of a probe
+/// layout.
+#[derive(Debug, Default)]
+struct Probe {
+    bounds: Vec<Rectangle>,
+    entries: Vec<Entry>,
+}
+
+impl Probe {
+    /// Creates the probe of a layout node, whose bounds have the same size
+    /// but are placed at the x coordinate given by its pre-order index.
+    fn node(&mut self, layout: Layout<'_>, parent: Point) -> layout::Node {
+        let bounds = Rectangle::new(
+            Point::new(self.bounds.len() as f32, 0.0),
+            layout.bounds().size(),
+        );
+
+        self.bounds.push(bounds);
+        self.entries.push(Entry::default());
+
+        let children = layout
+            .children()
+            .map(|child| self.node(child, bounds.position()))
+            .collect();
+
+        layout::Node::with_children(bounds.size(), children)
+            .move_to(bounds.position() - Vector::new(parent.x, parent.y))
+    }
+
+    fn entry(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+    ) -> Option<&mut Entry> {
+        let index = bounds.x as usize;
+
+        if self.bounds.get(index) != Some(&bounds) {
+            return None;
+        }
+
+        let entry = &mut self.entries[index];
+
+        if entry.id.is_none() {
+            entry.id = id.and_then(widget::Id::as_str).map(str::to_owned);
+        }
+
+        Some(entry)
+    }
+
+    fn kind(
+        &mut self,
+        kind: Kind,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        text: Option<&str>,
+    ) {
+        let Some(entry) = self.entry(id, bounds) else {
+            return;
+        };
+
+        // Widgets may expose themselves more than once (e.g. a text input
+        // is also focusable); the first kind wins, unless it is focusable
+        if entry.kind.is_none_or(|kind| kind == Kind::Focusable) {
+            entry.kind = Some(kind);
+        }
+
+        if entry.text.is_none() {
+            entry.text = text.map(str::to_owned);
+        }
+    }
+}
+
+impl Operation for Probe {
+    fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation)) {
+        operate(self);
+    }
+
+    fn container(&mut self, id: Option<&widget::Id>, bounds: Rectangle) {
+        self.kind(Kind::Container, id, bounds, None);
+    }
+
+    fn scrollable(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        _content_bounds: Rectangle,
+        translation: Vector,
+        _state: &mut dyn operation::Scrollable,
+    ) {
+        self.kind(Kind::Scrollable, id, bounds, None);
+
+        if let Some(entry) = self.entry(id, bounds) {
+            entry.translation = Some(translation);
+        }
+    }
+
+    fn focusable(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        _state: &mut dyn operation::Focusable,
+    ) {
+        self.kind(Kind::Focusable, id, bounds, None);
+    }
+
+    fn text_input(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        state: &mut dyn operation::TextInput,
+    ) {
+        self.kind(Kind::TextInput, id, bounds, Some(state.text()));
+    }
+
+    fn text(&mut self, id: Option<&widget::Id>, bounds: Rectangle, text: &str) {
+        self.kind(Kind::Text, id, bounds, Some(text));
+    }
+
+    fn custom(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: Rectangle,
+        _state: &mut dyn Any,
+    ) {
+        self.kind(Kind::Custom, id, bounds, None);
+    }
+}
+
+/// A widget that highlights the layout of its contents on hover.
+///
+/// While inspecting, mouse buttons select the hovered widget instead of
+/// reaching the contents.
+pub struct Inspect<'a, Message, Theme, Renderer> {
+    content: Element<'a, Message, Theme, Renderer>,
+    selected: Option<Path>,
+    on_capture: Box<dyn Fn(Snapshot) -> Message + 'a>,
+    on_hover: Box<dyn Fn(Option<Path>) -> Message + 'a>,
+    on_select: Box<dyn Fn(Option<Path>) -> Message + 'a>,
+}
+
+impl<'a, Message, Theme, Renderer> Inspect<'a, Message, Theme, Renderer> {
+    pub fn new(
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+        selected: Option<Path>,
+        on_capture: impl Fn(Snapshot) -> Message + 'a,
+        on_hover: impl Fn(Option<Path>) -> Message + 'a,
+        on_select: impl Fn(Option<Path>) -> Message + 'a,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            selected,
+            on_capture: Box::new(on_capture),
+            on_hover: Box::new(on_hover),
+            on_select: Box::new(on_select),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    snapshot: Option<Snapshot>,
+    hovered: Option<Path>,
+    grown: Option<layout::Node>,
+    is_outdated: bool,
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for Inspect<'_, Message, Theme, Renderer>
+where
+    Renderer: renderer::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        // The probe layout goes first, so the state of the contents
+        // ends up matching the actual layout
+        let grown = self.content.as_widget_mut().layout(
+            &mut tree.children[0],
+            renderer,
+            &grow(limits),
+        );
+
+        let node = self.content.as_widget_mut().layout(
+            &mut tree.children[0],
+            renderer,
+            limits,
+        );
+
+        let state = tree.state.downcast_mut::<State>();
+        state.grown = Some(grown);
+        state.is_outdated = true;
+
+        node
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        self.content.as_widget_mut().operate(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            operation,
+        );
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        match event {
+            Event::Mouse(
+                mouse::Event::ButtonPressed(_)
+                | mouse::Event::ButtonReleased(_),
+            ) if cursor.is_over(layout.bounds()) => {
+                let state = tree.state.downcast_ref::<State>();
+
+                if let Event::Mouse(mouse::Event::ButtonPressed(
+                    mouse::Button::Left,
+                )) = event
+                {
+                    shell.publish((self.on_select)(state.hovered.clone()));
+                }
+
+                shell.capture_event();
+                return;
+            }
+            Event::Window(window::Event::RedrawRequested(_))
+                if tree.state.downcast_ref::<State>().is_outdated =>
+            {
+                let grown = tree.state.downcast_mut::<State>().grown.take();
+
+                let snapshot = Snapshot::capture(
+                    &mut self.content,
+                    &mut tree.children[0],
+                    layout,
+                    grown.as_ref(),
+                    renderer,
+                );
+
+                let state = tree.state.downcast_mut::<State>();
+                state.grown = grown;
+                state.is_outdated = false;
+
+                if state.snapshot.as_ref() != Some(&snapshot) {
+                    state.snapshot = Some(snapshot.clone());
+
+                    shell.publish((self.on_capture)(snapshot));
+                }
+            }
+            // Scrolling moves widgets without changing the layout
+            Event::Mouse(mouse::Event::WheelScrolled { .. })
+            | Event::Keyboard(_)
+            | Event::Touch(_) => {
+                tree.state.downcast_mut::<State>().is_outdated = true;
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                let state = tree.state.downcast_mut::<State>();
+
+                let hovered = state
+                    .snapshot
+                    .as_ref()
+                    .and_then(|snapshot| snapshot.root.hit(*position));
+
+                if state.hovered != hovered {
+                    state.hovered = hovered.clone();
+
+                    shell.publish((self.on_hover)(hovered));
+                    shell.request_redraw();
+                }
+            }
+            Event::Mouse(mouse::Event::CursorLeft) => {
+                let state = tree.state.downcast_mut::<State>();
+
+                if state.hovered.take().is_some() {
+                    shell.publish((self.on_hover)(None));
+                    shell.request_redraw();
+                }
+            }
+            _ => {}
+        }
+
+        self.content.as_widget_mut().update(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::None
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+
+        let state = tree.state.downcast_ref::<State>();
+
+        let Some(snapshot) = &state.snapshot else {
+            return;
+        };
+
+        let selected = self
+            .selected
+            .as_deref()
+            .and_then(|path| snapshot.root.find(path));
+
+        let hovered = state
+            .hovered
+            .as_deref()
+            .and_then(|path| snapshot.root.find(path));
+
+        renderer.with_layer(*viewport, |renderer| {
+            if let Some(node) = hovered {
+                highlight(renderer, node);
+            }
+
+            if let Some(node) = selected {
+                outline(renderer, node.bounds, SELECTED, 2.0);
+            }
+        });
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        self.content.as_widget_mut().overlay(
+            &mut tree.children[0],
+            layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<Inspect<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: renderer::Renderer + 'a,
+{
+    fn from(inspect: Inspect<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(inspect)
+    }
+}
+
+const BOUNDS: Color = Color::from_rgba(0.3, 0.6, 1.0, 0.3);
+const PADDING: Color = Color::from_rgba(0.4, 0.8, 0.4, 0.4);
+const CLIP: Color = Color::from_rgb(1.0, 0.3, 0.3);
+const SELECTED: Color = Color::from_rgb(1.0, 0.6, 0.0);
+
+fn highlight<Renderer>(renderer: &mut Renderer, node: &Node)
+where
+    Renderer: renderer::Renderer,
+{
+    renderer.fill_quad(
+        renderer::Quad {
+            bounds: node.bounds,
+            border: border::width(1).color(BOUNDS.scale_alpha(3.0)),
+            ..renderer::Quad::default()
+        },
+        Background::Color(BOUNDS),
+    );
+
+    if let (Some(padding), [child]) = (node.padding(), node.children.as_slice())
+    {
+        let bounds = node.bounds;
+        let inner = child.bounds;
+
+        let strips = [
+            Rectangle::new(
+                bounds.position(),
+                Size::new(bounds.width, padding.top),
+            ),
+            Rectangle::new(
+                Point::new(bounds.x, inner.y + inner.height),
+                Size::new(bounds.width, padding.bottom),
+            ),
+            Rectangle::new(
+                Point::new(bounds.x, inner.y),
+                Size::new(padding.left, inner.height),
+            ),
+            Rectangle::new(
+                Point::new(inner.x + inner.width, inner.y),
+                Size::new(padding.right, inner.height),
+            ),
+        ];
+
+        for strip in strips {
+            if strip.width > 0.0 && strip.height > 0.0 {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: strip,
+                        ..renderer::Quad::default()
+                    },
+                    Background::Color(PADDING),
+                );
+            }
+        }
+    }
+
+    if let Some(clip) = node.clip() {
+        outline(renderer, clip, CLIP, 1.0);
+    }
+}
+
+fn outline<Renderer>(
+    renderer: &mut Renderer,
+    bounds: Rectangle,
+    color: Color,
+    width: f32,
+) where
+    Renderer: renderer::Renderer,
+{
+    renderer.fill_quad(
+        renderer::Quad {
+            bounds,
+            border: Border {
+                color,
+                width,
+                ..Border::default()
+            },
+            ..renderer::Quad::default()
+        },
+        Background::Color(Color::TRANSPARENT),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::core::Theme;
+    use crate::core::widget::operation::scrollable::AbsoluteOffset;
+    use crate::widget::{column, container, scrollable, space, text};
+
+    fn snapshot() -> Snapshot {
+        let mut content: Element<'_, (), Theme, ()> = column![
+            container(text("Hello").width(100).height(20)).padding(10),
+            scrollable(
+                column![
+                    space().width(Length::Fill).height(50),
+                    text("Scrolled").width(100).height(20)
+                ]
+                .width(Length::Fill)
+            )
+            .id("list")
+            .height(40),
+        ]
+        .width(Length::Fill)
+        .into();
+
+        let mut tree = Tree::new(&content);
+        let limits = layout::Limits::new(Size::ZERO, Size::new(200.0, 200.0));
+
+        let grown =
+            content
+                .as_widget_mut()
+                .layout(&mut tree, &(), &grow(&limits));
+        let node = content.as_widget_mut().layout(&mut tree, &(), &limits);
+
+        content.as_widget_mut().operate(
+            &mut tree,
+            Layout::new(&node),
+            &(),
+            &mut operation::scrollable::scroll_to(
+                widget::Id::new("list"),
+                AbsoluteOffset { x: 0.0, y: 30.0 },
+            ),
+        );
+
+        Snapshot::capture(
+            &mut content,
+            &mut tree,
+            Layout::new(&node),
+            Some(&grown),
+            &(),
+        )
+    }
+
+    #[test]
+    fn annotate() {
+        let snapshot = snapshot();
+        let root = &snapshot.root;
+
+        assert_eq!(root.kind, Some(Kind::Container));
+        assert_eq!(root.fluid, Some(Size::new(true, false)));
+
+        // A container and its contents may share their layout bounds
+        let container = &root.children[0];
+        let hello = &container.children[0];
+
+        assert_eq!(container.kind, Some(Kind::Container));
+        assert_eq!(container.fluid, Some(Size::new(false, false)));
+        assert_eq!(
+            container.bounds,
+            Rectangle::new(Point::ORIGIN, Size::new(120.0, 40.0))
+        );
+
+        assert_eq!(hello.kind, Some(Kind::Text));
+        assert_eq!(hello.text.as_deref(), Some("Hello"));
+        assert_eq!(hello.fluid, Some(Size::new(false, false)));
+
+        let list = &root.children[1];
+        let [space, scrolled] = list.children[0].children.as_slice() else {
+            panic!("scrollable content should have two children");
+        };
+
+        assert_eq!(list.kind, Some(Kind::Scrollable));
+        assert_eq!(list.id.as_deref(), Some("list"));
+
+        // Widgets without an operation still have their fluidity inferred
+        assert_eq!(space.kind, None);
+        assert_eq!(space.fluid, Some(Size::new(true, false)));
+        assert_eq!(
+            space.clip(),
+            Some(Rectangle::new(
+                Point::new(0.0, 40.0),
+                Size::new(200.0, 20.0)
+            ))
+        );
+
+        assert_eq!(scrolled.text.as_deref(), Some("Scrolled"));
+        assert_eq!(
+            scrolled.bounds,
+            Rectangle::new(Point::new(0.0, 60.0), Size::new(100.0, 20.0))
+        );
+        assert_eq!(scrolled.clip(), None);
+    }
+
+    #[test]
+    fn hit() {
+        let snapshot = snapshot();
+        let root = &snapshot.root;
+
+        assert_eq!(root.hit(Point::new(50.0, 20.0)), Some(vec![0, 0]));
+        assert_eq!(root.hit(Point::new(5.0, 5.0)), Some(vec![0]));
+        assert_eq!(root.hit(Point::new(150.0, 20.0)), Some(vec![]));
+        assert_eq!(root.hit(Point::new(250.0, 20.0)), None);
+
+        // Scrolled widgets are hit where they are drawn
+        assert_eq!(root.hit(Point::new(50.0, 70.0)), Some(vec![1, 0, 1]));
+        assert_eq!(root.hit(Point::new(50.0, 50.0)), Some(vec![1, 0, 0]));
+        assert_eq!(root.hit(Point::new(150.0, 70.0)), Some(vec![1, 0]));
+    }
+
+    #[test]
+    fn padding() {
+        let snapshot = snapshot();
+        let root = &snapshot.root;
+
+        assert_eq!(root.children[0].padding(), Some(Padding::new(10.0)));
+        assert_eq!(root.children[0].children[0].padding(), None);
+
+        // The content of a scrollable is not inset, but translated
+        assert_eq!(root.children[1].padding(), None);
+        assert_eq!(root.padding(), None);
+    }
+}
@@ -3,28 +3,38 @@ use iced_debug as debug;
 use iced_program as program;
 use iced_program::runtime;
 use iced_program::runtime::futures;
+use iced_selector as selector;
 use iced_widget as widget;
 use iced_widget::core;
 
 mod comet;
+#[cfg_attr(not(feature = "time-travel"), allow(dead_code))]
+mod diff;
+mod inspector;
+mod palette;
 mod time_machine;
 
+use crate::core::alignment::Vertical::Bottom;
 use crate::core::border;
 use crate::core::keyboard;
+use crate::core::theme::Palette;
+use crate::core::theme::palette::Pair;
 use crate::core::theme::{self, Theme};
-use crate::core::time::seconds;
+use crate::core::time::{Duration, Instant, seconds};
 use crate::core::window;
 use crate::core::{
     Alignment::Center, Color, Element, Font, Length::Fill, Settings,
 };
+use crate::diff::Diff;
 use crate::futures::Subscription;
+use crate::inspector::{Inspect, Inspection, Inspector};
 use crate::program::Program;
 use crate::program::message;
 use crate::runtime::task::{self, Task};
 use crate::time_machine::TimeMachine;
 use crate::widget::{
     bottom_right, button, center, column, container, opaque, row, scrollable,
-    space, stack, text, themer,
+    slider, space, stack, text, text_input, themer,
 };
 
 use std::fmt;
@@ -123,9 +133,23 @@ where
     state: P::State,
     show_notification: bool,
     time_machine: TimeMachine<P>,
+    panel: Option<Panel>,
+    inspector: Inspector,
+    editor: Option<palette::Editor>,
+    history: History,
+    metrics: Vec<debug::Series>,
+    last_sample: Option<Instant>,
+    #[cfg(feature = "capture")]
+    capture: Option<String>,
     mode: Mode,
 }
 
+#[derive(Default)]
+struct History {
+    filter: String,
+    selected: Option<(usize, Option<Diff>)>,
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     HideNotification,
@@ -134,6 +158,36 @@ pub enum Message {
     InstallComet,
     Installing(comet::install::Result),
     CancelSetup,
+    TogglePanel,
+    ShowPanel(Panel),
+    Inspected(window::Id, inspector::Snapshot),
+    Hovered(window::Id, Option<inspector::Path>),
+    Selected(window::Id, Option<inspector::Path>),
+    PaletteChanged(Palette),
+    PaletteTyped(Palette, palette::Field, String),
+    ResetPalette,
+    ExportPalette(Export),
+    FilterHistory(String),
+    SelectMessage(usize),
+    RewindTo(usize),
+    GoLive,
+    SampleMetrics(Instant),
+    #[cfg(feature = "capture")]
+    CaptureFrame,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Inspector,
+    Theme,
+    History,
+    Metrics,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Export {
+    Rust,
+    Toml,
 }
 
 enum Mode {
@@ -163,6 +217,14 @@ where
                 mode: Mode::Hidden,
                 show_notification: true,
                 time_machine: TimeMachine::new(),
+                panel: None,
+                inspector: Inspector::default(),
+                editor: None,
+                history: History::default(),
+                metrics: Vec::new(),
+                last_sample: None,
+                #[cfg(feature = "capture")]
+                capture: None,
             },
             Task::batch([task::blocking(|mut sender| {
                 thread::sleep(seconds(2));
@@ -264,6 +326,125 @@ where
                 Message::CancelSetup => {
                     self.mode = Mode::Hidden;
 
+                    Task::none()
+                }
+                Message::TogglePanel => {
+                    self.panel = if self.panel.is_some() {
+                        None
+                    } else {
+                        Some(Panel::Inspector)
+                    };
+
+                    Task::none()
+                }
+                Message::ShowPanel(panel) => {
+                    self.panel = Some(panel);
+
+                    Task::none()
+                }
+                Message::Inspected(window, snapshot) => {
+                    let inspection = self.inspector.get_mut(window);
+
+                    if inspection
+                        .selected
+                        .as_ref()
+                        .is_some_and(|path| snapshot.root.find(path).is_none())
+                    {
+                        inspection.selected = None;
+                    }
+
+                    inspection.snapshot = Some(snapshot);
+
+                    Task::none()
+                }
+                Message::Hovered(window, path) => {
+                    self.inspector.get_mut(window).hovered = path;
+
+                    Task::none()
+                }
+                Message::Selected(window, path) => {
+                    self.inspector.get_mut(window).selected = path;
+
+                    Task::none()
+                }
+                Message::PaletteChanged(palette) => {
+                    let editor = self
+                        .editor
+                        .get_or_insert_with(|| palette::Editor::new(palette));
+
+                    for field in palette::Field::ALL {
+                        if field.get(&editor.palette()) != field.get(&palette) {
+                            editor.set(*field, field.get(&palette));
+                        }
+                    }
+
+                    Task::none()
+                }
+                Message::PaletteTyped(palette, field, value) => {
+                    self.editor
+                        .get_or_insert_with(|| palette::Editor::new(palette))
+                        .type_hex(field, value);
+
+                    Task::none()
+                }
+                Message::ResetPalette => {
+                    self.editor = None;
+
+                    Task::none()
+                }
+                Message::ExportPalette(export) => {
+                    let Some(editor) = &self.editor else {
+                        return Task::none();
+                    };
+
+                    runtime::clipboard::write(match export {
+                        Export::Rust => palette::to_rust(&editor.palette()),
+                        Export::Toml => palette::to_toml(&editor.palette()),
+                    })
+                }
+                Message::FilterHistory(filter) => {
+                    self.history.filter = filter;
+
+                    Task::none()
+                }
+                Message::SelectMessage(index) => {
+                    self.history.selected =
+                        Some((index, self.time_machine.diff(program, index)));
+
+                    Task::none()
+                }
+                Message::RewindTo(index) => {
+                    self.time_machine.rewind(program, index + 1);
+
+                    Task::none()
+                }
+                Message::GoLive => {
+                    self.time_machine.go_to_present();
+
+                    Task::none()
+                }
+                Message::SampleMetrics(now) => {
+                    if self.last_sample.is_none_or(|last_sample| {
+                        now.duration_since(last_sample) >= SAMPLE_INTERVAL
+                    }) {
+                        self.metrics = debug::metrics();
+                        self.last_sample = Some(now);
+                    }
+
+                    Task::none()
+                }
+                #[cfg(feature = "capture")]
+                Message::CaptureFrame => {
+                    let timestamp = core::time::SystemTime::now()
+                        .duration_since(core::time::SystemTime::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis();
+
+                    let path = format!("frame-{timestamp}.json");
+
+                    debug::capture_frame(&path);
+                    self.capture = Some(path);
+
                     Task::none()
                 }
             },
@@ -279,6 +460,8 @@ where
                 debug::tasks_spawned(task.units());
                 span.finish();
 
+                self.time_machine.checkpoint(program, &self.state);
+
                 if self.time_machine.is_rewinding() {
                     debug::disable();
                 }
@@ -318,12 +501,98 @@ where
             }
         };
 
-        let theme = || {
-            program
+        let theme = || match &self.editor {
+            Some(editor) => Some(editor.theme().clone()),
+            None => program
                 .theme(state, window)
                 .as_ref()
                 .and_then(theme::Base::palette)
-                .map(|palette| Theme::custom("iced devtools", palette))
+                .map(|palette| Theme::custom("iced devtools", palette)),
+        };
+
+        let view = if let Some(panel) = self.panel {
+            let inspection = self.inspector.get(window);
+
+            let (view, content) = match panel {
+                Panel::Inspector => (
+                    Inspect::new(
+                        view,
+                        inspection
+                            .and_then(|inspection| inspection.selected.clone()),
+                        move |snapshot| {
+                            Event::Message(Message::Inspected(window, snapshot))
+                        },
+                        move |path| {
+                            Event::Message(Message::Hovered(window, path))
+                        },
+                        move |path| {
+                            Event::Message(Message::Selected(window, path))
+                        },
+                    )
+                    .into(),
+                    {
+                        let inspection = self::inspection(window, inspection);
+
+                        #[cfg(feature = "capture")]
+                        let inspection = column![
+                            capture(self.capture.as_deref()),
+                            inspection
+                        ]
+                        .spacing(10)
+                        .into();
+
+                        inspection
+                    },
+                ),
+                Panel::Theme => {
+                    let palette = self.editor.as_ref().map_or_else(
+                        || {
+                            theme::Base::palette(
+                                &self.base_theme(program, window),
+                            )
+                            .unwrap_or(Palette::LIGHT)
+                        },
+                        palette::Editor::palette,
+                    );
+
+                    (view, palette_editor(palette, self.editor.as_ref()))
+                }
+                Panel::History => {
+                    let filter = self.history.filter.to_lowercase();
+
+                    let messages = self
+                        .time_machine
+                        .descriptions()
+                        .iter()
+                        .map(String::as_str)
+                        .enumerate()
+                        .rev()
+                        .filter(|(_, message)| {
+                            variant(message).to_lowercase().contains(&filter)
+                        })
+                        .take(MAX_MESSAGES)
+                        .collect();
+
+                    (
+                        view,
+                        history(
+                            &self.history,
+                            messages,
+                            self.time_machine.is_rewinding(),
+                        ),
+                    )
+                }
+                Panel::Metrics => (view, metrics(&self.metrics)),
+            };
+
+            let panel = themer(
+                theme(),
+                self::panel(panel, content).map(Event::Message),
+            );
+
+            row![view, panel].into()
+        } else {
+            view
         };
 
         let setup = if let Mode::Setup(setup) = &self.mode {
@@ -351,7 +620,12 @@ where
 
         let notification = self
             .show_notification
-            .then(|| text("Press F12 to open debug metrics"))
+            .then(|| {
+                text(
+                    "Press F12 to open debug metrics \
+                    or Shift+F12 to inspect the user interface",
+                )
+            })
             .or_else(|| {
                 debug::is_stale().then(|| {
                     text(
@@ -382,7 +656,12 @@ where
         debug::subscriptions_tracked(subscription.units());
 
         let hotkeys =
-            futures::keyboard::on_key_press(|key, _modifiers| match key {
+            futures::keyboard::on_key_press(|key, modifiers| match key {
+                keyboard::Key::Named(keyboard::key::Named::F12)
+                    if modifiers.shift() =>
+                {
+                    Some(Message::TogglePanel)
+                }
                 keyboard::Key::Named(keyboard::key::Named::F12) => {
                     Some(Message::ToggleComet)
                 }
@@ -392,11 +671,30 @@ where
 
         let commands = debug::commands().map(Event::Command);
 
-        Subscription::batch([subscription, hotkeys, commands])
+        let metrics = if self.panel == Some(Panel::Metrics) {
+            runtime::window::frames()
+                .map(Message::SampleMetrics)
+                .map(Event::Message)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([subscription, hotkeys, commands, metrics])
     }
 
     pub fn theme(&self, program: &P, window: window::Id) -> Option<P::Theme> {
-        program.theme(self.state(), window)
+        let Some(editor) = &self.editor else {
+            return program.theme(self.state(), window);
+        };
+
+        theme::Base::preview(&self.base_theme(program, window), editor.theme())
+            .or_else(|| program.theme(self.state(), window))
+    }
+
+    fn base_theme(&self, program: &P, window: window::Id) -> P::Theme {
+        program.theme(self.state(), window).unwrap_or_else(|| {
+            <P::Theme as theme::Base>::default(theme::Mode::None)
+        })
     }
 
     pub fn style(&self, program: &P, theme: &P::Theme) -> theme::Style {
@@ -552,6 +850,628 @@ where
     .into()
 }
 
+fn inspection<'a, Renderer>(
+    window: window::Id,
+    inspection: Option<&'a Inspection>,
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    let Some(snapshot) =
+        inspection.and_then(|inspection| inspection.snapshot.as_ref())
+    else {
+        return text("Waiting for the next frame...").into();
+    };
+
+    let details = inspection.and_then(Inspection::focus).map_or_else(
+        || column![text("Hover or click a widget to inspect it.").size(12)],
+        |(_path, node)| {
+            let property = |name, value: String| {
+                row![
+                    text(name).size(12).width(70),
+                    text(value).size(12).font(Font::MONOSPACE)
+                ]
+            };
+
+            let bounds = node.bounds;
+
+            column![
+                text(node.to_string()).size(14).font(Font::MONOSPACE),
+                property("Position", format!("{}, {}", bounds.x, bounds.y)),
+                property(
+                    "Size",
+                    format!("{} x {}", bounds.width, bounds.height)
+                ),
+            ]
+            .push(node.fluid.map(|fluid| {
+                property(
+                    "Sizing",
+                    format!(
+                        "{} x {}",
+                        inspector::fluidity(fluid.width),
+                        inspector::fluidity(fluid.height)
+                    ),
+                )
+            }))
+            .push(node.padding().map(|padding| {
+                property(
+                    "Padding",
+                    format!(
+                        "{} {} {} {}",
+                        padding.top,
+                        padding.right,
+                        padding.bottom,
+                        padding.left
+                    ),
+                )
+            }))
+            .push(node.clip().map(|clip| {
+                property(
+                    "Clip",
+                    format!(
+                        "{}, {} ({} x {})",
+                        clip.x, clip.y, clip.width, clip.height
+                    ),
+                )
+            }))
+            .spacing(4)
+        },
+    );
+
+    let mut rows = Vec::new();
+    let mut path = Vec::new();
+
+    outline(window, inspection, &snapshot.root, &mut path, &mut rows);
+
+    column![
+        container(details)
+            .width(Fill)
+            .padding(8)
+            .style(container::bordered_box),
+        scrollable(column(rows)).width(Fill).height(Fill),
+    ]
+    .spacing(10)
+    .into()
+}
+
+#[cfg(feature = "capture")]
+fn capture<'a, Renderer>(
+    last: Option<&'a str>,
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    row![
+        button(text("Capture frame").size(12))
+            .on_press(Message::CaptureFrame)
+            .style(button::secondary),
+        last.map(|path| text!("Saved to {path}").size(12)),
+    ]
+    .spacing(10)
+    .align_y(Center)
+    .into()
+}
+
+const PANEL_WIDTH: f32 = 320.0;
+
+fn panel<'a, Renderer>(
+    current: Panel,
+    content: Element<'a, Message, Theme, Renderer>,
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    let tab = |label, panel| {
+        button(text(label).size(14))
+            .on_press(Message::ShowPanel(panel))
+            .style(if panel == current {
+                button::primary
+            } else {
+                button::text
+            })
+    };
+
+    let header = row![
+        tab("Layout", Panel::Inspector),
+        tab("Theme", Panel::Theme),
+        tab("History", Panel::History),
+        tab("Metrics", Panel::Metrics),
+        space::horizontal(),
+        button(text("Close").size(12))
+            .on_press(Message::TogglePanel)
+            .style(button::secondary),
+    ]
+    .spacing(5)
+    .align_y(Center);
+
+    container(column![header, content].spacing(10))
+        .width(PANEL_WIDTH)
+        .height(Fill)
+        .padding(10)
+        .style(container::dark)
+        .into()
+}
+
+fn palette_editor<'a, Renderer>(
+    palette: Palette,
+    editor: Option<&palette::Editor>,
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    let fields = palette::Field::ALL.iter().map(|&field| {
+        let color = field.get(&palette);
+        let [r, g, b, _] = color.into_rgba8();
+
+        let channel = move |name, value: u8, apply: fn(&mut [u8; 3], u8)| {
+            row![
+                text(name).size(12).width(12).font(Font::MONOSPACE),
+                slider(0..=u8::MAX, value, move |value| {
+                    let mut rgb = [r, g, b];
+                    apply(&mut rgb, value);
+
+                    let mut palette = palette;
+                    field.set(
+                        &mut palette,
+                        Color::from_rgba8(rgb[0], rgb[1], rgb[2], color.a),
+                    );
+
+                    Message::PaletteChanged(palette)
+                }),
+                text(value).size(12).width(24).font(Font::MONOSPACE),
+            ]
+            .spacing(5)
+            .align_y(Center)
+        };
+
+        let hex = editor
+            .map_or_else(|| palette::hex(color), |editor| editor.hex(field));
+
+        column![
+            row![
+                swatch(Pair::new(color, palette.text), 24.0),
+                text(field.name()).size(14).width(Fill),
+                text_input("#rrggbb", &hex)
+                    .on_input(move |value| {
+                        Message::PaletteTyped(palette, field, value)
+                    })
+                    .size(12)
+                    .font(Font::MONOSPACE)
+                    .width(90),
+            ]
+            .spacing(10)
+            .align_y(Center),
+            channel("R", r, |rgb, value| rgb[0] = value),
+            channel("G", g, |rgb, value| rgb[1] = value),
+            channel("B", b, |rgb, value| rgb[2] = value),
+        ]
+        .spacing(4)
+        .into()
+    });
+
+    let extended = editor.map_or_else(
+        || theme::palette::Extended::generate(palette),
+        |editor| *editor.extended(),
+    );
+
+    let scale = |name, pairs: &[Pair]| -> Element<'a, _, _, Renderer> {
+        row![text(name).size(12).width(80)]
+            .extend(pairs.iter().map(|&pair| swatch(pair, 30.0)))
+            .spacing(4)
+            .align_y(Center)
+            .into()
+    };
+
+    let preview = column![
+        text("Extended palette").size(14),
+        scale(
+            "background",
+            &[
+                extended.background.weakest,
+                extended.background.weak,
+                extended.background.base,
+                extended.background.strong,
+                extended.background.strongest,
+            ],
+        ),
+        scale(
+            "primary",
+            &[
+                extended.primary.weak,
+                extended.primary.base,
+                extended.primary.strong,
+            ],
+        ),
+        scale(
+            "secondary",
+            &[
+                extended.secondary.weak,
+                extended.secondary.base,
+                extended.secondary.strong,
+            ],
+        ),
+        scale(
+            "success",
+            &[
+                extended.success.weak,
+                extended.success.base,
+                extended.success.strong,
+            ],
+        ),
+        scale(
+            "warning",
+            &[
+                extended.warning.weak,
+                extended.warning.base,
+                extended.warning.strong,
+            ],
+        ),
+        scale(
+            "danger",
+            &[
+                extended.danger.weak,
+                extended.danger.base,
+                extended.danger.strong,
+            ],
+        ),
+    ]
+    .spacing(4);
+
+    let export = |label, format| {
+        button(text(label).size(12))
+            .on_press_maybe(editor.map(|_| Message::ExportPalette(format)))
+            .style(button::secondary)
+    };
+
+    let actions = row![
+        export("Copy Rust", Export::Rust),
+        export("Copy TOML", Export::Toml),
+        space::horizontal(),
+        button(text("Reset").size(12))
+            .on_press_maybe(editor.map(|_| Message::ResetPalette))
+            .style(button::danger),
+    ]
+    .spacing(5);
+
+    let code = container(
+        text(palette::to_rust(&palette))
+            .size(12)
+            .font(Font::MONOSPACE),
+    )
+    .width(Fill)
+    .padding(8)
+    .style(container::bordered_box);
+
+    scrollable(
+        column(fields)
+            .push(preview)
+            .push(actions)
+            .push(code)
+            .spacing(15),
+    )
+    .spacing(5)
+    .height(Fill)
+    .into()
+}
+
+fn history<'a, Renderer>(
+    history: &'a History,
+    messages: Vec<(usize, &'a str)>,
+    is_rewinding: bool,
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    if !cfg!(feature = "time-travel") {
+        return text("Enable the `time-travel` feature to record messages.")
+            .size(12)
+            .into();
+    }
+
+    let filter = text_input("Filter by variant...", &history.filter)
+        .on_input(Message::FilterHistory)
+        .size(12);
+
+    let log = column(messages.into_iter().map(|(index, message)| {
+        let is_selected = history
+            .selected
+            .as_ref()
+            .is_some_and(|(selected, _)| *selected == index);
+
+        button(
+            text!("{index:>4} {message}")
+                .size(12)
+                .font(Font::MONOSPACE)
+                .wrapping(text::Wrapping::None),
+        )
+        .width(Fill)
+        .padding([2, 4])
+        .on_press(Message::SelectMessage(index))
+        .style(if is_selected {
+            button::primary
+        } else {
+            button::text
+        })
+        .into()
+    }));
+
+    let details = history.selected.as_ref().map(|(index, diff)| {
+        let actions = row![
+            text!("Message #{index}").size(14),
+            space::horizontal(),
+            button(text("Rewind").size(12))
+                .on_press(Message::RewindTo(*index))
+                .style(button::secondary),
+        ]
+        .push(is_rewinding.then(|| {
+            button(text("Go live").size(12))
+                .on_press(Message::GoLive)
+                .style(button::success)
+        }))
+        .spacing(5)
+        .align_y(Center);
+
+        let diff: Element<'_, _, _, _> = match diff {
+            None => text(
+                "Call `debug_state` on your application \
+                to diff its state.",
+            )
+            .size(12)
+            .into(),
+            Some(diff) if diff.is_empty() => {
+                text("The state did not change.").size(12).into()
+            }
+            Some(diff) => column![
+                text!("Changed: {}", diff.fields.join(", ")).size(12),
+                scrollable(
+                    column(changes(diff).map(|line| {
+                        match line {
+                            None => text("...")
+                                .size(12)
+                                .font(Font::MONOSPACE)
+                                .style(text::secondary)
+                                .into(),
+                            Some(line) => {
+                                let (prefix, content, style): (
+                                    _,
+                                    _,
+                                    fn(&Theme) -> text::Style,
+                                ) = match line {
+                                    diff::Line::Same(content) => {
+                                        (' ', content, text::default)
+                                    }
+                                    diff::Line::Removed(content) => {
+                                        ('-', content, text::danger)
+                                    }
+                                    diff::Line::Added(content) => {
+                                        ('+', content, text::success)
+                                    }
+                                };
+
+                                text!("{prefix} {content}")
+                                    .size(12)
+                                    .font(Font::MONOSPACE)
+                                    .wrapping(text::Wrapping::None)
+                                    .style(style)
+                                    .into()
+                            }
+                        }
+                    }))
+                    .padding(5),
+                )
+                .direction(scrollable::Direction::Both {
+                    vertical: scrollable::Scrollbar::default(),
+                    horizontal: scrollable::Scrollbar::default(),
+                })
+                .width(Fill)
+                .height(200),
+            ]
+            .spacing(5)
+            .into(),
+        };
+
+        container(column![actions, diff].spacing(8))
+            .width(Fill)
+            .padding(8)
+            .style(container::bordered_box)
+    });
+
+    column![filter]
+        .push(details)
+        .push(scrollable(log).width(Fill).height(Fill))
+        .spacing(10)
+        .into()
+}
+
+fn metrics<'a, Renderer>(
+    metrics: &'a [debug::Series],
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    if metrics.is_empty() {
+        return text(
+            "No metrics recorded yet. Use `debug::counter` or \
+            `debug::gauge` to track your own!",
+        )
+        .size(12)
+        .into();
+    }
+
+    scrollable(
+        column(metrics.iter().map(|series| {
+            let last = series.values.last().copied().unwrap_or_default();
+
+            column![
+                row![
+                    text(&series.name).size(12).font(Font::MONOSPACE),
+                    space::horizontal(),
+                    text!(
+                        "{last} {kind}",
+                        kind = match series.kind {
+                            debug::MetricKind::Counter => "total",
+                            debug::MetricKind::Gauge => "now",
+                        }
+                    )
+                    .size(12)
+                    .font(Font::MONOSPACE),
+                ],
+                sparkline(&series.values),
+            ]
+            .spacing(4)
+            .into()
+        }))
+        .spacing(15),
+    )
+    .height(Fill)
+    .into()
+}
+
+fn sparkline<'a, Renderer>(
+    values: &[f64],
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    const HEIGHT: f32 = 40.0;
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    let bars = values.iter().map(|value| {
+        let height = if range > 0.0 {
+            ((value - min) / range) as f32 * (HEIGHT - 1.0) + 1.0
+        } else {
+            HEIGHT / 2.0
+        };
+
+        container(space())
+            .width(Fill)
+            .height(height)
+            .style(|theme: &Theme| {
+                container::Style::default()
+                    .background(theme.extended_palette().primary.base.color)
+            })
+            .into()
+    });
+
+    container(row(bars).spacing(1).align_y(Bottom))
+        .width(Fill)
+        .height(HEIGHT)
+        .align_bottom(HEIGHT)
+        .into()
+}
+
+/// The minimum amount of time between metric samples.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The maximum amount of messages listed in the history.
+const MAX_MESSAGES: usize = 500;
+
+/// The amount of unchanged lines shown around every change of a [`Diff`].
+const CONTEXT_LINES: usize = 2;
+
+/// Returns the changed lines of a [`Diff`] with some context; `None`
+/// separates distant changes.
+fn changes(diff: &Diff) -> impl Iterator<Item = Option<&diff::Line>> {
+    let is_near_change = |index: usize| {
+        diff.lines[index.saturating_sub(CONTEXT_LINES)
+            ..(index + CONTEXT_LINES + 1).min(diff.lines.len())]
+            .iter()
+            .any(diff::Line::is_change)
+    };
+
+    let mut was_visible = true;
+
+    diff.lines
+        .iter()
+        .enumerate()
+        .filter_map(move |(index, line)| {
+            let is_visible = is_near_change(index);
+            let separator = !was_visible && is_visible;
+
+            was_visible = is_visible;
+
+            if separator {
+                Some(vec![None, Some(line)])
+            } else if is_visible {
+                Some(vec![Some(line)])
+            } else {
+                None
+            }
+        })
+        .flatten()
+}
+
+/// Returns the name of the variant of a [`Debug`](fmt::Debug) message.
+fn variant(message: &str) -> &str {
+    message.split(['(', '{', ' ']).next().unwrap_or(message)
+}
+
+fn swatch<'a, Renderer>(
+    pair: Pair,
+    size: f32,
+) -> Element<'a, Message, Theme, Renderer>
+where
+    Renderer: program::Renderer + 'a,
+{
+    container(text("Aa").size(size / 2.5).color(pair.text))
+        .center(size)
+        .style(move |_theme| {
+            container::Style::default()
+                .background(pair.color)
+                .border(border::rounded(4).color(Color::BLACK).width(1))
+        })
+        .into()
+}
+
+fn outline<'a, Renderer>(
+    window: window::Id,
+    inspection: Option<&Inspection>,
+    node: &inspector::Node,
+    path: &mut inspector::Path,
+    rows: &mut Vec<Element<'a, Message, Theme, Renderer>>,
+) where
+    Renderer: program::Renderer + 'a,
+{
+    let is_selected = inspection
+        .and_then(|inspection| inspection.selected.as_ref())
+        == Some(path);
+
+    let is_hovered = inspection
+        .and_then(|inspection| inspection.hovered.as_ref())
+        == Some(path);
+
+    rows.push(
+        button(
+            text!("{:indent$}{node}", "", indent = path.len() * 2)
+                .size(12)
+                .font(Font::MONOSPACE)
+                .wrapping(text::Wrapping::None),
+        )
+        .width(Fill)
+        .padding([2, 4])
+        .on_press(Message::Selected(window, Some(path.clone())))
+        .style(move |theme, status| {
+            if is_selected {
+                button::primary(theme, status)
+            } else if is_hovered {
+                button::secondary(theme, status)
+            } else {
+                button::text(theme, status)
+            }
+        })
+        .into(),
+    );
+
+    for (index, child) in node.children.iter().enumerate() {
+        path.push(index);
+        outline(window, inspection, child, path, rows);
+        let _ = path.pop();
+    }
+}
+
 fn inline_code<'a, Renderer>(
     code: impl text::IntoFragment<'a>,
 ) -> Element<'a, Message, Theme, Renderer>
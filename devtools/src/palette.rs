@@ -0,0 +1,286 @@
+use crate::core::Color;
+use crate::core::theme::palette::Extended;
+use crate::core::theme::{Palette, Theme};
+
+use std::fmt::Write;
+
+/// A color of a [`Palette`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl Field {
+    pub const ALL: &'static [Self] = &[
+        Self::Background,
+        Self::Text,
+        Self::Primary,
+        Self::Success,
+        Self::Warning,
+        Self::Danger,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Background => "background",
+            Self::Text => "text",
+            Self::Primary => "primary",
+            Self::Success => "success",
+            Self::Warning => "warning",
+            Self::Danger => "danger",
+        }
+    }
+
+    pub fn get(self, palette: &Palette) -> Color {
+        match self {
+            Self::Background => palette.background,
+            Self::Text => palette.text,
+            Self::Primary => palette.primary,
+            Self::Success => palette.success,
+            Self::Warning => palette.warning,
+            Self::Danger => palette.danger,
+        }
+    }
+
+    pub fn set(self, palette: &mut Palette, color: Color) {
+        match self {
+            Self::Background => palette.background = color,
+            Self::Text => palette.text = color,
+            Self::Primary => palette.primary = color,
+            Self::Success => palette.success = color,
+            Self::Warning => palette.warning = color,
+            Self::Danger => palette.danger = color,
+        }
+    }
+}
+
+/// The state of the palette editor.
+///
+/// The [`Extended`] palette and the edited [`Theme`]—shared by the devtools
+/// and the previewed program—are generated once per edit, instead of every
+/// frame.
+#[derive(Debug, Clone)]
+pub struct Editor {
+    palette: Palette,
+    extended: Extended,
+    theme: Theme,
+    drafts: Vec<(Field, String)>,
+}
+
+impl Editor {
+    pub fn new(palette: Palette) -> Self {
+        let extended = Extended::generate(palette);
+
+        Self {
+            palette,
+            extended,
+            theme: theme(palette, extended),
+            drafts: Vec::new(),
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        self.palette
+    }
+
+    pub fn extended(&self) -> &Extended {
+        &self.extended
+    }
+
+    /// Returns the [`Theme`] of the edited palette.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Returns the hexadecimal value of a color; or its draft, if it is
+    /// being typed.
+    pub fn hex(&self, field: Field) -> String {
+        self.drafts
+            .iter()
+            .find(|(draft, _)| *draft == field)
+            .map_or_else(
+                || hex(field.get(&self.palette)),
+                |(_, value)| value.clone(),
+            )
+    }
+
+    pub fn set(&mut self, field: Field, color: Color) {
+        self.apply(field, color);
+        self.drafts.retain(|(draft, _)| *draft != field);
+    }
+
+    /// Updates the draft of a color and applies it, if valid.
+    pub fn type_hex(&mut self, field: Field, value: String) {
+        if let Some(color) = parse(&value) {
+            self.apply(field, color);
+        }
+
+        self.drafts.retain(|(draft, _)| *draft != field);
+        self.drafts.push((field, value));
+    }
+
+    fn apply(&mut self, field: Field, color: Color) {
+        if field.get(&self.palette) == color {
+            return;
+        }
+
+        field.set(&mut self.palette, color);
+
+        self.extended = Extended::generate(self.palette);
+        self.theme = theme(self.palette, self.extended);
+    }
+}
+
+fn theme(palette: Palette, extended: Extended) -> Theme {
+    Theme::custom_with_fn("Edited", palette, |_| extended)
+}
+
+/// Parses a hexadecimal color, with or without a leading `#`.
+pub fn parse(value: &str) -> Option<Color> {
+    let digits = value.trim().strip_prefix('#').unwrap_or(value.trim());
+
+    // Color::parse slices the digits by byte
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Color::parse(digits)
+}
+
+/// Formats a [`Color`] as `#rrggbb`, or `#rrggbbaa` if translucent.
+pub fn hex(color: Color) -> String {
+    let [r, g, b, a] = color.into_rgba8();
+
+    if a == u8::MAX {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+/// Exports a [`Palette`] as Rust code.
+pub fn to_rust(palette: &Palette) -> String {
+    let mut code = String::from("Palette {\n");
+
+    for field in Field::ALL {
+        let [r, g, b, _] = field.get(palette).into_rgba8();
+        let alpha = field.get(palette).a;
+
+        let _ = if alpha < 1.0 {
+            writeln!(
+                code,
+                "    {}: color!(0x{r:02x}{g:02x}{b:02x}, {alpha}),",
+                field.name()
+            )
+        } else {
+            writeln!(
+                code,
+                "    {}: color!(0x{r:02x}{g:02x}{b:02x}),",
+                field.name()
+            )
+        };
+    }
+
+    code.push('}');
+    code
+}
+
+/// Exports a [`Palette`] as a TOML table.
+pub fn to_toml(palette: &Palette) -> String {
+    let mut toml = String::from("[palette]\n");
+
+    for field in Field::ALL {
+        let _ = writeln!(
+            toml,
+            "{} = \"{}\"",
+            field.name(),
+            hex(field.get(palette))
+        );
+    }
+
+    toml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors_are_parsed_and_formatted() {
+        let color = Color::from_rgb8(0x58, 0x65, 0xf2);
+
+        assert_eq!(parse("#5865f2"), Some(color));
+        assert_eq!(parse("5865F2"), Some(color));
+        assert_eq!(parse(" #5865f2 "), Some(color));
+        assert_eq!(parse("#fff"), Some(Color::WHITE));
+        assert_eq!(parse("#000000ff"), Some(Color::BLACK));
+
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("#58"), None);
+        assert_eq!(parse("#5865f"), None);
+        assert_eq!(parse("#58650g"), None);
+        assert_eq!(parse("#é12"), None);
+
+        assert_eq!(hex(color), "#5865f2");
+        assert_eq!(hex(color.scale_alpha(0.5)), "#5865f280");
+        assert_eq!(parse(&hex(color.scale_alpha(0.5))), parse("#5865f280"));
+    }
+
+    #[test]
+    fn typed_colors_are_applied_when_valid() {
+        let mut editor = Editor::new(Palette::LIGHT);
+        let red = Color::from_rgb8(0xff, 0, 0);
+
+        editor.type_hex(Field::Primary, "#ff".to_owned());
+        editor.type_hex(Field::Primary, "#ff000".to_owned());
+
+        assert_eq!(editor.palette(), Palette::LIGHT);
+        assert_eq!(editor.hex(Field::Primary), "#ff000");
+
+        editor.type_hex(Field::Primary, "#ff0000".to_owned());
+
+        assert_eq!(editor.palette().primary, red);
+        assert_eq!(editor.extended().primary.base.color, red);
+        assert_eq!(editor.theme().palette().primary, red);
+
+        editor.set(Field::Primary, Color::BLACK);
+
+        assert_eq!(editor.hex(Field::Primary), "#000000");
+        assert_eq!(editor.extended().primary.base.color, Color::BLACK);
+    }
+
+    #[test]
+    fn palettes_are_exported() {
+        let mut palette = Palette::LIGHT;
+        palette.background = Color::WHITE.scale_alpha(0.5);
+
+        assert_eq!(
+            to_rust(&palette),
+            "Palette {
+    background: color!(0xffffff, 0.5),
+    text: color!(0x000000),
+    primary: color!(0x5865f2),
+    success: color!(0x12664f),
+    warning: color!(0xb77e33),
+    danger: color!(0xc3423f),
+}"
+        );
+
+        assert_eq!(
+            to_toml(&palette),
+            "[palette]
+background = \"#ffffff80\"
+text = \"#000000\"
+primary = \"#5865f2\"
+success = \"#12664f\"
+warning = \"#b77e33\"
+danger = \"#c3423f\"
+"
+        );
+    }
+}
@@ -1,4 +1,8 @@
 use crate::Program;
+use crate::diff::Diff;
+
+#[cfg(feature = "time-travel")]
+use std::collections::VecDeque;
 
 #[cfg(feature = "time-travel")]
 pub struct TimeMachine<P>
@@ -7,18 +11,22 @@ where
 {
     state: Option<P::State>,
     messages: Vec<P::Message>,
+    descriptions: Vec<String>,
+    checkpoints: VecDeque<(usize, P::State)>,
 }
 
 #[cfg(feature = "time-travel")]
 impl<P> TimeMachine<P>
 where
     P: Program,
-    P::Message: Clone,
+    P::Message: Clone + std::fmt::Debug,
 {
     pub fn new() -> Self {
         Self {
             state: None,
             messages: Vec::new(),
+            descriptions: Vec::new(),
+            checkpoints: VecDeque::new(),
         }
     }
 
@@ -28,20 +36,88 @@ where
 
     pub fn push(&mut self, message: &P::Message) {
         self.messages.push(message.clone());
+        self.descriptions.push(format!("{message:?}"));
+    }
+
+    pub fn checkpoint(&mut self, program: &P, state: &P::State) {
+        let Some(checkpoints) = program.checkpoints() else {
+            return;
+        };
+
+        if checkpoints.capacity == 0
+            || !self.messages.len().is_multiple_of(checkpoints.interval)
+        {
+            return;
+        }
+
+        if self.checkpoints.len() >= checkpoints.capacity {
+            let _ = self.checkpoints.pop_front();
+        }
+
+        self.checkpoints
+            .push_back((self.messages.len(), checkpoints.snapshot(state)));
     }
 
     pub fn rewind(&mut self, program: &P, message: usize) {
         crate::debug::disable();
-        let (mut state, _) = program.boot();
 
-        if message < self.messages.len() {
-            // TODO: Run concurrently (?)
-            for message in &self.messages[0..message] {
+        self.state = Some(self.replay(program, message));
+    }
+
+    /// Diffs the state right before and after the given message.
+    ///
+    /// Returns `None` if the message does not exist or the [`Program`]
+    /// cannot debug its state.
+    pub fn diff(&self, program: &P, index: usize) -> Option<Diff> {
+        let message = self.messages.get(index)?.clone();
+
+        if !self.is_rewinding() {
+            crate::debug::disable();
+        }
+
+        let (before, after) = unrecorded(|| {
+            let mut state = self.replay(program, index);
+            let before = program.debug_state(&state);
+
+            let _ = program.update(&mut state, message);
+
+            (before, program.debug_state(&state))
+        });
+
+        if !self.is_rewinding() {
+            crate::debug::enable();
+        }
+
+        Some(Diff::new(&before?, &after?))
+    }
+
+    /// Returns the [`Debug`](std::fmt::Debug) output of every recorded
+    /// message; formatted once, when recorded.
+    pub fn descriptions(&self) -> &[String] {
+        &self.descriptions
+    }
+
+    fn replay(&self, program: &P, message: usize) -> P::State {
+        let message = message.min(self.messages.len());
+
+        let checkpoint = program.checkpoints().and_then(|checkpoints| {
+            self.checkpoints
+                .iter()
+                .rev()
+                .find(|(index, _)| *index <= message)
+                .map(|(index, state)| (*index, checkpoints.snapshot(state)))
+        });
+
+        let (start, mut state) =
+            checkpoint.unwrap_or_else(|| (0, program.boot().0));
+
+        unrecorded(|| {
+            for message in &self.messages[start..message] {
                 let _ = program.update(&mut state, message.clone());
             }
-        }
+        });
 
-        self.state = Some(state);
+        state
     }
 
     pub fn go_to_present(&mut self) {
@@ -54,6 +130,21 @@ where
     }
 }
 
+/// Runs the given closure without recording the messages it replays in the
+/// current session, if any.
+#[cfg(feature = "time-travel")]
+fn unrecorded<T>(f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "session")]
+    {
+        crate::program::session::suspend(f)
+    }
+
+    #[cfg(not(feature = "session"))]
+    {
+        f()
+    }
+}
+
 #[cfg(not(feature = "time-travel"))]
 pub struct TimeMachine<P>
 where
@@ -79,11 +170,238 @@ where
 
     pub fn push(&mut self, _message: &P::Message) {}
 
+    pub fn checkpoint(&mut self, _program: &P, _state: &P::State) {}
+
     pub fn rewind(&mut self, _program: &P, _message: usize) {}
 
+    pub fn diff(&self, _program: &P, _message: usize) -> Option<Diff> {
+        None
+    }
+
+    pub fn descriptions(&self) -> &[String] {
+        &[]
+    }
+
     pub fn go_to_present(&mut self) {}
 
     pub fn state(&self) -> Option<&P::State> {
         None
     }
 }
+
+#[cfg(all(test, feature = "time-travel"))]
+mod tests {
+    use super::*;
+
+    use crate::core::window;
+    use crate::core::{Element, Settings, Theme};
+    use crate::futures::backend::null;
+    use crate::program::Checkpoints;
+    use crate::runtime::Task;
+    use crate::widget::{Renderer, text};
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A program that adds every message to its state.
+    struct Sum {
+        checkpoints: Option<Checkpoints<i64>>,
+        updates: AtomicUsize,
+    }
+
+    impl Sum {
+        fn new(checkpoints: Option<Checkpoints<i64>>) -> Self {
+            Self {
+                checkpoints,
+                updates: AtomicUsize::new(0),
+            }
+        }
+
+        /// Returns the amount of updates since the last call.
+        fn updates(&self) -> usize {
+            self.updates.swap(0, Ordering::Relaxed)
+        }
+    }
+
+    impl Program for Sum {
+        type State = i64;
+        type Message = i64;
+        type Theme = Theme;
+        type Renderer = Renderer;
+        type Executor = null::Executor;
+
+        fn name() -> &'static str {
+            "sum"
+        }
+
+        fn settings(&self) -> Settings {
+            Settings::default()
+        }
+
+        fn window(&self) -> Option<window::Settings> {
+            None
+        }
+
+        fn boot(&self) -> (Self::State, Task<Self::Message>) {
+            (0, Task::none())
+        }
+
+        fn update(
+            &self,
+            state: &mut Self::State,
+            message: Self::Message,
+        ) -> Task<Self::Message> {
+            *state += message;
+            let _ = self.updates.fetch_add(1, Ordering::Relaxed);
+
+            Task::none()
+        }
+
+        fn view<'a>(
+            &self,
+            state: &'a Self::State,
+            _window: window::Id,
+        ) -> Element<'a, Self::Message, Self::Theme, Self::Renderer> {
+            text(state).into()
+        }
+
+        fn checkpoints(&self) -> Option<&Checkpoints<Self::State>> {
+            self.checkpoints.as_ref()
+        }
+
+        fn debug_state(&self, state: &Self::State) -> Option<String> {
+            Some(state.to_string())
+        }
+    }
+
+    /// Records the given messages like the devtools do.
+    fn record(
+        program: &Sum,
+        messages: impl IntoIterator<Item = i64>,
+    ) -> TimeMachine<Sum> {
+        let mut time_machine = TimeMachine::new();
+        let mut state = program.boot().0;
+
+        for message in messages {
+            time_machine.push(&message);
+            let _ = program.update(&mut state, message);
+            time_machine.checkpoint(program, &state);
+        }
+
+        let _ = program.updates();
+
+        time_machine
+    }
+
+    #[test]
+    fn rewinding_replays_every_message_without_checkpoints() {
+        let program = Sum::new(None);
+        let mut time_machine = record(&program, 1..=5);
+
+        time_machine.rewind(&program, 3);
+
+        assert!(time_machine.is_rewinding());
+        assert_eq!(time_machine.state(), Some(&6));
+        assert_eq!(program.updates(), 3);
+
+        time_machine.go_to_present();
+
+        assert!(!time_machine.is_rewinding());
+        assert_eq!(time_machine.state(), None);
+    }
+
+    #[test]
+    fn rewinding_replays_from_the_nearest_checkpoint() {
+        let program = Sum::new(Some(Checkpoints::cloned().interval(2)));
+        let mut time_machine = record(&program, 1..=5);
+
+        time_machine.rewind(&program, 3);
+
+        assert_eq!(time_machine.state(), Some(&6));
+        assert_eq!(program.updates(), 1);
+
+        time_machine.rewind(&program, 4);
+
+        assert_eq!(time_machine.state(), Some(&10));
+        assert_eq!(program.updates(), 0);
+
+        time_machine.rewind(&program, 1);
+
+        assert_eq!(time_machine.state(), Some(&1));
+        assert_eq!(program.updates(), 1);
+
+        // Rewinding past the last message stops at the present
+        time_machine.rewind(&program, 10);
+
+        assert_eq!(time_machine.state(), Some(&15));
+        assert_eq!(program.updates(), 1);
+    }
+
+    #[test]
+    fn oldest_checkpoints_are_discarded() {
+        let program =
+            Sum::new(Some(Checkpoints::cloned().interval(1).capacity(2)));
+        let mut time_machine = record(&program, 1..=5);
+
+        time_machine.rewind(&program, 3);
+
+        assert_eq!(time_machine.state(), Some(&6));
+        assert_eq!(program.updates(), 3);
+
+        time_machine.rewind(&program, 4);
+
+        assert_eq!(time_machine.state(), Some(&10));
+        assert_eq!(program.updates(), 0);
+    }
+
+    #[test]
+    fn diffs_replay_from_the_nearest_checkpoint() {
+        let program = Sum::new(Some(Checkpoints::cloned().interval(2)));
+        let time_machine = record(&program, 1..=5);
+
+        assert_eq!(time_machine.diff(&program, 2), Some(Diff::new("3", "6")));
+        assert_eq!(program.updates(), 1);
+
+        assert_eq!(time_machine.diff(&program, 5), None);
+        assert_eq!(time_machine.descriptions(), ["1", "2", "3", "4", "5"]);
+    }
+
+    #[test]
+    #[cfg(feature = "session")]
+    fn rewinding_does_not_record_sessions() {
+        use crate::program::session::{Recorder, Session};
+
+        let path = std::env::temp_dir().join(format!(
+            "iced_devtools_rewind_{}.session",
+            std::process::id()
+        ));
+
+        let program = crate::program::with_recorder(
+            Sum::new(Some(Checkpoints::cloned().interval(2))),
+            Recorder::create(&path).unwrap(),
+        );
+
+        let mut time_machine = TimeMachine::new();
+        let mut state = program.boot().0;
+
+        for message in 1..=5 {
+            time_machine.push(&message);
+            let _ = program.update(&mut state, message);
+            time_machine.checkpoint(&program, &state);
+        }
+
+        let recorded = std::fs::read(&path).unwrap();
+
+        time_machine.rewind(&program, 3);
+        assert_eq!(time_machine.state(), Some(&6));
+        assert_eq!(time_machine.diff(&program, 4), Some(Diff::new("10", "15")));
+
+        time_machine.go_to_present();
+
+        let session = Session::<i64>::load(&path).unwrap();
+        let unchanged = std::fs::read(&path).unwrap() == recorded;
+        let _ = std::fs::remove_file(&path);
+
+        assert!(unchanged);
+        assert_eq!(session.messages, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -51,6 +51,11 @@ pub mod time {
         ) -> futures::stream::BoxStream<'static, Self::Output> {
             use futures::stream::StreamExt;
 
+            #[cfg(feature = "virtual-time")]
+            if let Some(ticks) = crate::clock::Clock::every(self.0) {
+                return ticks;
+            }
+
             smol::Timer::interval(self.0).boxed()
         }
     }
@@ -39,6 +39,11 @@ pub mod time {
         Subscription::run_with(duration, |duration| {
             use futures::stream::StreamExt;
 
+            #[cfg(feature = "virtual-time")]
+            if let Some(ticks) = crate::clock::Clock::every(*duration) {
+                return ticks;
+            }
+
             let start = tokio::time::Instant::now() + *duration;
 
             let mut interval = tokio::time::interval_at(start, *duration);
@@ -0,0 +1,100 @@
+//! Control the timers of a headless runtime with a virtual clock.
+use crate::BoxStream;
+use crate::core::time::{Duration, Instant};
+
+use futures::channel::mpsc;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Clock>> = const { RefCell::new(None) };
+}
+
+/// A virtual clock that drives timer subscriptions.
+///
+/// Any timer subscription—like `time::every`—that starts while a [`Clock`] is
+/// [entered](Self::enter) follows the [`Clock`] instead of real time. The timer
+/// fires whenever the [`Clock`] advances past its next deadline; missed ticks
+/// are skipped.
+#[derive(Debug, Clone)]
+pub struct Clock {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    now: Instant,
+    timers: Vec<mpsc::UnboundedSender<Instant>>,
+}
+
+impl Clock {
+    /// Creates a new [`Clock`] starting at the given [`Instant`].
+    pub fn new(now: Instant) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                now,
+                timers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns the current time of the [`Clock`].
+    pub fn now(&self) -> Instant {
+        self.state.lock().expect("Lock clock state").now
+    }
+
+    /// Advances the [`Clock`] to the given [`Instant`], notifying its timers.
+    pub fn advance(&self, now: Instant) {
+        let mut state = self.state.lock().expect("Lock clock state");
+
+        state.now = now;
+        state
+            .timers
+            .retain(|timer| timer.unbounded_send(now).is_ok());
+    }
+
+    /// Runs the given closure with the [`Clock`] as the current one.
+    ///
+    /// Any timer subscription started inside the closure will follow the
+    /// [`Clock`].
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous =
+            CURRENT.with(|current| current.replace(Some(self.clone())));
+
+        let result = f();
+
+        CURRENT.with(|current| {
+            let _ = current.replace(previous);
+        });
+
+        result
+    }
+
+    /// Returns a stream of ticks for the given `interval` following the
+    /// current [`Clock`], if any.
+    pub fn every(interval: Duration) -> Option<BoxStream<Instant>> {
+        use futures::future;
+        use futures::stream::StreamExt;
+
+        let clock = CURRENT.with(|current| current.borrow().clone())?;
+
+        let (sender, receiver) = mpsc::unbounded();
+
+        let mut state = clock.state.lock().expect("Lock clock state");
+        let start = state.now;
+        state.timers.push(sender);
+
+        let ticks = receiver
+            .scan(start + interval, move |next, now| {
+                future::ready(Some(if now < *next {
+                    None
+                } else {
+                    *next = now + interval;
+                    Some(now)
+                }))
+            })
+            .filter_map(future::ready);
+
+        Some(crate::boxed_stream(ticks))
+    }
+}
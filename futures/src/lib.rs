@@ -18,6 +18,9 @@ pub mod keyboard;
 pub mod stream;
 pub mod subscription;
 
+#[cfg(feature = "virtual-time")]
+pub mod clock;
+
 pub use executor::Executor;
 pub use maybe::{MaybeSend, MaybeSync};
 pub use platform::*;
@@ -0,0 +1,556 @@
+//! Capture the layer stack of a frame to inspect it offline.
+//!
+//! A [`Frame`] contains everything a renderer was asked to draw in a single
+//! frame, grouped by layer, in logical coordinates. It can be serialized
+//! to JSON and rendered again later; which is handy to track down
+//! rendering bugs.
+use crate::core::renderer;
+use crate::core::{self, Color, Point, Radians, Rectangle, Transformation};
+use crate::gradient;
+use crate::text;
+
+use serde::{Deserialize, Serialize};
+
+/// The layer stack of a single frame.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Frame {
+    /// The physical width of the frame.
+    pub width: u32,
+    /// The physical height of the frame.
+    pub height: u32,
+    /// The scale factor of the frame.
+    pub scale_factor: f32,
+    /// The background color of the frame.
+    pub background: Color,
+    /// The layers of the frame, from bottom to top.
+    pub layers: Vec<Layer>,
+}
+
+impl Frame {
+    /// Serializes the [`Frame`] as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Serialize frame capture")
+    }
+
+    /// Parses a [`Frame`] from JSON.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// The primitives of a layer.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Layer {
+    /// The clip bounds of the layer.
+    pub bounds: Rectangle,
+    /// The quads of the layer.
+    pub quads: Vec<Quad>,
+    /// The paths of the layer.
+    pub paths: Vec<Path>,
+    /// The meshes of the layer.
+    pub meshes: Vec<Mesh>,
+    /// The images of the layer.
+    pub images: Vec<Image>,
+    /// The text of the layer.
+    pub text: Vec<Text>,
+    /// The amount of primitives of the layer that could not be captured;
+    /// like custom shader primitives.
+    #[serde(default)]
+    pub uncaptured: usize,
+}
+
+/// A captured quad.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quad {
+    /// The bounds of the quad.
+    pub bounds: Rectangle,
+    /// The background of the quad.
+    pub background: Fill,
+    /// The border color of the quad.
+    pub border_color: Color,
+    /// The border width of the quad.
+    pub border_width: f32,
+    /// The border radii of the quad; top-left, top-right, bottom-right,
+    /// and bottom-left.
+    pub border_radius: [f32; 4],
+    /// The shadow color of the quad.
+    pub shadow_color: Color,
+    /// The shadow offset of the quad.
+    pub shadow_offset: [f32; 2],
+    /// The shadow blur radius of the quad.
+    pub shadow_blur_radius: f32,
+}
+
+impl Quad {
+    /// Captures a [`renderer::Quad`] filled with the given background.
+    pub fn new(quad: &renderer::Quad, background: &core::Background) -> Self {
+        Self {
+            bounds: quad.bounds,
+            background: Fill::new(background, quad.bounds),
+            border_color: quad.border.color,
+            border_width: quad.border.width,
+            border_radius: quad.border.radius.into(),
+            shadow_color: quad.shadow.color,
+            shadow_offset: [quad.shadow.offset.x, quad.shadow.offset.y],
+            shadow_blur_radius: quad.shadow.blur_radius,
+        }
+    }
+}
+
+/// The fill of a captured primitive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Fill {
+    /// A solid color.
+    Color(Color),
+    /// A linear gradient.
+    Gradient(Gradient),
+}
+
+impl Fill {
+    /// Captures a [`core::Background`] drawn in the given bounds.
+    pub fn new(background: &core::Background, bounds: Rectangle) -> Self {
+        match background {
+            core::Background::Color(color) => Self::Color(*color),
+            core::Background::Gradient(core::Gradient::Linear(linear)) => {
+                let (start, end) = linear.angle.to_distance(&bounds);
+
+                Self::Gradient(Gradient {
+                    start: [start.x, start.y],
+                    end: [end.x, end.y],
+                    stops: linear
+                        .stops
+                        .iter()
+                        .flatten()
+                        .map(|stop| (stop.offset, stop.color))
+                        .collect(),
+                })
+            }
+        }
+    }
+
+    /// Turns the [`Fill`] into a [`core::Background`] drawn in the given
+    /// bounds.
+    ///
+    /// A [`core::Background`] gradient always spans its bounds, so the stops
+    /// of a [`Gradient`] are moved to keep its colors in place.
+    pub fn background(&self, bounds: Rectangle) -> core::Background {
+        let gradient = match self {
+            Fill::Color(color) => return core::Background::Color(*color),
+            Fill::Gradient(gradient) => gradient,
+        };
+
+        let angle = Radians(
+            (gradient.end[1] - gradient.start[1])
+                .atan2(gradient.end[0] - gradient.start[0])
+                + std::f32::consts::FRAC_PI_2,
+        );
+
+        let (start, end) = angle.to_distance(&bounds);
+        let (from, to) = (gradient.offset_at(start), gradient.offset_at(end));
+
+        if (to - from).abs() <= f32::EPSILON {
+            return core::Background::Color(gradient.color_at(bounds.center()));
+        }
+
+        // Stops are added in order, since adding a stop before another
+        // one replaces it
+        let stops = gradient
+            .stops
+            .iter()
+            .map(|(offset, color)| ((offset - from) / (to - from), *color))
+            .filter(|(offset, _)| *offset > 0.0 && *offset < 1.0)
+            .take(6);
+
+        core::Background::Gradient(core::Gradient::Linear(
+            std::iter::once((0.0, gradient.color_at(start)))
+                .chain(stops)
+                .chain(std::iter::once((1.0, gradient.color_at(end))))
+                .fold(
+                    core::gradient::Linear::new(angle),
+                    |linear, (offset, color)| linear.add_stop(offset, color),
+                ),
+        ))
+    }
+}
+
+/// A captured linear gradient.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Gradient {
+    /// The absolute starting point of the gradient.
+    pub start: [f32; 2],
+    /// The absolute ending point of the gradient.
+    pub end: [f32; 2],
+    /// The offsets and colors of the stops of the gradient.
+    pub stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    /// Returns the [`Color`] of the [`Gradient`] at the given point.
+    pub fn color_at(&self, point: Point) -> Color {
+        let offset = self.offset_at(point);
+
+        let mut stops = self.stops.iter();
+
+        let Some(&(mut previous_offset, mut previous_color)) = stops.next()
+        else {
+            return Color::TRANSPARENT;
+        };
+
+        if offset <= previous_offset {
+            return previous_color;
+        }
+
+        for &(next_offset, next_color) in stops {
+            if offset <= next_offset {
+                let t = (offset - previous_offset)
+                    / (next_offset - previous_offset).max(f32::EPSILON);
+
+                return Color {
+                    r: previous_color.r + (next_color.r - previous_color.r) * t,
+                    g: previous_color.g + (next_color.g - previous_color.g) * t,
+                    b: previous_color.b + (next_color.b - previous_color.b) * t,
+                    a: previous_color.a + (next_color.a - previous_color.a) * t,
+                };
+            }
+
+            previous_offset = next_offset;
+            previous_color = next_color;
+        }
+
+        previous_color
+    }
+
+    /// Returns the offset of the given point along the [`Gradient`].
+    fn offset_at(&self, point: Point) -> f32 {
+        let direction =
+            [self.end[0] - self.start[0], self.end[1] - self.start[1]];
+        let length = direction[0].powi(2) + direction[1].powi(2);
+
+        if length > 0.0 {
+            ((point.x - self.start[0]) * direction[0]
+                + (point.y - self.start[1]) * direction[1])
+                / length
+        } else {
+            0.0
+        }
+    }
+}
+
+impl From<gradient::Linear> for Gradient {
+    fn from(linear: gradient::Linear) -> Self {
+        Self {
+            start: [linear.start.x, linear.start.y],
+            end: [linear.end.x, linear.end.y],
+            stops: linear
+                .stops
+                .iter()
+                .flatten()
+                .map(|stop| (stop.offset, stop.color))
+                .collect(),
+        }
+    }
+}
+
+/// A captured vector path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Path {
+    /// The SVG path data of the path.
+    pub data: String,
+    /// The fill of the path, if filled.
+    pub fill: Option<Fill>,
+    /// The fill and width of the stroke of the path, if stroked.
+    pub stroke: Option<(Fill, f32)>,
+    /// The clip bounds of the path.
+    pub clip_bounds: Rectangle,
+}
+
+/// A captured triangle mesh.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mesh {
+    /// The positions of the vertices of the mesh.
+    pub vertices: Vec<[f32; 2]>,
+    /// The colors of the vertices of the mesh.
+    pub colors: Vec<Color>,
+    /// The indices of the triangles of the mesh.
+    pub indices: Vec<u32>,
+    /// The clip bounds of the mesh.
+    pub clip_bounds: Rectangle,
+}
+
+impl Mesh {
+    /// Captures a [`crate::Mesh`] with the given [`Transformation`].
+    pub fn new(mesh: &crate::Mesh, transformation: Transformation) -> Self {
+        let transformation = transformation * mesh.transformation();
+
+        let (vertices, colors) = match mesh {
+            crate::Mesh::Solid { buffers, .. } => buffers
+                .vertices
+                .iter()
+                .map(|vertex| {
+                    let position =
+                        Point::from(vertex.position) * transformation;
+
+                    (
+                        [position.x, position.y],
+                        crate::color::unpack(vertex.color),
+                    )
+                })
+                .unzip(),
+            crate::Mesh::Gradient { buffers, .. } => buffers
+                .vertices
+                .iter()
+                .map(|vertex| {
+                    let gradient = Gradient::from(vertex.gradient.unpack());
+                    let color = gradient.color_at(Point::from(vertex.position));
+                    let position =
+                        Point::from(vertex.position) * transformation;
+
+                    ([position.x, position.y], color)
+                })
+                .unzip(),
+        };
+
+        Self {
+            vertices,
+            colors,
+            indices: mesh.indices().to_vec(),
+            clip_bounds: mesh.clip_bounds() * transformation,
+        }
+    }
+}
+
+/// A captured image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Image {
+    /// Whether the image is a vector image.
+    pub is_vector: bool,
+    /// The bounds of the image.
+    pub bounds: Rectangle,
+    /// The clip bounds of the image.
+    pub clip_bounds: Rectangle,
+}
+
+impl Image {
+    /// Captures a [`crate::Image`] with the given [`Transformation`].
+    pub fn new(image: &crate::Image, transformation: Transformation) -> Self {
+        let (is_vector, bounds, clip_bounds) = match image {
+            crate::Image::Raster {
+                bounds,
+                clip_bounds,
+                ..
+            } => (false, bounds, clip_bounds),
+            crate::Image::Vector {
+                bounds,
+                clip_bounds,
+                ..
+            } => (true, bounds, clip_bounds),
+        };
+
+        Self {
+            is_vector,
+            bounds: *bounds * transformation,
+            clip_bounds: *clip_bounds * transformation,
+        }
+    }
+}
+
+/// Some captured text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Text {
+    /// The contents of the text, if still alive.
+    pub content: Option<String>,
+    /// The visible bounds of the text.
+    pub bounds: Rectangle,
+    /// The color of the text.
+    pub color: Color,
+    /// The size of the text, if known.
+    pub size: Option<f32>,
+}
+
+impl Text {
+    /// Captures some [`crate::Text`] with the given [`Transformation`].
+    ///
+    /// Returns `None` if the text is not visible.
+    pub fn new(
+        text: &crate::Text,
+        transformation: Transformation,
+    ) -> Option<Self> {
+        let bounds = text.visible_bounds()? * transformation;
+
+        let (content, color, size) = match text {
+            crate::Text::Paragraph {
+                paragraph, color, ..
+            } => {
+                let paragraph = paragraph.upgrade();
+
+                (
+                    paragraph
+                        .as_ref()
+                        .map(|paragraph| contents(paragraph.buffer())),
+                    *color,
+                    paragraph.map(|paragraph| {
+                        paragraph.buffer().metrics().font_size
+                    }),
+                )
+            }
+            crate::Text::Editor { editor, color, .. } => {
+                let editor = editor.upgrade();
+
+                (
+                    editor.as_ref().map(|editor| contents(editor.buffer())),
+                    *color,
+                    editor.map(|editor| editor.buffer().metrics().font_size),
+                )
+            }
+            crate::Text::Cached {
+                content,
+                color,
+                size,
+                ..
+            } => (Some(content.clone()), *color, Some(size.0)),
+            crate::Text::Raw { raw, .. } => (None, raw.color, None),
+        };
+
+        Some(Self {
+            content,
+            bounds,
+            color,
+            size: size.map(|size| size * transformation.scale_factor()),
+        })
+    }
+}
+
+fn contents(buffer: &text::cosmic_text::Buffer) -> String {
+    buffer
+        .lines
+        .iter()
+        .map(text::cosmic_text::BufferLine::text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient() -> Gradient {
+        Gradient {
+            start: [0.0, 0.0],
+            end: [10.0, 0.0],
+            stops: vec![(0.0, Color::BLACK), (1.0, Color::WHITE)],
+        }
+    }
+
+    #[test]
+    fn frames_round_trip_through_json() {
+        let bounds =
+            Rectangle::new(Point::new(1.0, 2.0), core::Size::new(3.0, 4.0));
+
+        let frame = Frame {
+            width: 200,
+            height: 100,
+            scale_factor: 2.0,
+            background: Color::WHITE,
+            layers: vec![Layer {
+                bounds,
+                quads: vec![Quad {
+                    bounds,
+                    background: Fill::Gradient(gradient()),
+                    border_color: Color::BLACK,
+                    border_width: 1.0,
+                    border_radius: [1.0, 2.0, 3.0, 4.0],
+                    shadow_color: Color::TRANSPARENT,
+                    shadow_offset: [0.0, 1.0],
+                    shadow_blur_radius: 2.0,
+                }],
+                paths: vec![Path {
+                    data: "M 0 0 L 1 1 Z".to_owned(),
+                    fill: None,
+                    stroke: Some((Fill::Color(Color::BLACK), 2.0)),
+                    clip_bounds: bounds,
+                }],
+                meshes: vec![Mesh {
+                    vertices: vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+                    colors: vec![Color::BLACK; 3],
+                    indices: vec![0, 1, 2],
+                    clip_bounds: bounds,
+                }],
+                images: vec![Image {
+                    is_vector: true,
+                    bounds,
+                    clip_bounds: bounds,
+                }],
+                text: vec![Text {
+                    content: Some("Hello\nworld".to_owned()),
+                    bounds,
+                    color: Color::BLACK,
+                    size: Some(16.0),
+                }],
+                uncaptured: 1,
+            }],
+        };
+
+        assert_eq!(Frame::from_json(&frame.to_json()).unwrap(), frame);
+    }
+
+    #[test]
+    fn gradient_colors() {
+        let gradient = gradient();
+
+        assert_eq!(gradient.color_at(Point::new(-5.0, 0.0)), Color::BLACK);
+        assert_eq!(gradient.color_at(Point::new(20.0, 7.0)), Color::WHITE);
+        assert_eq!(
+            gradient.color_at(Point::new(5.0, 3.0)),
+            Color::from_rgb(0.5, 0.5, 0.5)
+        );
+
+        let empty = Gradient {
+            stops: Vec::new(),
+            ..gradient
+        };
+
+        assert_eq!(empty.color_at(Point::ORIGIN), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn backgrounds_keep_gradients_in_place() {
+        let bounds = Rectangle::new(Point::ORIGIN, core::Size::new(20.0, 10.0));
+
+        // The gradient only spans the left half of the bounds
+        let fill = Fill::Gradient(Gradient {
+            stops: vec![
+                (0.0, Color::BLACK),
+                (0.5, Color::from_rgb(1.0, 0.0, 0.0)),
+                (1.0, Color::WHITE),
+            ],
+            ..gradient()
+        });
+
+        let Fill::Gradient(replayed) =
+            Fill::new(&fill.background(bounds), bounds)
+        else {
+            panic!("gradient is kept");
+        };
+
+        let Fill::Gradient(original) = fill else {
+            unreachable!()
+        };
+
+        for x in [0.0, 2.5, 5.0, 7.5, 10.0, 15.0, 20.0] {
+            let point = Point::new(x, 5.0);
+            let [a, b] = [original.color_at(point), replayed.color_at(point)];
+
+            assert!(
+                (a.r - b.r).abs() < 1e-4
+                    && (a.g - b.g).abs() < 1e-4
+                    && (a.b - b.b).abs() < 1e-4,
+                "{a:?} != {b:?} at {x}"
+            );
+        }
+
+        assert_eq!(
+            Fill::Color(Color::BLACK).background(bounds),
+            core::Background::Color(Color::BLACK)
+        );
+    }
+}
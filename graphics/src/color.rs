@@ -15,6 +15,12 @@ impl Packed {
     }
 }
 
+impl From<[f32; 4]> for Packed {
+    fn from(components: [f32; 4]) -> Self {
+        Self(components)
+    }
+}
+
 /// A flag that indicates whether the renderer should perform gamma correction.
 pub const GAMMA_CORRECTION: bool = internal::GAMMA_CORRECTION;
 
@@ -23,6 +29,11 @@ pub fn pack(color: impl Into<Color>) -> Packed {
     Packed(internal::pack(color.into()))
 }
 
+/// Unpacks a [`Packed`] color.
+pub fn unpack(packed: Packed) -> Color {
+    internal::unpack(packed.0)
+}
+
 #[cfg(not(feature = "web-colors"))]
 mod internal {
     use crate::core::Color;
@@ -32,6 +43,10 @@ mod internal {
     pub fn pack(color: Color) -> [f32; 4] {
         color.into_linear()
     }
+
+    pub fn unpack([r, g, b, a]: [f32; 4]) -> Color {
+        Color::from_linear_rgba(r, g, b, a)
+    }
 }
 
 #[cfg(feature = "web-colors")]
@@ -43,4 +58,8 @@ mod internal {
     pub fn pack(color: Color) -> [f32; 4] {
         [color.r, color.g, color.b, color.a]
     }
+
+    pub fn unpack([r, g, b, a]: [f32; 4]) -> Color {
+        Color::from_rgba(r, g, b, a)
+    }
 }
@@ -71,6 +71,14 @@ pub trait Compositor: Sized {
             .load_font(font);
     }
 
+    /// Loads a new version of a font from its bytes, replacing the old one.
+    fn reload_font(&mut self, font: Cow<'static, [u8]>) {
+        crate::text::font_system()
+            .write()
+            .expect("Write to font system")
+            .reload_font(font);
+    }
+
     /// Presents the [`Renderer`] primitives to the next frame of the given [`Surface`].
     ///
     /// [`Renderer`]: Self::Renderer
@@ -181,6 +189,8 @@ impl Compositor for () {
 
     fn load_font(&mut self, _font: Cow<'static, [u8]>) {}
 
+    fn reload_font(&mut self, _font: Cow<'static, [u8]>) {}
+
     fn information(&self) -> Information {
         Information {
             adapter: String::from("Null Renderer"),
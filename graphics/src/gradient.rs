@@ -141,6 +141,49 @@ pub struct Packed {
     direction: [f32; 4],
 }
 
+impl Packed {
+    /// Unpacks the [`Packed`] gradient into a [`Linear`] one.
+    pub fn unpack(&self) -> Linear {
+        let [start_x, start_y, end_x, end_y] = self.direction;
+
+        let offsets = self
+            .offsets
+            .iter()
+            .flat_map(|offsets| unpack_f16s(*offsets));
+
+        let mut stops = [None; 8];
+
+        for ((stop, [rg, ba]), offset) in
+            stops.iter_mut().zip(self.colors).zip(offsets)
+        {
+            let offset = offset.to_f32();
+
+            if !(0.0..=1.0).contains(&offset) {
+                continue;
+            }
+
+            let [r, g] = unpack_f16s(rg);
+            let [b, a] = unpack_f16s(ba);
+
+            *stop = Some(ColorStop {
+                offset,
+                color: color::unpack(color::Packed::from([
+                    r.to_f32(),
+                    g.to_f32(),
+                    b.to_f32(),
+                    a.to_f32(),
+                ])),
+            });
+        }
+
+        Linear {
+            start: Point::new(start_x, start_y),
+            end: Point::new(end_x, end_y),
+            stops,
+        }
+    }
+}
+
 /// Creates a new [`Packed`] gradient for use in shader code.
 pub fn pack(gradient: &core::Gradient, bounds: Rectangle) -> Packed {
     match gradient {
@@ -189,3 +232,54 @@ fn pack_f16s(f: [f16; 2]) -> u32 {
 
     one | two
 }
+
+/// Unpacks one u32 into two f16s.
+fn unpack_f16s(f: u32) -> [f16; 2] {
+    [f16::from_bits((f >> 16) as u16), f16::from_bits(f as u16)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f16s_are_packed_in_pairs() {
+        let pair = [f16::from_f32(0.5), f16::from_f32(-2.0)];
+
+        assert_eq!(unpack_f16s(pack_f16s(pair)), pair);
+    }
+
+    #[test]
+    fn packed_gradients_are_unpacked() {
+        let linear = Linear::new(Point::new(1.0, 2.0), Point::new(30.0, 40.0))
+            .add_stop(0.0, Color::BLACK)
+            .add_stop(0.5, Color::from_rgb(1.0, 0.0, 0.0))
+            .add_stop(1.0, Color::WHITE);
+
+        let unpacked = linear.pack().unpack();
+
+        assert_eq!((unpacked.start, unpacked.end), (linear.start, linear.end));
+
+        for (a, b) in unpacked.stops.iter().zip(linear.stops) {
+            let (Some(a), Some(b)) = (a, b) else {
+                assert_eq!(a.is_none(), b.is_none());
+                continue;
+            };
+
+            // Colors are packed as 16-bit floats in linear space
+            assert_eq!(a.offset, b.offset);
+            assert!(
+                (a.color.r - b.color.r).abs() < 1e-3
+                    && (a.color.g - b.color.g).abs() < 1e-3
+                    && (a.color.b - b.color.b).abs() < 1e-3
+                    && (a.color.a - b.color.a).abs() < 1e-3,
+                "{a:?} != {b:?}"
+            );
+        }
+
+        assert_eq!(
+            Linear::new(Point::ORIGIN, Point::ORIGIN).pack().unpack(),
+            Linear::new(Point::ORIGIN, Point::ORIGIN)
+        );
+    }
+}
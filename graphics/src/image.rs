@@ -6,6 +6,8 @@ use crate::core::Rectangle;
 use crate::core::image;
 use crate::core::svg;
 
+use std::path::{Path, PathBuf};
+
 /// A raster or vector image.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, PartialEq)]
@@ -37,6 +39,15 @@ impl Image {
     }
 }
 
+/// Returns the canonical form of the given path; or the path itself, if it
+/// cannot be resolved.
+///
+/// Renderers use it to find the cached images loaded from a changed file;
+/// no matter the path they were loaded with.
+pub fn canonicalize(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
 /// An image buffer.
 #[cfg(feature = "image")]
 pub type Buffer = ::image::ImageBuffer<::image::Rgba<u8>, Bytes>;
@@ -24,6 +24,9 @@ pub mod mesh;
 pub mod shell;
 pub mod text;
 
+#[cfg(feature = "capture")]
+pub mod capture;
+
 #[cfg(feature = "geometry")]
 pub mod geometry;
 
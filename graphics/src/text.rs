@@ -163,6 +163,41 @@ impl FontSystem {
         self.version = Version(self.version.0 + 1);
     }
 
+    /// Loads a new version of a font from its bytes.
+    ///
+    /// Any faces previously loaded from memory with the same name are
+    /// replaced; so fonts can be reloaded while they are being edited.
+    pub fn reload_font(&mut self, bytes: Cow<'static, [u8]>) {
+        let database = self.raw.db_mut();
+
+        let ids = database.load_font_source(
+            cosmic_text::fontdb::Source::Binary(Arc::new(bytes.into_owned())),
+        );
+
+        let stale: Vec<_> = database
+            .faces()
+            .filter(|face| {
+                !ids.contains(&face.id)
+                    && matches!(
+                        face.source,
+                        cosmic_text::fontdb::Source::Binary(_)
+                    )
+                    && ids.iter().any(|id| {
+                        database.face(*id).is_some_and(|new| {
+                            new.post_script_name == face.post_script_name
+                        })
+                    })
+            })
+            .map(|face| face.id)
+            .collect();
+
+        for id in stale {
+            database.remove_face(id);
+        }
+
+        self.version = Version(self.version.0 + 1);
+    }
+
     /// Returns the current [`Version`] of the [`FontSystem`].
     ///
     /// Loading a font will increase the version of a [`FontSystem`].
@@ -352,3 +387,51 @@ pub fn to_color(color: Color) -> cosmic_text::Color {
 
     cosmic_text::Color::rgba(r, g, b, a)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICONS: &[u8] = include_bytes!("../fonts/Iced-Icons.ttf").as_slice();
+
+    fn font_system() -> FontSystem {
+        FontSystem {
+            raw: cosmic_text::FontSystem::new_with_locale_and_db(
+                String::from("en-US"),
+                cosmic_text::fontdb::Database::new(),
+            ),
+            loaded_fonts: HashSet::new(),
+            version: Version::default(),
+        }
+    }
+
+    fn faces(font_system: &mut FontSystem) -> usize {
+        font_system.raw().db().faces().count()
+    }
+
+    #[test]
+    fn borrowed_fonts_are_loaded_once() {
+        let mut font_system = font_system();
+
+        font_system.load_font(Cow::Borrowed(ICONS));
+        font_system.load_font(Cow::Borrowed(ICONS));
+
+        assert_eq!(faces(&mut font_system), 1);
+        assert_eq!(font_system.version(), Version(1));
+    }
+
+    #[test]
+    fn only_reloaded_fonts_are_replaced() {
+        let mut font_system = font_system();
+
+        font_system.load_font(Cow::Owned(ICONS.to_vec()));
+        font_system.load_font(Cow::Owned(ICONS.to_vec()));
+
+        assert_eq!(faces(&mut font_system), 2);
+
+        font_system.reload_font(Cow::Owned(ICONS.to_vec()));
+
+        assert_eq!(faces(&mut font_system), 1);
+        assert_eq!(font_system.version(), Version(3));
+    }
+}
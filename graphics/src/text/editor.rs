@@ -109,139 +109,105 @@ impl editor::Editor for Editor {
         self.buffer().lines.len()
     }
 
-    fn selection(&self) -> Option<String> {
-        self.internal().editor.copy_selection()
-    }
-
-    fn cursor(&self) -> editor::Cursor {
-        let internal = self.internal();
+    fn visible_lines(&self) -> Vec<(usize, Rectangle)> {
+        let width = self.internal().bounds.width;
+        let mut lines: Vec<(usize, Rectangle)> = Vec::new();
+
+        for run in self.buffer().layout_runs() {
+            // Folded lines have no height
+            if run.line_height == 0.0 {
+                continue;
+            }
 
-        if let Ok(Some(cursor)) = internal.cursor.read().as_deref() {
-            return cursor.clone();
+            match lines.last_mut() {
+                Some((line, bounds)) if *line == run.line_i => {
+                    bounds.height += run.line_height;
+                }
+                _ => {
+                    lines.push((
+                        run.line_i,
+                        Rectangle {
+                            x: 0.0,
+                            y: run.line_top,
+                            width,
+                            height: run.line_height,
+                        },
+                    ));
+                }
+            }
         }
 
-        let cursor = internal.editor.cursor();
-        let buffer = buffer_from_editor(&internal.editor);
+        lines
+    }
 
-        let cursor = match internal.editor.selection_bounds() {
-            Some((start, end)) => {
-                let line_height = buffer.metrics().line_height;
-                let selected_lines = end.line - start.line + 1;
+    fn selection(&self) -> Option<String> {
+        self.internal().editor.copy_selection()
+    }
 
-                let visual_lines_offset =
-                    visual_lines_offset(start.line, buffer);
+    fn selection_anchor(&self) -> Option<(usize, usize)> {
+        let editor = &self.internal().editor;
+        let cursor = editor.cursor();
+        let (start, end) = editor.selection_bounds()?;
 
-                let regions = buffer
-                    .lines
-                    .iter()
-                    .skip(start.line)
-                    .take(selected_lines)
-                    .enumerate()
-                    .flat_map(|(i, line)| {
-                        highlight_line(
-                            line,
-                            if i == 0 { start.index } else { 0 },
-                            if i == selected_lines - 1 {
-                                end.index
-                            } else {
-                                line.text().len()
-                            },
-                        )
-                    })
-                    .enumerate()
-                    .filter_map(|(visual_line, (x, width))| {
-                        if width > 0.0 {
-                            Some(Rectangle {
-                                x,
-                                width,
-                                y: (visual_line as i32 + visual_lines_offset)
-                                    as f32
-                                    * line_height
-                                    - buffer.scroll().vertical,
-                                height: line_height,
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
+        let anchor = if cursor.line == start.line && cursor.index == start.index
+        {
+            end
+        } else {
+            start
+        };
 
-                Cursor::Selection(regions)
-            }
-            _ => {
-                let line_height = buffer.metrics().line_height;
+        Some((anchor.line, anchor.index))
+    }
 
-                let visual_lines_offset =
-                    visual_lines_offset(cursor.line, buffer);
+    fn set_cursor(
+        &mut self,
+        position: (usize, usize),
+        anchor: Option<(usize, usize)>,
+    ) {
+        let editor =
+            self.0.take().expect("Editor should always be initialized");
 
-                let line = buffer
-                    .lines
-                    .get(cursor.line)
-                    .expect("Cursor line should be present");
+        let mut internal = Arc::try_unwrap(editor)
+            .expect("Editor cannot have multiple strong references");
 
-                let layout =
-                    line.layout_opt().expect("Line layout should be cached");
+        // Clear cursor cache
+        let _ = internal
+            .cursor
+            .write()
+            .expect("Write to cursor cache")
+            .take();
 
-                let mut lines = layout.iter().enumerate();
+        let editor = &mut internal.editor;
+        let buffer = buffer_from_editor(editor);
+
+        let cursor = clamp_cursor(buffer, position);
+        let selection = anchor
+            .map(|anchor| clamp_cursor(buffer, anchor))
+            .filter(|anchor| *anchor != cursor);
+
+        editor.set_cursor(cursor);
+        editor.set_selection(
+            selection.map_or(cosmic_text::Selection::None, |anchor| {
+                cosmic_text::Selection::Normal(anchor)
+            }),
+        );
 
-                let (visual_line, offset) = lines
-                    .find_map(|(i, line)| {
-                        let start = line
-                            .glyphs
-                            .first()
-                            .map(|glyph| glyph.start)
-                            .unwrap_or(0);
-                        let end = line
-                            .glyphs
-                            .last()
-                            .map(|glyph| glyph.end)
-                            .unwrap_or(0);
+        self.0 = Some(Arc::new(internal));
+    }
 
-                        let is_cursor_before_start = start > cursor.index;
+    fn cursor(&self) -> editor::Cursor {
+        let internal = self.internal();
 
-                        let is_cursor_before_end = match cursor.affinity {
-                            cosmic_text::Affinity::Before => {
-                                cursor.index <= end
-                            }
-                            cosmic_text::Affinity::After => cursor.index < end,
-                        };
-
-                        if is_cursor_before_start {
-                            // Sometimes, the glyph we are looking for is right
-                            // between lines. This can happen when a line wraps
-                            // on a space.
-                            // In that case, we can assume the cursor is at the
-                            // end of the previous line.
-                            // i is guaranteed to be > 0 because `start` is always
-                            // 0 for the first line, so there is no way for the
-                            // cursor to be before it.
-                            Some((i - 1, layout[i - 1].w))
-                        } else if is_cursor_before_end {
-                            let offset = line
-                                .glyphs
-                                .iter()
-                                .take_while(|glyph| cursor.index > glyph.start)
-                                .map(|glyph| glyph.w)
-                                .sum();
-
-                            Some((i, offset))
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or((
-                        layout.len().saturating_sub(1),
-                        layout.last().map(|line| line.w).unwrap_or(0.0),
-                    ));
+        if let Ok(Some(cursor)) = internal.cursor.read().as_deref() {
+            return cursor.clone();
+        }
 
-                Cursor::Caret(Point::new(
-                    offset,
-                    (visual_lines_offset + visual_line as i32) as f32
-                        * line_height
-                        - buffer.scroll().vertical,
-                ))
-            }
-        };
+        let cursor = to_cursor(
+            buffer_from_editor(&internal.editor),
+            internal.editor.cursor(),
+            internal.editor.selection_bounds(),
+        )
+        .expect("Line layout should be cached");
 
         *internal.cursor.write().expect("Write to cursor cache") =
             Some(cursor.clone());
@@ -249,6 +215,46 @@ impl editor::Editor for Editor {
         cursor
     }
 
+    fn cursor_at(
+        &self,
+        position: (usize, usize),
+        anchor: Option<(usize, usize)>,
+    ) -> Option<editor::Cursor> {
+        let buffer = self.buffer();
+
+        let cursor = clamp_cursor(buffer, position);
+        let selection = anchor
+            .map(|anchor| clamp_cursor(buffer, anchor))
+            .filter(|anchor| *anchor != cursor)
+            .map(|anchor| {
+                if (anchor.line, anchor.index) < (cursor.line, cursor.index) {
+                    (anchor, cursor)
+                } else {
+                    (cursor, anchor)
+                }
+            });
+
+        let (first, last) = selection
+            .map_or((cursor.line, cursor.line), |(start, end)| {
+                (start.line, end.line)
+            });
+
+        // Every line up to the cursor must be laid out to find its
+        // visual position
+        let is_laid_out = buffer
+            .lines
+            .get(buffer.scroll().line.min(first)..=last)
+            .is_some_and(|lines| {
+                lines.iter().all(|line| line.layout_opt().is_some())
+            });
+
+        if !is_laid_out {
+            return None;
+        }
+
+        to_cursor(buffer, cursor, selection)
+    }
+
     fn cursor_position(&self) -> (usize, usize) {
         let cursor = self.internal().editor.cursor();
 
@@ -278,48 +284,58 @@ impl editor::Editor for Editor {
         match action {
             // Motion events
             Action::Move(motion) => {
-                if let Some((start, end)) = editor.selection_bounds() {
-                    editor.set_selection(cosmic_text::Selection::None);
-
-                    match motion {
-                        // These motions are performed as-is even when a selection
-                        // is present
-                        Motion::Home
-                        | Motion::End
-                        | Motion::DocumentStart
-                        | Motion::DocumentEnd => {
-                            editor.action(
-                                font_system.raw(),
-                                cosmic_text::Action::Motion(to_motion(motion)),
-                            );
+                // Matches are searched by the owner of the editor
+                if let Some(cosmic_motion) = to_motion(motion) {
+                    if let Some((start, end)) = editor.selection_bounds() {
+                        editor.set_selection(cosmic_text::Selection::None);
+
+                        match motion {
+                            // These motions are performed as-is even when a selection
+                            // is present
+                            Motion::Home
+                            | Motion::End
+                            | Motion::DocumentStart
+                            | Motion::DocumentEnd => {
+                                editor.action(
+                                    font_system.raw(),
+                                    cosmic_text::Action::Motion(cosmic_motion),
+                                );
+                            }
+                            // Other motions simply move the cursor to one end of the selection
+                            _ => editor.set_cursor(match motion.direction() {
+                                Direction::Left => start,
+                                Direction::Right => end,
+                            }),
                         }
-                        // Other motions simply move the cursor to one end of the selection
-                        _ => editor.set_cursor(match motion.direction() {
-                            Direction::Left => start,
-                            Direction::Right => end,
-                        }),
+                    } else {
+                        editor.action(
+                            font_system.raw(),
+                            cosmic_text::Action::Motion(cosmic_motion),
+                        );
                     }
-                } else {
-                    editor.action(
-                        font_system.raw(),
-                        cosmic_text::Action::Motion(to_motion(motion)),
-                    );
+
+                    skip_hidden(editor, font_system.raw(), Some(cosmic_motion));
                 }
             }
 
             // Selection events
             Action::Select(motion) => {
-                let cursor = editor.cursor();
+                if let Some(cosmic_motion) = to_motion(motion) {
+                    let cursor = editor.cursor();
 
-                if editor.selection_bounds().is_none() {
-                    editor
-                        .set_selection(cosmic_text::Selection::Normal(cursor));
-                }
+                    if editor.selection_bounds().is_none() {
+                        editor.set_selection(cosmic_text::Selection::Normal(
+                            cursor,
+                        ));
+                    }
 
-                editor.action(
-                    font_system.raw(),
-                    cosmic_text::Action::Motion(to_motion(motion)),
-                );
+                    editor.action(
+                        font_system.raw(),
+                        cosmic_text::Action::Motion(cosmic_motion),
+                    );
+
+                    skip_hidden(editor, font_system.raw(), Some(cosmic_motion));
+                }
 
                 // Deselect if selection matches cursor position
                 if let Some((start, end)) = editor.selection_bounds()
@@ -369,6 +385,12 @@ impl editor::Editor for Editor {
 
             // Editing events
             Action::Edit(edit) => {
+                let topmost_line = editor
+                    .selection_bounds()
+                    .map(|(start, _)| start)
+                    .unwrap_or(editor.cursor())
+                    .line;
+
                 match edit {
                     Edit::Insert(c) => {
                         editor.action(
@@ -417,7 +439,8 @@ impl editor::Editor for Editor {
                     .map(|(start, _)| start)
                     .unwrap_or(cursor);
 
-                internal.topmost_line_changed = Some(selection_start.line);
+                internal.topmost_line_changed =
+                    Some(topmost_line.min(selection_start.line));
             }
 
             // Mouse events
@@ -429,6 +452,8 @@ impl editor::Editor for Editor {
                         y: position.y as i32,
                     },
                 );
+
+                skip_hidden(editor, font_system.raw(), None);
             }
             Action::Drag(position) => {
                 editor.action(
@@ -439,6 +464,8 @@ impl editor::Editor for Editor {
                     },
                 );
 
+                skip_hidden(editor, font_system.raw(), None);
+
                 // Deselect if selection matches cursor position
                 if let Some((start, end)) = editor.selection_bounds()
                     && start.line == end.line
@@ -456,6 +483,52 @@ impl editor::Editor for Editor {
                     },
                 );
             }
+
+            // Folded lines keep their text, but take no space
+            Action::Fold(lines) => {
+                let attributes = hidden(text::to_attributes(internal.font));
+
+                for line in buffer_mut_from_editor(editor)
+                    .lines
+                    .iter_mut()
+                    .take(lines.end)
+                    .skip(lines.start + 1)
+                {
+                    let _ = line.set_attrs_list(cosmic_text::AttrsList::new(
+                        &attributes,
+                    ));
+                }
+            }
+            Action::Unfold(line) => {
+                let attributes = text::to_attributes(internal.font);
+
+                for hidden in buffer_mut_from_editor(editor)
+                    .lines
+                    .iter_mut()
+                    .skip(line + 1)
+                    .take_while(|line| is_hidden(line))
+                {
+                    let _ = hidden.set_attrs_list(cosmic_text::AttrsList::new(
+                        &attributes,
+                    ));
+                }
+
+                // Unfolded lines need to be highlighted again
+                internal.topmost_line_changed = Some(
+                    internal
+                        .topmost_line_changed
+                        .map_or(line + 1, |topmost| topmost.min(line + 1)),
+                );
+            }
+
+            // History and multiple cursors are handled by the owner of the
+            // editor
+            Action::Undo
+            | Action::Redo
+            | Action::AddCursor(_)
+            | Action::ClearCursors
+            | Action::SelectNextOccurrence
+            | Action::SelectBox { .. } => {}
         }
 
         self.0 = Some(Arc::new(internal));
@@ -509,8 +582,14 @@ impl editor::Editor for Editor {
             log::trace!("Updating font of `Editor`...");
 
             for line in buffer.lines.iter_mut() {
+                let attributes = text::to_attributes(new_font);
+
                 let _ = line.set_attrs_list(cosmic_text::AttrsList::new(
-                    &text::to_attributes(new_font),
+                    &if is_hidden(line) {
+                        hidden(attributes)
+                    } else {
+                        attributes
+                    },
                 ));
             }
 
@@ -583,21 +662,25 @@ impl editor::Editor for Editor {
         let buffer = buffer_from_editor(&internal.editor);
 
         let scroll = buffer.scroll();
-        let mut window = (internal.bounds.height / buffer.metrics().line_height)
-            .ceil() as i32;
+        let line_height = buffer.metrics().line_height;
+        let mut window = internal.bounds.height;
 
         let last_visible_line = buffer.lines[scroll.line..]
             .iter()
             .enumerate()
             .find_map(|(i, line)| {
-                let visible_lines = line
+                let height: f32 = line
                     .layout_opt()
                     .as_ref()
                     .expect("Line layout should be cached")
-                    .len() as i32;
+                    .iter()
+                    .map(|visual_line| {
+                        visual_line.line_height_opt.unwrap_or(line_height)
+                    })
+                    .sum();
 
-                if window > visible_lines {
-                    window -= visible_lines;
+                if window > height {
+                    window -= height;
                     None
                 } else {
                     Some(scroll.line + i)
@@ -625,9 +708,23 @@ impl editor::Editor for Editor {
         for line in &mut buffer_mut_from_editor(&mut internal.editor).lines
             [current_line..=last_visible_line]
         {
+            let highlights = highlighter.highlight_line(line.text());
+
+            // Folded lines are highlighted too; so the state of the
+            // highlighter stays correct below them
+            if is_hidden(line) {
+                highlights.for_each(drop);
+
+                let _ = line.set_attrs_list(cosmic_text::AttrsList::new(
+                    &hidden(attributes.clone()),
+                ));
+
+                continue;
+            }
+
             let mut list = cosmic_text::AttrsList::new(&attributes);
 
-            for (range, highlight) in highlighter.highlight_line(line.text()) {
+            for (range, highlight) in highlights {
                 let format = format_highlight(&highlight);
 
                 if format.color.is_some() || format.font.is_some() {
@@ -724,10 +821,13 @@ fn highlight_line(
     line: &cosmic_text::BufferLine,
     from: usize,
     to: usize,
-) -> impl Iterator<Item = (f32, f32)> + '_ {
+    line_height: f32,
+) -> impl Iterator<Item = (f32, f32, f32)> + '_ {
     let layout = line.layout_opt().map(Vec::as_slice).unwrap_or_default();
 
     layout.iter().map(move |visual_line| {
+        let height = visual_line.line_height_opt.unwrap_or(line_height);
+
         let start = visual_line
             .glyphs
             .first()
@@ -742,9 +842,9 @@ fn highlight_line(
         let range = start.max(from)..end.min(to);
 
         if range.is_empty() {
-            (0.0, 0.0)
+            (0.0, 0.0, height)
         } else if range.start == start && range.end == end {
-            (0.0, visual_line.w)
+            (0.0, visual_line.w, height)
         } else {
             let first_glyph = visual_line
                 .glyphs
@@ -762,28 +862,224 @@ fn highlight_line(
                 .map(|glyph| glyph.w)
                 .sum();
 
-            (x, width)
+            (x, width, height)
         }
     })
 }
 
-fn visual_lines_offset(line: usize, buffer: &cosmic_text::Buffer) -> i32 {
+/// Computes the visual [`Cursor`] of the given position and selection.
+///
+/// Returns `None` if the line of the position is not laid out.
+fn to_cursor(
+    buffer: &cosmic_text::Buffer,
+    cursor: cosmic_text::Cursor,
+    selection: Option<(cosmic_text::Cursor, cosmic_text::Cursor)>,
+) -> Option<Cursor> {
+    let cursor = match selection {
+        Some((start, end)) => {
+            let line_height = buffer.metrics().line_height;
+            let selected_lines = end.line - start.line + 1;
+
+            let mut y = visual_lines_offset(start.line, buffer)
+                - buffer.scroll().vertical;
+
+            let regions = buffer
+                .lines
+                .iter()
+                .skip(start.line)
+                .take(selected_lines)
+                .enumerate()
+                .flat_map(|(i, line)| {
+                    highlight_line(
+                        line,
+                        if i == 0 { start.index } else { 0 },
+                        if i == selected_lines - 1 {
+                            end.index
+                        } else {
+                            line.text().len()
+                        },
+                        line_height,
+                    )
+                })
+                .filter_map(|(x, width, height)| {
+                    let top = y;
+                    y += height;
+
+                    (width > 0.0).then_some(Rectangle {
+                        x,
+                        width,
+                        y: top,
+                        height,
+                    })
+                })
+                .collect();
+
+            Cursor::Selection(regions)
+        }
+        _ => {
+            let line_height = buffer.metrics().line_height;
+
+            let line = buffer.lines.get(cursor.line)?;
+            let layout = line.layout_opt()?;
+
+            let mut lines = layout.iter().enumerate();
+
+            let (visual_line, offset) = lines
+                .find_map(|(i, line)| {
+                    let start = line
+                        .glyphs
+                        .first()
+                        .map(|glyph| glyph.start)
+                        .unwrap_or(0);
+                    let end =
+                        line.glyphs.last().map(|glyph| glyph.end).unwrap_or(0);
+
+                    let is_cursor_before_start = start > cursor.index;
+
+                    let is_cursor_before_end = match cursor.affinity {
+                        cosmic_text::Affinity::Before => cursor.index <= end,
+                        cosmic_text::Affinity::After => cursor.index < end,
+                    };
+
+                    if is_cursor_before_start {
+                        // Sometimes, the glyph we are looking for is right
+                        // between lines. This can happen when a line wraps
+                        // on a space.
+                        // In that case, we can assume the cursor is at the
+                        // end of the previous line.
+                        // i is guaranteed to be > 0 because `start` is always
+                        // 0 for the first line, so there is no way for the
+                        // cursor to be before it.
+                        Some((i - 1, layout[i - 1].w))
+                    } else if is_cursor_before_end {
+                        let offset = line
+                            .glyphs
+                            .iter()
+                            .take_while(|glyph| cursor.index > glyph.start)
+                            .map(|glyph| glyph.w)
+                            .sum();
+
+                        Some((i, offset))
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or((
+                    layout.len().saturating_sub(1),
+                    layout.last().map(|line| line.w).unwrap_or(0.0),
+                ));
+
+            let y = visual_lines_offset(cursor.line, buffer)
+                + layout[..visual_line]
+                    .iter()
+                    .map(|line| line.line_height_opt.unwrap_or(line_height))
+                    .sum::<f32>();
+
+            Cursor::Caret(Point::new(offset, y - buffer.scroll().vertical))
+        }
+    };
+
+    Some(cursor)
+}
+
+/// Clamps the given position to the text of the buffer.
+fn clamp_cursor(
+    buffer: &cosmic_text::Buffer,
+    (line, index): (usize, usize),
+) -> cosmic_text::Cursor {
+    let line = line.min(buffer.lines.len().saturating_sub(1));
+    let text = buffer.lines.get(line).map_or("", |line| line.text());
+
+    let mut index = index.min(text.len());
+
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+
+    cosmic_text::Cursor::new(line, index)
+}
+
+/// Returns the vertical offset of the given line from the topmost line of
+/// the buffer.
+fn visual_lines_offset(line: usize, buffer: &cosmic_text::Buffer) -> f32 {
     let scroll = buffer.scroll();
+    let line_height = buffer.metrics().line_height;
 
     let start = scroll.line.min(line);
     let end = scroll.line.max(line);
 
-    let visual_lines_offset: usize = buffer.lines[start..]
+    let visual_lines_offset: f32 = buffer.lines[start..]
         .iter()
         .take(end - start)
-        .map(|line| line.layout_opt().map(Vec::len).unwrap_or_default())
+        .flat_map(|line| {
+            line.layout_opt().map(Vec::as_slice).unwrap_or_default()
+        })
+        .map(|line| line.line_height_opt.unwrap_or(line_height))
         .sum();
 
-    visual_lines_offset as i32 * if scroll.line < line { 1 } else { -1 }
+    visual_lines_offset * if scroll.line < line { 1.0 } else { -1.0 }
+}
+
+/// Returns whether the given line is hidden by a fold.
+fn is_hidden(line: &cosmic_text::BufferLine) -> bool {
+    line.attrs_list().defaults().metrics_opt.is_some()
+}
+
+/// Returns the attributes of a line hidden by a fold.
+///
+/// Hidden lines are laid out with no size, so they take no space.
+fn hidden(attributes: cosmic_text::Attrs<'_>) -> cosmic_text::Attrs<'_> {
+    attributes
+        .metrics(cosmic_text::Metrics::new(0.0, 0.0))
+        .color(cosmic_text::Color::rgba(0, 0, 0, 0))
 }
 
-fn to_motion(motion: Motion) -> cosmic_text::Motion {
-    match motion {
+/// Moves the cursor of the editor out of the lines hidden by a fold.
+///
+/// The given motion is repeated until the cursor leaves them. If there is
+/// no motion or it gets stuck, the cursor moves to the end of the line
+/// before them.
+fn skip_hidden(
+    editor: &mut cosmic_text::Editor<'static>,
+    font_system: &mut cosmic_text::FontSystem,
+    motion: Option<cosmic_text::Motion>,
+) {
+    let is_cursor_hidden = |editor: &cosmic_text::Editor<'static>| {
+        buffer_from_editor(editor)
+            .lines
+            .get(editor.cursor().line)
+            .is_some_and(is_hidden)
+    };
+
+    while is_cursor_hidden(editor) {
+        let cursor = editor.cursor();
+
+        if let Some(motion) = motion {
+            editor.action(font_system, cosmic_text::Action::Motion(motion));
+
+            if editor.cursor() != cursor {
+                continue;
+            }
+        }
+
+        let buffer = buffer_from_editor(editor);
+        let line = (0..cursor.line)
+            .rev()
+            .find(|i| !is_hidden(&buffer.lines[*i]))
+            .unwrap_or(0);
+        let index = buffer.lines[line].text().len();
+
+        editor.set_cursor(cosmic_text::Cursor::new(line, index));
+        break;
+    }
+}
+
+/// Returns the [`cosmic_text::Motion`] of the given [`Motion`], if any.
+///
+/// Matches are searched by the owner of the editor, so there is no
+/// equivalent for [`Motion::NextMatch`] and [`Motion::PreviousMatch`].
+fn to_motion(motion: Motion) -> Option<cosmic_text::Motion> {
+    Some(match motion {
         Motion::Left => cosmic_text::Motion::Left,
         Motion::Right => cosmic_text::Motion::Right,
         Motion::Up => cosmic_text::Motion::Up,
@@ -796,7 +1092,8 @@ fn to_motion(motion: Motion) -> cosmic_text::Motion {
         Motion::PageDown => cosmic_text::Motion::PageDown,
         Motion::DocumentStart => cosmic_text::Motion::BufferStart,
         Motion::DocumentEnd => cosmic_text::Motion::BufferEnd,
-    }
+        Motion::NextMatch | Motion::PreviousMatch => return None,
+    })
 }
 
 fn buffer_from_editor<'a, 'b>(
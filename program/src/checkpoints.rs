@@ -0,0 +1,89 @@
+use std::fmt;
+
+/// A checkpoint strategy for time-travel debugging.
+///
+/// By default, rewinding a [`Program`](crate::Program) boots it from scratch
+/// and replays every message up to the desired point in time. With
+/// [`Checkpoints`], the state is snapshotted periodically, so rewinding only
+/// replays the messages after the nearest checkpoint.
+///
+/// Checkpoints are kept in a ring buffer; once its capacity is reached, the
+/// oldest checkpoint is discarded.
+pub struct Checkpoints<State> {
+    /// The amount of messages between checkpoints.
+    pub interval: usize,
+    /// The maximum amount of checkpoints kept in memory.
+    pub capacity: usize,
+    snapshot: Box<dyn Fn(&State) -> State>,
+}
+
+impl<State> Checkpoints<State> {
+    /// Creates new [`Checkpoints`] with the given snapshot strategy.
+    ///
+    /// By default, a checkpoint is taken every 100 messages and up to
+    /// 50 checkpoints are kept.
+    pub fn new(snapshot: impl Fn(&State) -> State + 'static) -> Self {
+        Self {
+            interval: 100,
+            capacity: 50,
+            snapshot: Box::new(snapshot),
+        }
+    }
+
+    /// Creates new [`Checkpoints`] that snapshot the state by cloning it.
+    pub fn cloned() -> Self
+    where
+        State: Clone + 'static,
+    {
+        Self::new(State::clone)
+    }
+
+    /// Sets the amount of messages between checkpoints.
+    pub fn interval(mut self, messages: usize) -> Self {
+        self.interval = messages.max(1);
+        self
+    }
+
+    /// Sets the maximum amount of checkpoints kept in memory.
+    pub fn capacity(mut self, checkpoints: usize) -> Self {
+        self.capacity = checkpoints;
+        self
+    }
+
+    /// Takes a snapshot of the given state.
+    pub fn snapshot(&self, state: &State) -> State {
+        (self.snapshot)(state)
+    }
+}
+
+impl<State> fmt::Debug for Checkpoints<State> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checkpoints")
+            .field("interval", &self.interval)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let checkpoints =
+            Checkpoints::new(|state: &Vec<u8>| state[..1].to_vec())
+                .interval(0)
+                .capacity(3);
+
+        assert_eq!(checkpoints.interval, 1);
+        assert_eq!(checkpoints.capacity, 3);
+        assert_eq!(checkpoints.snapshot(&vec![1, 2, 3]), vec![1]);
+
+        let cloned = Checkpoints::cloned();
+
+        assert_eq!(cloned.interval, 100);
+        assert_eq!(cloned.capacity, 50);
+        assert_eq!(cloned.snapshot(&vec![1, 2, 3]), vec![1, 2, 3]);
+    }
+}
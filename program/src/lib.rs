@@ -6,8 +6,13 @@ pub use iced_runtime::futures;
 
 pub mod message;
 
+#[cfg(feature = "session")]
+pub mod session;
+
+mod checkpoints;
 mod preset;
 
+pub use checkpoints::Checkpoints;
 pub use preset::Preset;
 
 use crate::core::renderer;
@@ -116,6 +121,24 @@ pub trait Program: Sized {
     fn presets(&self) -> &[Preset<Self::State, Self::Message>] {
         &[]
     }
+
+    /// Returns the [`Checkpoints`] strategy of the [`Program`], if any.
+    ///
+    /// When time traveling, devtools snapshot the state with it; so that
+    /// rewinding only replays the messages after the nearest checkpoint,
+    /// instead of booting the [`Program`] again.
+    fn checkpoints(&self) -> Option<&Checkpoints<Self::State>> {
+        None
+    }
+
+    /// Returns a human-readable representation of the given state, if
+    /// the [`Program`] supports it.
+    ///
+    /// This is used by devtools to show how each message changes the
+    /// state while time traveling.
+    fn debug_state(&self, _state: &Self::State) -> Option<String> {
+        None
+    }
 }
 
 /// Decorates a [`Program`] with the given title function.
@@ -651,6 +674,104 @@ pub fn with_executor<P: Program, E: Executor>(
     }
 }
 
+/// Decorates a [`Program`] with the given [`session::Recorder`].
+#[cfg(feature = "session")]
+pub fn with_recorder<P: Program>(
+    program: P,
+    recorder: session::Recorder<P::Message>,
+) -> impl Program<State = P::State, Message = P::Message, Theme = P::Theme> {
+    struct WithRecorder<P: Program> {
+        program: P,
+        recorder: session::Recorder<P::Message>,
+    }
+
+    impl<P: Program> Program for WithRecorder<P> {
+        type State = P::State;
+        type Message = P::Message;
+        type Theme = P::Theme;
+        type Renderer = P::Renderer;
+        type Executor = P::Executor;
+
+        fn update(
+            &self,
+            state: &mut Self::State,
+            message: Self::Message,
+        ) -> Task<Self::Message> {
+            self.recorder.record(&message);
+            self.program.update(state, message)
+        }
+
+        fn name() -> &'static str {
+            P::name()
+        }
+
+        fn settings(&self) -> Settings {
+            self.program.settings()
+        }
+
+        fn window(&self) -> Option<window::Settings> {
+            self.program.window()
+        }
+
+        fn boot(&self) -> (Self::State, Task<Self::Message>) {
+            self.program.boot()
+        }
+
+        fn view<'a>(
+            &self,
+            state: &'a Self::State,
+            window: window::Id,
+        ) -> Element<'a, Self::Message, Self::Theme, Self::Renderer> {
+            self.program.view(state, window)
+        }
+
+        fn title(&self, state: &Self::State, window: window::Id) -> String {
+            self.program.title(state, window)
+        }
+
+        fn subscription(
+            &self,
+            state: &Self::State,
+        ) -> Subscription<Self::Message> {
+            self.program.subscription(state)
+        }
+
+        fn theme(
+            &self,
+            state: &Self::State,
+            window: window::Id,
+        ) -> Option<Self::Theme> {
+            self.program.theme(state, window)
+        }
+
+        fn style(
+            &self,
+            state: &Self::State,
+            theme: &Self::Theme,
+        ) -> theme::Style {
+            self.program.style(state, theme)
+        }
+
+        fn scale_factor(&self, state: &Self::State, window: window::Id) -> f32 {
+            self.program.scale_factor(state, window)
+        }
+
+        fn presets(&self) -> &[Preset<Self::State, Self::Message>] {
+            self.program.presets()
+        }
+
+        fn checkpoints(&self) -> Option<&Checkpoints<Self::State>> {
+            self.program.checkpoints()
+        }
+
+        fn debug_state(&self, state: &Self::State) -> Option<String> {
+            self.program.debug_state(state)
+        }
+    }
+
+    WithRecorder { program, recorder }
+}
+
 /// The renderer of some [`Program`].
 pub trait Renderer:
     text::Renderer<Font = Font> + compositor::Default + renderer::Headless
@@ -0,0 +1,229 @@
+//! Record the message stream of a [`Program`] and replay it later.
+//!
+//! A [`Recorder`] writes every message processed by a [`Program`] to a
+//! session file. A [`Session`] loads that file back, so it can be replayed
+//! headlessly in the emulator of `iced_test`—reproducing the exact same state,
+//! views, and tasks without a window:
+//!
+//! ```ignore
+//! use iced_program::session::Session;
+//!
+//! let session = Session::load("bug.session")?;
+//! let state = iced_test::replay(&program, session, (1024.0, 768.0));
+//! ```
+//!
+//! Messages processed inside [`suspend`] are not recorded; so tools that replay
+//! past messages (like time travel) do not duplicate them in the session file.
+//!
+//! [`Program`]: crate::Program
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use std::cell::Cell;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+const MAGIC: &[u8] = b"iced-session";
+const VERSION: u32 = 1;
+
+thread_local! {
+    static IS_SUSPENDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs the given closure without recording any of the messages it processes
+/// in the current thread.
+pub fn suspend<T>(f: impl FnOnce() -> T) -> T {
+    let was_suspended = IS_SUSPENDED.replace(true);
+    let result = f();
+    IS_SUSPENDED.set(was_suspended);
+
+    result
+}
+
+/// Writes the messages of a [`Program`](crate::Program) to a session file.
+pub struct Recorder<Message> {
+    writer: Mutex<Option<BufWriter<File>>>,
+    encode: fn(&mut BufWriter<File>, &Message) -> bincode::Result<()>,
+}
+
+impl<Message> Recorder<Message> {
+    /// Creates a new [`Recorder`] that writes to the file at the given path.
+    ///
+    /// The file will be truncated if it exists.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error>
+    where
+        Message: Serialize,
+    {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(MAGIC)?;
+        bincode::serialize_into(&mut writer, &VERSION)?;
+        writer.flush()?;
+
+        Ok(Self {
+            writer: Mutex::new(Some(writer)),
+            encode: |writer, message| bincode::serialize_into(writer, message),
+        })
+    }
+
+    /// Records the given message.
+    ///
+    /// Messages are flushed immediately, so the session survives crashes.
+    /// If writing fails, the error is logged and recording stops.
+    ///
+    /// Nothing is recorded while recording is [suspended](suspend).
+    pub fn record(&self, message: &Message) {
+        if IS_SUSPENDED.get() {
+            return;
+        }
+
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+        let Some(file) = writer.as_mut() else {
+            return;
+        };
+
+        let result = (self.encode)(file, message)
+            .map_err(Error::from)
+            .and_then(|()| Ok(file.flush()?));
+
+        if let Err(error) = result {
+            log::error!("session recording stopped: {error}");
+            *writer = None;
+        }
+    }
+}
+
+impl<Message> std::fmt::Debug for Recorder<Message> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+/// A recorded stream of messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session<Message> {
+    /// The messages of the [`Session`], in the order they were processed.
+    pub messages: Vec<Message>,
+}
+
+impl<Message> Session<Message>
+where
+    Message: DeserializeOwned,
+{
+    /// Loads the [`Session`] from the file at the given path.
+    ///
+    /// A truncated message at the end of the file (e.g. due to a crash while
+    /// recording) is ignored.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a [`Session`] from the given reader.
+    pub fn read(mut reader: impl BufRead) -> Result<Self, Error> {
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+
+        if magic != MAGIC {
+            return Err(Error::InvalidFormat);
+        }
+
+        let version: u32 = bincode::deserialize_from(&mut reader)?;
+
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let mut messages = Vec::new();
+
+        while !reader.fill_buf()?.is_empty() {
+            match bincode::deserialize_from(&mut reader) {
+                Ok(message) => messages.push(message),
+                Err(error)
+                    if matches!(
+                        &*error,
+                        bincode::ErrorKind::Io(error)
+                            if error.kind() == io::ErrorKind::UnexpectedEof
+                    ) =>
+                {
+                    break;
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        Ok(Self { messages })
+    }
+}
+
+/// A session error.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+    /// An IO operation failed.
+    #[error("an IO operation failed: {0}")]
+    IOFailed(Arc<io::Error>),
+    /// The encoding or decoding of a message failed.
+    #[error("the encoding or decoding of a message failed: {0}")]
+    CodingFailed(Arc<bincode::Error>),
+    /// The file is not a session.
+    #[error("the file is not a session")]
+    InvalidFormat,
+    /// The session was recorded with an unsupported format version.
+    #[error("the session format version ({0}) is not supported")]
+    UnsupportedVersion(u32),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::IOFailed(Arc::new(error))
+    }
+}
+
+impl From<bincode::Error> for Error {
+    fn from(error: bincode::Error) -> Self {
+        Self::CodingFailed(Arc::new(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "iced_program_session_{}.session",
+            std::process::id()
+        ));
+
+        let recorder = Recorder::create(&path).unwrap();
+
+        for message in ["Increment", "Decrement", "Increment"] {
+            recorder.record(&message.to_owned());
+        }
+
+        drop(recorder);
+
+        // Simulate a crash in the middle of a write
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes.extend([42, 0, 0]);
+
+        let session = Session::<String>::read(bytes.as_slice()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            session.messages,
+            vec!["Increment", "Decrement", "Increment"]
+        );
+
+        assert!(matches!(
+            Session::<String>::read(&b"not a session"[..]),
+            Err(Error::InvalidFormat)
+        ));
+    }
+}
@@ -11,6 +11,7 @@ use crate::graphics::mesh;
 use crate::graphics::{self, Shell};
 
 use std::borrow::Cow;
+use std::path::Path;
 
 /// A renderer `A` with a fallback strategy `B`.
 ///
@@ -83,6 +84,10 @@ where
     ) {
         delegate!(self, renderer, renderer.allocate_image(handle, callback));
     }
+
+    fn invalidate_asset(&mut self, path: &Path) {
+        delegate!(self, renderer, renderer.invalidate_asset(path));
+    }
 }
 
 impl<A, B> core::text::Renderer for Renderer<A, B>
@@ -358,6 +363,10 @@ where
         delegate!(self, compositor, compositor.load_font(font));
     }
 
+    fn reload_font(&mut self, font: Cow<'static, [u8]>) {
+        delegate!(self, compositor, compositor.reload_font(font));
+    }
+
     fn information(&self) -> compositor::Information {
         delegate!(self, compositor, compositor.information())
     }
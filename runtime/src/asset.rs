@@ -0,0 +1,315 @@
+//! Watch asset files and reload them as they change.
+//!
+//! This is a lightweight alternative to hotpatching while iterating on art:
+//! [`watch`] notices when some files change on disk and [`reload`] evicts
+//! them from the renderer caches; so their new version shows up instantly.
+//!
+//! ```
+//! # mod iced {
+//! #     pub use iced_runtime::Task;
+//! #     pub use iced_runtime::asset;
+//! #     pub use iced_runtime::futures::Subscription;
+//! # }
+//! use iced::asset;
+//! use iced::{Subscription, Task};
+//!
+//! use std::path::PathBuf;
+//!
+//! enum Message {
+//!     AssetChanged(PathBuf),
+//! }
+//!
+//! fn update(message: Message) -> Task<Message> {
+//!     match message {
+//!         Message::AssetChanged(path) => asset::reload(path),
+//!     }
+//! }
+//!
+//! fn subscription() -> Subscription<Message> {
+//!     asset::watch(["assets"]).map(Message::AssetChanged)
+//! }
+//! ```
+use crate::Action;
+use crate::font;
+use crate::futures::futures::channel::mpsc;
+use crate::futures::{MaybeSend, Subscription};
+use crate::image;
+use crate::task::{self, Task};
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Watches the given files and directories, producing the path of any file
+/// that is created or modified.
+///
+/// Directories are watched recursively.
+pub fn watch(
+    paths: impl IntoIterator<Item = impl Into<PathBuf>>,
+) -> Subscription<PathBuf> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+
+    Subscription::run_with(paths, |paths| {
+        let paths = paths.clone();
+        let (sender, receiver) = mpsc::unbounded();
+
+        let _ = thread::spawn(move || {
+            let mut files = scan(&paths);
+
+            while !sender.is_closed() {
+                thread::sleep(POLL_INTERVAL);
+
+                let current = scan(&paths);
+
+                for (path, modified) in &current {
+                    if files.get(path) != Some(modified) {
+                        let _ = sender.unbounded_send(path.clone());
+                    }
+                }
+
+                files = current;
+            }
+        });
+
+        receiver
+    })
+}
+
+/// Reloads the asset at the given path and redraws every window.
+///
+/// Any image or vector image loaded from the path is evicted from the
+/// renderer caches; and fonts (`.ttf` and `.otf` files) are loaded again,
+/// replacing their previous version.
+///
+/// Any other kind of asset (like a theme file) needs to be reloaded by
+/// your own logic.
+pub fn reload<T>(path: impl Into<PathBuf>) -> Task<T>
+where
+    T: MaybeSend + 'static,
+{
+    let path = path.into();
+
+    let is_font = path.extension().is_some_and(|extension| {
+        extension.eq_ignore_ascii_case("ttf")
+            || extension.eq_ignore_ascii_case("otf")
+    });
+
+    let font = if is_font {
+        let path = path.clone();
+
+        task::blocking(move |mut sender| {
+            if let Ok(bytes) = fs::read(path) {
+                let _ = sender.try_send(bytes);
+            }
+        })
+        .then(|bytes| font::reload(bytes).discard())
+    } else {
+        Task::none()
+    };
+
+    image::invalidate(path)
+        .chain(font)
+        .chain(task::effect(Action::Reload))
+}
+
+fn scan(paths: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
+    let mut files = HashMap::new();
+    let mut directories = HashSet::new();
+
+    for path in paths {
+        visit(path, &mut files, &mut directories);
+    }
+
+    files
+}
+
+/// Collects the files in the given path, visiting every directory only
+/// once; so symbolic links pointing to a parent directory do not loop
+/// forever.
+fn visit(
+    path: &Path,
+    files: &mut HashMap<PathBuf, SystemTime>,
+    directories: &mut HashSet<PathBuf>,
+) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_dir() {
+        let Ok(directory) = fs::canonicalize(path) else {
+            return;
+        };
+
+        if !directories.insert(directory) {
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            visit(&entry.path(), files, directories);
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        let _ = files.insert(path.to_path_buf(), modified);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::futures::futures::stream::{self, StreamExt};
+    use crate::futures::futures::task::{Context, Poll, noop_waker_ref};
+    use crate::futures::subscription;
+
+    use std::time::Instant;
+
+    fn directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir()
+            .join(format!("iced_asset_{}_{name}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(directory.join("nested")).expect("Create directory");
+
+        directory
+    }
+
+    #[test]
+    fn scan_visits_directories_recursively() {
+        let directory = directory("scan");
+
+        fs::write(directory.join("a.png"), b"a").expect("Write file");
+        fs::write(directory.join("nested").join("b.svg"), b"b")
+            .expect("Write file");
+
+        let files = scan(&[directory.clone(), directory.join("missing")]);
+
+        let mut paths: Vec<_> = files.into_keys().collect();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                directory.join("a.png"),
+                directory.join("nested").join("b.svg")
+            ]
+        );
+
+        let files = scan(&[directory.join("a.png")]);
+
+        assert_eq!(files.len(), 1);
+        assert!(files.contains_key(&directory.join("a.png")));
+
+        fs::remove_dir_all(directory).expect("Remove directory");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scan_visits_linked_directories_once() {
+        let directory = directory("symlink");
+
+        fs::write(directory.join("nested").join("a.png"), b"a")
+            .expect("Write file");
+        std::os::unix::fs::symlink(
+            &directory,
+            directory.join("nested").join("parent"),
+        )
+        .expect("Create symbolic link");
+
+        let files = scan(&[directory.clone()]);
+
+        assert_eq!(files.len(), 1);
+        assert!(files.contains_key(&directory.join("nested").join("a.png")));
+
+        fs::remove_dir_all(directory).expect("Remove directory");
+    }
+
+    #[test]
+    fn watch_produces_every_changed_file() {
+        let directory = directory("burst");
+
+        let mut recipes = subscription::into_recipes(watch([&directory]));
+        let recipe = recipes.pop().expect("Watch must have a recipe");
+        let mut files = recipe.stream(Box::pin(stream::empty()));
+
+        thread::sleep(POLL_INTERVAL);
+
+        let mut created: Vec<_> = (0..250)
+            .map(|i| directory.join(format!("{i}.png")))
+            .collect();
+
+        for path in &created {
+            fs::write(path, b"burst").expect("Write file");
+        }
+
+        let mut context = Context::from_waker(noop_waker_ref());
+        let mut changed = Vec::new();
+        let start = Instant::now();
+
+        while changed.len() < created.len() {
+            match files.poll_next_unpin(&mut context) {
+                Poll::Ready(Some(path)) => changed.push(path),
+                Poll::Ready(None) => break,
+                Poll::Pending => {
+                    assert!(
+                        start.elapsed() < POLL_INTERVAL * 20,
+                        "Watch must notice every new file"
+                    );
+
+                    thread::sleep(POLL_INTERVAL / 5);
+                }
+            }
+        }
+
+        created.sort();
+        changed.sort();
+        changed.dedup();
+
+        assert_eq!(changed, created);
+
+        drop(files);
+        fs::remove_dir_all(directory).expect("Remove directory");
+    }
+
+    #[test]
+    fn watch_produces_created_files() {
+        let directory = directory("watch");
+
+        let mut recipes = subscription::into_recipes(watch([&directory]));
+        let recipe = recipes.pop().expect("Watch must have a recipe");
+        let mut files = recipe.stream(Box::pin(stream::empty()));
+
+        thread::sleep(POLL_INTERVAL);
+
+        let path = directory.join("nested").join("c.ttf");
+        fs::write(&path, b"c").expect("Write file");
+
+        let mut context = Context::from_waker(noop_waker_ref());
+        let start = Instant::now();
+
+        let changed = loop {
+            if let Poll::Ready(changed) = files.poll_next_unpin(&mut context) {
+                break changed;
+            }
+
+            assert!(
+                start.elapsed() < POLL_INTERVAL * 20,
+                "Watch must notice new files"
+            );
+
+            thread::sleep(POLL_INTERVAL / 5);
+        };
+
+        assert_eq!(changed, Some(path));
+
+        drop(files);
+        fs::remove_dir_all(directory).expect("Remove directory");
+    }
+}
@@ -14,3 +14,13 @@ pub fn load(bytes: impl Into<Cow<'static, [u8]>>) -> Task<Result<(), Error>> {
         channel,
     })
 }
+
+/// Load a new version of a font from its bytes.
+///
+/// Any font previously loaded from memory with the same name is replaced.
+pub fn reload(bytes: impl Into<Cow<'static, [u8]>>) -> Task<Result<(), Error>> {
+    task::oneshot(|channel| Action::ReloadFont {
+        bytes: bytes.into(),
+        channel,
+    })
+}
@@ -3,6 +3,8 @@ use crate::core::image::Handle;
 use crate::futures::futures::channel::oneshot;
 use crate::task::{self, Task};
 
+use std::path::PathBuf;
+
 pub use crate::core::image::{Allocation, Error};
 
 /// An image action.
@@ -10,6 +12,9 @@ pub use crate::core::image::{Allocation, Error};
 pub enum Action {
     /// Allocates the given [`Handle`].
     Allocate(Handle, oneshot::Sender<Result<Allocation, Error>>),
+
+    /// Evicts any cached image loaded from the given path.
+    Invalidate(PathBuf),
 }
 
 /// Allocates an image [`Handle`].
@@ -22,3 +27,10 @@ pub fn allocate(handle: impl Into<Handle>) -> Task<Result<Allocation, Error>> {
         crate::Action::Image(Action::Allocate(handle.into(), sender))
     })
 }
+
+/// Evicts any cached image or vector image loaded from the given path.
+///
+/// The file will be loaded again the next time it is drawn.
+pub fn invalidate<T>(path: impl Into<PathBuf>) -> Task<T> {
+    task::effect(crate::Action::Image(Action::Invalidate(path.into())))
+}
@@ -9,6 +9,8 @@
     html_logo_url = "https://raw.githubusercontent.com/iced-rs/iced/9ab6923e943f784985e9ef9ca28b10278297225d/docs/logo.svg"
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#[cfg(not(target_arch = "wasm32"))]
+pub mod asset;
 pub mod clipboard;
 pub mod font;
 pub mod image;
@@ -43,6 +45,14 @@ pub enum Action<T> {
         channel: oneshot::Sender<Result<(), font::Error>>,
     },
 
+    /// Load a new version of a font from its bytes, replacing the old one.
+    ReloadFont {
+        /// The bytes of the font to reload.
+        bytes: Cow<'static, [u8]>,
+        /// The channel to send back the load result.
+        channel: oneshot::Sender<Result<(), font::Error>>,
+    },
+
     /// Run a widget operation.
     Widget(Box<dyn core::widget::Operation>),
 
@@ -80,6 +90,9 @@ impl<T> Action<T> {
             Action::LoadFont { bytes, channel } => {
                 Err(Action::LoadFont { bytes, channel })
             }
+            Action::ReloadFont { bytes, channel } => {
+                Err(Action::ReloadFont { bytes, channel })
+            }
             Action::Widget(operation) => Err(Action::Widget(operation)),
             Action::Clipboard(action) => Err(Action::Clipboard(action)),
             Action::Window(action) => Err(Action::Window(action)),
@@ -101,6 +114,9 @@ where
             Action::LoadFont { .. } => {
                 write!(f, "Action::LoadFont")
             }
+            Action::ReloadFont { .. } => {
+                write!(f, "Action::ReloadFont")
+            }
             Action::Widget { .. } => {
                 write!(f, "Action::Widget")
             }
@@ -1,5 +1,5 @@
 //! Find and query widgets in your applications.
-pub use iced_selector::{Bounded, Candidate, Selector, Target, Text};
+pub use iced_selector::{Bounded, Candidate, Outline, Selector, Target, Text};
 
 use crate::core::Rectangle;
 
@@ -26,3 +26,8 @@ where
     task::widget(selector.find())
         .map(|target| target.as_ref().and_then(Bounded::visible_bounds))
 }
+
+/// Produces the [`Outline`] of the widget tree.
+pub fn outline() -> Task<Outline> {
+    task::widget(iced_selector::outline())
+}
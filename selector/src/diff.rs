@@ -0,0 +1,148 @@
+//! Diff the lines of two texts.
+
+/// A line of a diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    /// A line present in both texts.
+    Same(String),
+    /// A line only present in the old text.
+    Removed(String),
+    /// A line only present in the new text.
+    Added(String),
+}
+
+impl Line {
+    /// Returns true if the [`Line`] was removed or added.
+    pub fn is_change(&self) -> bool {
+        !matches!(self, Self::Same(_))
+    }
+
+    /// Returns the contents of the [`Line`].
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Same(line) | Self::Removed(line) | Self::Added(line) => line,
+        }
+    }
+}
+
+/// The maximum amount of changed lines diffed precisely.
+///
+/// Beyond this, the changed region is reported as removed and then added,
+/// to keep the quadratic cost of the diff under control.
+const MAX_LINES: usize = 2_000;
+
+/// Diffs the lines of the `old` and `new` texts.
+pub fn lines(old: &str, new: &str) -> Vec<Line> {
+    let old: Vec<_> = old.lines().collect();
+    let new: Vec<_> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let removed = &old[prefix..old.len() - suffix];
+    let added = &new[prefix..new.len() - suffix];
+
+    let mut lines: Vec<_> = old[..prefix]
+        .iter()
+        .map(|line| Line::Same((*line).to_owned()))
+        .collect();
+
+    if removed.len() > MAX_LINES || added.len() > MAX_LINES {
+        lines.extend(
+            removed.iter().map(|line| Line::Removed((*line).to_owned())),
+        );
+        lines.extend(added.iter().map(|line| Line::Added((*line).to_owned())));
+    } else {
+        lines.extend(lcs(removed, added));
+    }
+
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| Line::Same((*line).to_owned())),
+    );
+
+    lines
+}
+
+fn lcs(old: &[&str], new: &[&str]) -> Vec<Line> {
+    let mut table = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            table[i][j] = if old[i] == new[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(Line::Same(old[i].to_owned()));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            lines.push(Line::Removed(old[i].to_owned()));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j].to_owned()));
+            j += 1;
+        }
+    }
+
+    lines.extend(
+        old[i..]
+            .iter()
+            .map(|line| Line::Removed((*line).to_owned())),
+    );
+    lines.extend(new[j..].iter().map(|line| Line::Added((*line).to_owned())));
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_trims_common_lines() {
+        let lines = lines("a\nb\nc\nd", "a\nx\nc\nd");
+
+        assert_eq!(
+            lines,
+            [
+                Line::Same("a".to_owned()),
+                Line::Removed("b".to_owned()),
+                Line::Added("x".to_owned()),
+                Line::Same("c".to_owned()),
+                Line::Same("d".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_caps_large_changes() {
+        let old: String = (0..=MAX_LINES).map(|i| format!("{i}\n")).collect();
+        let new: String = (0..=MAX_LINES).map(|i| format!("{i}!\n")).collect();
+
+        let lines = lines(&old, &new);
+
+        assert_eq!(lines.len(), 2 * (MAX_LINES + 1));
+        assert!(
+            lines[..=MAX_LINES]
+                .iter()
+                .all(|line| matches!(line, Line::Removed(_)))
+        );
+    }
+}
@@ -4,7 +4,11 @@ use iced_core as core;
 mod find;
 mod target;
 
+pub mod diff;
+pub mod outline;
+
 pub use find::{Find, FindAll};
+pub use outline::{Outline, outline};
 pub use target::{Bounded, Candidate, Target, Text};
 
 use crate::core::Point;
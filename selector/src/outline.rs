@@ -0,0 +1,602 @@
+//! Dump the widget tree as a structural outline.
+use crate::core::widget::operation::{
+    Focusable, Outcome, Scrollable, TextInput,
+};
+use crate::core::widget::{Id, Operation};
+use crate::core::{Rectangle, Vector};
+use crate::diff::{self, Line};
+
+use std::any::Any;
+use std::fmt::{self, Write};
+
+/// A structural outline of a widget tree, akin to an accessibility tree.
+///
+/// An [`Outline`] contains every widget that exposes itself to a
+/// [`widget::Operation`](Operation), nested in the same way the widgets
+/// themselves are nested.
+///
+/// Its [`Display`](fmt::Display) implementation produces a deterministic,
+/// human-readable outline that can be stored and compared with [`Outline::diff`]
+/// to catch structural regressions.
+///
+/// When the `serde` feature is enabled, an [`Outline`] can also be serialized
+/// to JSON with [`Outline::to_json`] and loaded back with [`Outline::from_json`];
+/// so outlines stored by previous runs can be diffed against newer ones.
+///
+/// Widget identifiers created with [`Id::unique`] are not stable across runs
+/// and, therefore, are omitted.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outline {
+    /// The root nodes of the [`Outline`].
+    pub nodes: Vec<Node>,
+}
+
+/// A widget in an [`Outline`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    /// The [`Kind`] of widget.
+    pub kind: Kind,
+    /// The name of the [`Id`] of the widget, if any.
+    pub id: Option<String>,
+    /// The layout bounds of the widget.
+    pub bounds: Rectangle,
+    /// The visible bounds of the widget, if it is visible at all.
+    pub visible_bounds: Option<Rectangle>,
+    /// The text contents of the widget, if any.
+    pub text: Option<String>,
+    /// Whether the widget is focused, if it can be focused.
+    pub is_focused: Option<bool>,
+    /// The children of the widget.
+    pub children: Vec<Node>,
+}
+
+/// The kind of a [`Node`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Kind {
+    /// A container of other widgets.
+    Container,
+    /// A widget that can be focused.
+    Focusable,
+    /// A widget that can be scrolled.
+    Scrollable,
+    /// A widget that can be typed into.
+    TextInput,
+    /// A widget that displays text.
+    Text,
+    /// A widget with custom state.
+    Custom,
+    /// A placeholder for the children of a widget that traversed them
+    /// without exposing itself.
+    Group,
+}
+
+impl Kind {
+    /// Returns the name of the [`Kind`].
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Kind::Container => "container",
+            Kind::Focusable => "focusable",
+            Kind::Scrollable => "scrollable",
+            Kind::TextInput => "text_input",
+            Kind::Text => "text",
+            Kind::Custom => "custom",
+            Kind::Group => "group",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Returns a [`widget::Operation`](Operation) that produces the [`Outline`]
+/// of the widget tree.
+pub fn outline() -> impl Operation<Outline> {
+    Builder {
+        levels: vec![Vec::new()],
+        stack: vec![(Rectangle::INFINITE, Vector::ZERO)],
+        viewport: Rectangle::INFINITE,
+        translation: Vector::ZERO,
+        is_parent: false,
+    }
+}
+
+impl Outline {
+    /// Returns the total amount of nodes in the [`Outline`].
+    pub fn len(&self) -> usize {
+        fn count(nodes: &[Node]) -> usize {
+            nodes.iter().map(|node| 1 + count(&node.children)).sum()
+        }
+
+        count(&self.nodes)
+    }
+
+    /// Returns true if the [`Outline`] has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Serializes the [`Outline`] as JSON.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Serialize outline")
+    }
+
+    /// Parses an [`Outline`] from JSON.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Compares the [`Outline`] with a newer one and returns the lines that
+    /// changed, if any.
+    ///
+    /// Removed lines are prefixed with `-`, added ones with `+`, and
+    /// unchanged lines with a space.
+    pub fn diff(&self, other: &Self) -> Option<String> {
+        if self == other {
+            return None;
+        }
+
+        let old = self.to_string();
+        let new = other.to_string();
+
+        let lines = diff::lines(&old, &new);
+
+        // Outlines may differ in details that are not part of
+        // their textual representation
+        if !lines.iter().any(Line::is_change) {
+            return None;
+        }
+
+        let mut diff = String::new();
+
+        for line in lines {
+            let _ = match line {
+                Line::Same(line) => writeln!(diff, "  {line}"),
+                Line::Removed(line) => writeln!(diff, "- {line}"),
+                Line::Added(line) => writeln!(diff, "+ {line}"),
+            };
+        }
+
+        Some(diff)
+    }
+}
+
+impl fmt::Display for Outline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write(
+            f: &mut fmt::Formatter<'_>,
+            nodes: &[Node],
+            depth: usize,
+        ) -> fmt::Result {
+            for node in nodes {
+                writeln!(f, "{:indent$}{node}", "", indent = depth * 2)?;
+                write(f, &node.children, depth + 1)?;
+            }
+
+            Ok(())
+        }
+
+        write(f, &self.nodes, 0)
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if let Some(id) = &self.id {
+            write!(f, " #{id}")?;
+        }
+
+        if let Some(text) = &self.text {
+            write!(f, " {text:?}")?;
+        }
+
+        write!(
+            f,
+            " at ({}, {}) [{}x{}]",
+            self.bounds.x, self.bounds.y, self.bounds.width, self.bounds.height
+        )?;
+
+        if self.is_focused == Some(true) {
+            write!(f, " focused")?;
+        }
+
+        match self.visible_bounds {
+            None => write!(f, " hidden"),
+            Some(visible_bounds)
+                if visible_bounds.size() != self.bounds.size() =>
+            {
+                write!(
+                    f,
+                    " clipped to ({}, {}) [{}x{}]",
+                    visible_bounds.x,
+                    visible_bounds.y,
+                    visible_bounds.width,
+                    visible_bounds.height
+                )
+            }
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+struct Builder {
+    levels: Vec<Vec<Node>>,
+    stack: Vec<(Rectangle, Vector)>,
+    viewport: Rectangle,
+    translation: Vector,
+    /// Whether the last node can hold the children of the next traversal.
+    is_parent: bool,
+}
+
+impl Builder {
+    fn push(
+        &mut self,
+        kind: Kind,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        text: Option<String>,
+        is_focused: Option<bool>,
+    ) {
+        let id = id.and_then(Id::as_str).map(str::to_owned);
+        let level = self.levels.last_mut().expect("Outline level must exist");
+
+        // Widgets may expose themselves more than once (e.g. a text input
+        // is also focusable), so we merge those into a single node
+        if let Some(last) = level.last_mut()
+            && last.id == id
+            && last.bounds == bounds
+            && last.children.is_empty()
+            && (last.kind == Kind::Focusable || kind == Kind::Focusable)
+            && last.kind != kind
+        {
+            if last.kind == Kind::Focusable {
+                last.kind = kind;
+            }
+
+            last.text = last.text.take().or(text);
+            last.is_focused = last.is_focused.or(is_focused);
+
+            self.is_parent = is_parent(last.kind);
+
+            return;
+        }
+
+        level.push(Node {
+            kind,
+            id,
+            bounds,
+            visible_bounds: self
+                .viewport
+                .intersection(&(bounds + self.translation)),
+            text,
+            is_focused,
+            children: Vec::new(),
+        });
+
+        self.is_parent = is_parent(kind);
+    }
+}
+
+fn is_parent(kind: Kind) -> bool {
+    matches!(kind, Kind::Container | Kind::Scrollable)
+}
+
+impl Operation<Outline> for Builder {
+    fn traverse(
+        &mut self,
+        operate: &mut dyn FnMut(&mut dyn Operation<Outline>),
+    ) {
+        let has_parent = std::mem::take(&mut self.is_parent);
+
+        self.stack.push((self.viewport, self.translation));
+        self.levels.push(Vec::new());
+
+        operate(self);
+
+        let children = self.levels.pop().unwrap_or_default();
+        let _ = self.stack.pop();
+
+        let (viewport, translation) = self.stack.last().unwrap();
+        self.viewport = *viewport;
+        self.translation = *translation;
+
+        self.is_parent = false;
+
+        let level = self.levels.last_mut().expect("Outline level must exist");
+
+        if has_parent && let Some(parent) = level.last_mut() {
+            parent.children.extend(children);
+        } else if let Some(bounds) = children
+            .iter()
+            .map(|child| child.bounds)
+            .reduce(|a, b| a.union(&b))
+        {
+            // Widgets may traverse their children without exposing
+            // themselves first, so we group the children instead of
+            // nesting them under an unrelated sibling
+            level.push(Node {
+                kind: Kind::Group,
+                id: None,
+                bounds,
+                visible_bounds: self
+                    .viewport
+                    .intersection(&(bounds + self.translation)),
+                text: None,
+                is_focused: None,
+                children,
+            });
+        }
+    }
+
+    fn container(&mut self, id: Option<&Id>, bounds: Rectangle) {
+        self.push(Kind::Container, id, bounds, None, None);
+    }
+
+    fn focusable(
+        &mut self,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        state: &mut dyn Focusable,
+    ) {
+        self.push(Kind::Focusable, id, bounds, None, Some(state.is_focused()));
+    }
+
+    fn scrollable(
+        &mut self,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        _content_bounds: Rectangle,
+        translation: Vector,
+        _state: &mut dyn Scrollable,
+    ) {
+        self.push(Kind::Scrollable, id, bounds, None, None);
+
+        let visible_bounds =
+            self.viewport.intersection(&(bounds + self.translation));
+
+        self.translation = self.translation - translation;
+        self.viewport = visible_bounds.unwrap_or_default();
+    }
+
+    fn text_input(
+        &mut self,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        state: &mut dyn TextInput,
+    ) {
+        self.push(
+            Kind::TextInput,
+            id,
+            bounds,
+            Some(state.text().to_owned()),
+            None,
+        );
+    }
+
+    fn text(&mut self, id: Option<&Id>, bounds: Rectangle, text: &str) {
+        self.push(Kind::Text, id, bounds, Some(text.to_owned()), None);
+    }
+
+    fn custom(
+        &mut self,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        _state: &mut dyn Any,
+    ) {
+        self.push(Kind::Custom, id, bounds, None, None);
+    }
+
+    fn finish(&self) -> Outcome<Outline> {
+        Outcome::Some(Outline {
+            nodes: self.levels.first().cloned().unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::widget::operation::scrollable;
+
+    fn node(kind: Kind, text: Option<&str>, children: Vec<Node>) -> Node {
+        Node {
+            kind,
+            id: None,
+            bounds: Rectangle::new((0.0, 0.0).into(), (10.0, 10.0).into()),
+            visible_bounds: Some(Rectangle::new(
+                (0.0, 0.0).into(),
+                (10.0, 10.0).into(),
+            )),
+            text: text.map(str::to_owned),
+            is_focused: None,
+            children,
+        }
+    }
+
+    #[test]
+    fn display_and_diff() {
+        let old = Outline {
+            nodes: vec![node(
+                Kind::Container,
+                None,
+                vec![node(Kind::Text, Some("Hello"), Vec::new())],
+            )],
+        };
+
+        assert_eq!(
+            old.to_string(),
+            "container at (0, 0) [10x10]\n  text \"Hello\" at (0, 0) [10x10]\n"
+        );
+        assert_eq!(old.diff(&old), None);
+
+        let mut new = old.clone();
+        new.nodes[0].children[0].text = Some("Bye".to_owned());
+
+        assert_eq!(
+            old.diff(&new).as_deref(),
+            Some(
+                "  container at (0, 0) [10x10]\n\
+                 -   text \"Hello\" at (0, 0) [10x10]\n\
+                 +   text \"Bye\" at (0, 0) [10x10]\n"
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json() {
+        let outline = Outline {
+            nodes: vec![node(
+                Kind::TextInput,
+                Some("\"quoted\"\n"),
+                vec![node(Kind::Text, None, Vec::new())],
+            )],
+        };
+
+        let json = outline.to_json();
+
+        assert!(json.contains("\"kind\": \"text_input\""));
+        assert_eq!(Outline::from_json(&json).ok(), Some(outline.clone()));
+
+        let mut stored = Outline::from_json(&json).expect("Parse outline");
+        stored.nodes[0].children.clear();
+
+        assert!(stored.diff(&outline).is_some());
+    }
+
+    struct State {
+        is_focused: bool,
+    }
+
+    impl Focusable for State {
+        fn is_focused(&self) -> bool {
+            self.is_focused
+        }
+
+        fn focus(&mut self) {
+            self.is_focused = true;
+        }
+
+        fn unfocus(&mut self) {
+            self.is_focused = false;
+        }
+    }
+
+    impl Scrollable for State {
+        fn snap_to(&mut self, _offset: scrollable::RelativeOffset) {}
+
+        fn scroll_to(&mut self, _offset: scrollable::AbsoluteOffset) {}
+
+        fn scroll_by(
+            &mut self,
+            _offset: scrollable::AbsoluteOffset,
+            _bounds: Rectangle,
+            _content_bounds: Rectangle,
+        ) {
+        }
+    }
+
+    impl TextInput for State {
+        fn text(&self) -> &str {
+            "Typed"
+        }
+
+        fn move_cursor_to_front(&mut self) {}
+
+        fn move_cursor_to_end(&mut self) {}
+
+        fn move_cursor_to(&mut self, _position: usize) {}
+
+        fn select_all(&mut self) {}
+    }
+
+    fn rectangle(y: f32, height: f32) -> Rectangle {
+        Rectangle::new((0.0, y).into(), (100.0, height).into())
+    }
+
+    #[test]
+    fn builder() {
+        let mut state = State { is_focused: true };
+        let mut operation = outline();
+
+        operation.container(Some(&Id::new("root")), rectangle(0.0, 100.0));
+        operation.traverse(&mut |operation| {
+            operation.scrollable(
+                None,
+                rectangle(0.0, 50.0),
+                rectangle(0.0, 200.0),
+                Vector::new(0.0, 80.0),
+                &mut state,
+            );
+            operation.traverse(&mut |operation| {
+                operation.text(None, rectangle(0.0, 20.0), "Scrolled away");
+                operation.text(None, rectangle(120.0, 20.0), "Visible");
+            });
+
+            operation.focusable(
+                Some(&Id::new("input")),
+                rectangle(60.0, 20.0),
+                &mut state,
+            );
+            operation.text_input(
+                Some(&Id::new("input")),
+                rectangle(60.0, 20.0),
+                &mut state,
+            );
+            operation.text(Some(&Id::unique()), rectangle(90.0, 20.0), "Tail");
+        });
+
+        let Outcome::Some(outline) = operation.finish() else {
+            panic!("Outline operation must produce an outline");
+        };
+
+        assert_eq!(outline.len(), 6);
+        assert_eq!(
+            outline.to_string(),
+            "container #root at (0, 0) [100x100]\n\
+             \x20 scrollable at (0, 0) [100x50]\n\
+             \x20   text \"Scrolled away\" at (0, 0) [100x20] hidden\n\
+             \x20   text \"Visible\" at (0, 120) [100x20] clipped to (0, 40) [100x10]\n\
+             \x20 text_input #input \"Typed\" at (0, 60) [100x20] focused\n\
+             \x20 text \"Tail\" at (0, 90) [100x20]\n"
+        );
+    }
+
+    #[test]
+    fn traversal_without_parent() {
+        let mut operation = outline();
+
+        operation.container(Some(&Id::new("root")), rectangle(0.0, 100.0));
+        operation.traverse(&mut |operation| {
+            operation.text(None, rectangle(0.0, 20.0), "Sibling");
+            operation.traverse(&mut |operation| {
+                operation.text(None, rectangle(20.0, 20.0), "First");
+                operation.text(None, rectangle(40.0, 20.0), "Second");
+            });
+            operation.traverse(&mut |_operation| {});
+        });
+
+        let Outcome::Some(outline) = operation.finish() else {
+            panic!("Outline operation must produce an outline");
+        };
+
+        assert_eq!(
+            outline.to_string(),
+            "container #root at (0, 0) [100x100]\n\
+             \x20 text \"Sibling\" at (0, 0) [100x20]\n\
+             \x20 group at (0, 20) [100x40]\n\
+             \x20   text \"First\" at (0, 20) [100x20]\n\
+             \x20   text \"Second\" at (0, 40) [100x20]\n"
+        );
+    }
+}
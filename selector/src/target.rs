@@ -45,6 +45,18 @@ pub enum Target {
 }
 
 impl Target {
+    /// Returns the [`Id`] of the [`Target`], if any.
+    pub fn id(&self) -> Option<&Id> {
+        match self {
+            Target::Container { id, .. }
+            | Target::Focusable { id, .. }
+            | Target::Scrollable { id, .. }
+            | Target::TextInput { id, .. }
+            | Target::Text { id, .. }
+            | Target::Custom { id, .. } => id.as_ref(),
+        }
+    }
+
     /// Returns the layout bounds of the [`Target`].
     pub fn bounds(&self) -> Rectangle {
         match self {
@@ -36,8 +36,8 @@ use crate::shell;
 use crate::theme;
 use crate::window;
 use crate::{
-    Element, Executor, Font, Preset, Result, Settings, Size, Subscription,
-    Task, Theme,
+    Checkpoints, Element, Executor, Font, Preset, Result, Settings, Size,
+    Subscription, Task, Theme,
 };
 
 use iced_debug as debug;
@@ -163,6 +163,8 @@ where
         settings: Settings::default(),
         window: window::Settings::default(),
         presets: Vec::new(),
+        checkpoints: None,
+        debug_state: None,
     }
 }
 
@@ -179,6 +181,8 @@ pub struct Application<P: Program> {
     settings: Settings,
     window: window::Settings,
     presets: Vec<Preset<P::State, P::Message>>,
+    checkpoints: Option<Checkpoints<P::State>>,
+    debug_state: Option<fn(&P::State) -> String>,
 }
 
 impl<P: Program> Application<P> {
@@ -356,6 +360,8 @@ impl<P: Program> Application<P> {
             settings: self.settings,
             window: self.window,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -371,6 +377,8 @@ impl<P: Program> Application<P> {
             settings: self.settings,
             window: self.window,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -388,6 +396,8 @@ impl<P: Program> Application<P> {
             settings: self.settings,
             window: self.window,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -403,6 +413,8 @@ impl<P: Program> Application<P> {
             settings: self.settings,
             window: self.window,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -420,6 +432,8 @@ impl<P: Program> Application<P> {
             settings: self.settings,
             window: self.window,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -437,6 +451,8 @@ impl<P: Program> Application<P> {
             settings: self.settings,
             window: self.window,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -454,6 +470,55 @@ impl<P: Program> Application<P> {
             ..self
         }
     }
+
+    /// Records every message of the [`Application`] with the given
+    /// [`Recorder`](program::session::Recorder).
+    ///
+    /// The recorded session can be loaded later as a
+    /// [`Session`](program::session::Session) and replayed with `iced_test`.
+    #[cfg(feature = "session")]
+    pub fn record(
+        self,
+        recorder: program::session::Recorder<P::Message>,
+    ) -> Application<
+        impl Program<State = P::State, Message = P::Message, Theme = P::Theme>,
+    > {
+        Application {
+            raw: program::with_recorder(self.raw, recorder),
+            settings: self.settings,
+            window: self.window,
+            presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
+        }
+    }
+
+    /// Sets the time-travel [`Checkpoints`] of the [`Application`].
+    ///
+    /// Checkpoints make rewinding faster by replaying messages from
+    /// the nearest snapshot of the state, instead of booting the
+    /// [`Application`] from scratch.
+    pub fn checkpoints(self, checkpoints: Checkpoints<P::State>) -> Self {
+        Self {
+            checkpoints: Some(checkpoints),
+            ..self
+        }
+    }
+
+    /// Shows the [`Debug`](std::fmt::Debug) representation of the state of
+    /// the [`Application`] in devtools.
+    ///
+    /// While time traveling, every message can then be inspected to see how
+    /// it changed the state.
+    pub fn debug_state(self) -> Self
+    where
+        P::State: std::fmt::Debug,
+    {
+        Self {
+            debug_state: Some(|state| format!("{state:#?}")),
+            ..self
+        }
+    }
 }
 
 impl<P: Program> Program for Application<P> {
@@ -522,6 +587,14 @@ impl<P: Program> Program for Application<P> {
     fn presets(&self) -> &[Preset<Self::State, Self::Message>] {
         &self.presets
     }
+
+    fn checkpoints(&self) -> Option<&Checkpoints<Self::State>> {
+        self.checkpoints.as_ref()
+    }
+
+    fn debug_state(&self, state: &Self::State) -> Option<String> {
+        self.debug_state.map(|debug_state| debug_state(state))
+    }
 }
 
 /// The logic to initialize the `State` of some [`Application`].
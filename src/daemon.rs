@@ -6,8 +6,8 @@ use crate::shell;
 use crate::theme;
 use crate::window;
 use crate::{
-    Element, Executor, Font, Preset, Result, Settings, Subscription, Task,
-    Theme,
+    Checkpoints, Element, Executor, Font, Preset, Result, Settings,
+    Subscription, Task, Theme,
 };
 
 use iced_debug as debug;
@@ -110,6 +110,8 @@ where
         },
         settings: Settings::default(),
         presets: Vec::new(),
+        checkpoints: None,
+        debug_state: None,
     }
 }
 
@@ -125,6 +127,8 @@ pub struct Daemon<P: Program> {
     raw: P,
     settings: Settings,
     presets: Vec<Preset<P::State, P::Message>>,
+    checkpoints: Option<Checkpoints<P::State>>,
+    debug_state: Option<fn(&P::State) -> String>,
 }
 
 impl<P: Program> Daemon<P> {
@@ -199,6 +203,8 @@ impl<P: Program> Daemon<P> {
             }),
             settings: self.settings,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -213,6 +219,8 @@ impl<P: Program> Daemon<P> {
             raw: program::with_subscription(self.raw, f),
             settings: self.settings,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -229,6 +237,8 @@ impl<P: Program> Daemon<P> {
             }),
             settings: self.settings,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -243,6 +253,8 @@ impl<P: Program> Daemon<P> {
             raw: program::with_style(self.raw, f),
             settings: self.settings,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -257,6 +269,8 @@ impl<P: Program> Daemon<P> {
             raw: program::with_scale_factor(self.raw, f),
             settings: self.settings,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -273,6 +287,8 @@ impl<P: Program> Daemon<P> {
             raw: program::with_executor::<P, E>(self.raw),
             settings: self.settings,
             presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
         }
     }
 
@@ -290,6 +306,54 @@ impl<P: Program> Daemon<P> {
             ..self
         }
     }
+
+    /// Records every message of the [`Daemon`] with the given
+    /// [`Recorder`](program::session::Recorder).
+    ///
+    /// The recorded session can be loaded later as a
+    /// [`Session`](program::session::Session) and replayed with `iced_test`.
+    #[cfg(feature = "session")]
+    pub fn record(
+        self,
+        recorder: program::session::Recorder<P::Message>,
+    ) -> Daemon<
+        impl Program<State = P::State, Message = P::Message, Theme = P::Theme>,
+    > {
+        Daemon {
+            raw: program::with_recorder(self.raw, recorder),
+            settings: self.settings,
+            presets: self.presets,
+            checkpoints: self.checkpoints,
+            debug_state: self.debug_state,
+        }
+    }
+
+    /// Sets the time-travel [`Checkpoints`] of the [`Daemon`].
+    ///
+    /// Checkpoints make rewinding faster by replaying messages from
+    /// the nearest snapshot of the state, instead of booting the
+    /// [`Daemon`] from scratch.
+    pub fn checkpoints(self, checkpoints: Checkpoints<P::State>) -> Self {
+        Self {
+            checkpoints: Some(checkpoints),
+            ..self
+        }
+    }
+
+    /// Shows the [`Debug`](std::fmt::Debug) representation of the state of
+    /// the [`Daemon`] in devtools.
+    ///
+    /// While time traveling, every message can then be inspected to see how
+    /// it changed the state.
+    pub fn debug_state(self) -> Self
+    where
+        P::State: std::fmt::Debug,
+    {
+        Self {
+            debug_state: Some(|state| format!("{state:#?}")),
+            ..self
+        }
+    }
 }
 
 impl<P: Program> Program for Daemon<P> {
@@ -358,6 +422,14 @@ impl<P: Program> Program for Daemon<P> {
     fn presets(&self) -> &[Preset<Self::State, Self::Message>] {
         &self.presets
     }
+
+    fn checkpoints(&self) -> Option<&Checkpoints<Self::State>> {
+        self.checkpoints.as_ref()
+    }
+
+    fn debug_state(&self, state: &Self::State) -> Option<String> {
+        self.debug_state.map(|debug_state| debug_state(state))
+    }
 }
 
 /// The title logic of some [`Daemon`].
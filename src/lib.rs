@@ -524,8 +524,10 @@ pub use crate::core::{
     Function, Gradient, Length, Padding, Pixels, Point, Radians, Rectangle,
     Rotation, Settings, Shadow, Size, Theme, Transformation, Vector, never,
 };
-pub use crate::program::Preset;
 pub use crate::program::message;
+#[cfg(feature = "session")]
+pub use crate::program::session;
+pub use crate::program::{Checkpoints, Preset};
 pub use crate::runtime::exit;
 pub use iced_futures::Subscription;
 
@@ -536,7 +538,9 @@ pub use alignment::Vertical::{Bottom, Top};
 
 pub mod debug {
     //! Debug your applications.
-    pub use iced_debug::{Span, time, time_with};
+    pub use iced_debug::{
+        Metric, Series, Span, counter, gauge, metrics, time, time_with,
+    };
 }
 
 pub mod task {
@@ -547,6 +551,12 @@ pub mod task {
     pub use crate::runtime::task::{Never, Sipper, Straw, sipper, stream};
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod asset {
+    //! Watch asset files and reload them as they change.
+    pub use crate::runtime::asset::{reload, watch};
+}
+
 pub mod clipboard {
     //! Access the clipboard.
     pub use crate::runtime::clipboard::{
@@ -632,6 +642,51 @@ pub mod widget {
         pub use iced_widget::image::*;
     }
 
+    pub mod virtual_list {
+        //! Display huge lists by only laying out their visible rows.
+        pub use iced_widget::virtual_list::{
+            RowHeight, VirtualList, virtual_list,
+        };
+
+        use crate::Task;
+        use crate::runtime::{Action, task};
+
+        /// Scrolls the closest scrollable around the [`VirtualList`] with the
+        /// given [`Id`] so the row at the given index is at its top.
+        ///
+        /// [`Id`]: crate::widget::Id
+        pub fn scroll_to<T>(
+            id: impl Into<crate::widget::Id>,
+            index: usize,
+        ) -> Task<T> {
+            task::effect(Action::widget(iced_widget::virtual_list::scroll_to(
+                id, index,
+            )))
+        }
+    }
+
+    pub mod table {
+        //! Display tables.
+        pub use iced_widget::table::{
+            Catalog, Column, Sort, Style, StyleFn, Table, column, default,
+            table,
+        };
+
+        use crate::Task;
+        use crate::runtime::{Action, task};
+
+        /// Scrolls the closest scrollable around the [`Table`] with the given
+        /// [`Id`] just enough to show the row at the given index.
+        ///
+        /// [`Id`]: crate::widget::Id
+        pub fn scroll_to<T>(
+            id: impl Into<crate::widget::Id>,
+            row: usize,
+        ) -> Task<T> {
+            task::effect(Action::widget(iced_widget::table::scroll_to(id, row)))
+        }
+    }
+
     // We hide the re-exported modules by `iced_widget`
     mod core {}
     mod graphics {}
@@ -1,15 +1,18 @@
 //! Run your application in a headless runtime.
 use crate::core;
+use crate::core::event;
 use crate::core::mouse;
 use crate::core::renderer;
-use crate::core::time::Instant;
+use crate::core::time::{Duration, Instant};
 use crate::core::widget;
 use crate::core::window;
 use crate::core::{Bytes, Element, Point, Size};
+use crate::debug;
 use crate::instruction;
 use crate::program;
 use crate::program::Program;
 use crate::runtime;
+use crate::runtime::futures::clock::Clock;
 use crate::runtime::futures::futures::StreamExt;
 use crate::runtime::futures::futures::channel::mpsc;
 use crate::runtime::futures::futures::stream;
@@ -18,9 +21,14 @@ use crate::runtime::futures::{Executor, Runtime};
 use crate::runtime::task;
 use crate::runtime::user_interface;
 use crate::runtime::{Task, UserInterface};
-use crate::{Instruction, Selector};
+use crate::{Error, Instruction, Selector};
 
+use std::collections::BTreeMap;
 use std::fmt;
+use std::slice;
+
+/// The duration of a frame in the virtual clock of an [`Emulator`].
+pub const FRAME: Duration = Duration::from_nanos(16_666_667);
 
 /// A headless runtime that can run iced applications and execute
 /// [instructions](crate::Instruction).
@@ -31,17 +39,35 @@ use std::fmt;
 ///
 /// If you want to run a simulation without side effects, use a [`Simulator`](crate::Simulator)
 /// instead.
+///
+/// Time does not pass on its own inside an [`Emulator`]. Instead, it keeps a virtual
+/// clock that can only be advanced with [`instruction::Time`] controls. Any redraws
+/// requested by the user interface will happen against this clock; which makes animations
+/// and other time-based behaviors deterministic.
+///
+/// Subscriptions are notified of every redraw as well—like a
+/// [`window::frames`](crate::runtime::window::frames) subscription would expect. Timer
+/// subscriptions—like `time::every`—follow the virtual clock too. However, their messages
+/// will be processed asynchronously; and any other timers of the [`Executor`] (like a
+/// `sleep` inside a [`Task`]) still run in real time!
+///
+/// An [`Emulator`] keeps track of every window opened by its [`Program`]. Only one of them
+/// is active at a time, and it is the one receiving all the instructions. Newly opened
+/// windows become active automatically, but [`instruction::Window`] controls can be used to
+/// switch between them.
 pub struct Emulator<P: Program> {
     state: P::State,
     runtime: Runtime<P::Executor, mpsc::Sender<Event<P>>, Event<P>>,
     renderer: P::Renderer,
     mode: Mode,
-    size: Size,
-    window: core::window::Id,
+    windows: BTreeMap<window::Id, Window>,
+    window: window::Id,
     cursor: mouse::Cursor,
     clipboard: Clipboard,
-    cache: Option<user_interface::Cache>,
     pending_tasks: usize,
+    clock: Instant,
+    timers: Clock,
+    is_replaying: bool,
 }
 
 /// An emulation event.
@@ -65,6 +91,9 @@ enum Action_<P: Program> {
 impl<P: Program + 'static> Emulator<P> {
     /// Creates a new [`Emulator`] of the [`Program`] with the given [`Mode`] and [`Size`].
     ///
+    /// The [`Size`] will be used for the main window of the [`Program`], if any. Any other
+    /// windows will use the size in their [`window::Settings`].
+    ///
     /// The [`Emulator`] will send [`Event`] notifications through the provided [`mpsc::Sender`].
     ///
     /// When the [`Emulator`] has finished booting, an [`Event::Ready`] will be produced.
@@ -105,6 +134,8 @@ impl<P: Program + 'static> Emulator<P> {
 
         let runtime = Runtime::new(executor, sender);
 
+        let boot_span = debug::boot();
+
         let (state, task) = runtime.enter(|| {
             if let Some(preset) = preset {
                 preset.boot()
@@ -113,17 +144,33 @@ impl<P: Program + 'static> Emulator<P> {
             }
         });
 
+        boot_span.finish();
+
+        let window = window::Id::unique();
+        let mut windows = BTreeMap::new();
+
+        if let Some(settings) = program.window() {
+            let _ = windows.insert(
+                window,
+                Window::new(size, settings.exit_on_close_request),
+            );
+        }
+
+        let now = Instant::now();
+
         let mut emulator = Self {
             state,
             runtime,
             renderer,
             mode,
-            size,
+            windows,
+            window,
             clipboard: Clipboard { content: None },
             cursor: mouse::Cursor::Unavailable,
-            window: core::window::Id::unique(),
-            cache: Some(user_interface::Cache::default()),
             pending_tasks: 0,
+            clock: now,
+            timers: Clock::new(now),
+            is_replaying: false,
         };
 
         emulator.resubscribe(program);
@@ -142,6 +189,7 @@ impl<P: Program + 'static> Emulator<P> {
             .runtime
             .enter(|| program.update(&mut self.state, message));
 
+        self.request_redraws();
         self.resubscribe(program);
 
         match self.mode {
@@ -177,23 +225,44 @@ impl<P: Program + 'static> Emulator<P> {
             }
             Action_::Runtime(action) => match action {
                 runtime::Action::Output(message) => {
-                    self.update(program, message);
+                    // A replayed session already contains every message
+                    if !self.is_replaying {
+                        self.update(program, message);
+                    }
                 }
-                runtime::Action::LoadFont { .. } => {
-                    // TODO
+                runtime::Action::LoadFont { bytes, channel } => {
+                    crate::renderer::graphics::text::font_system()
+                        .write()
+                        .expect("Write to font system")
+                        .load_font(bytes);
+
+                    let _ = channel.send(Ok(()));
                 }
-                runtime::Action::Widget(operation) => {
-                    let mut user_interface = UserInterface::build(
-                        program.view(&self.state, self.window),
-                        self.size,
-                        self.cache.take().unwrap(),
-                        &mut self.renderer,
-                    );
+                runtime::Action::ReloadFont { bytes, channel } => {
+                    crate::renderer::graphics::text::font_system()
+                        .write()
+                        .expect("Write to font system")
+                        .reload_font(bytes);
 
+                    let _ = channel.send(Ok(()));
+                }
+                runtime::Action::Widget(operation) => {
                     let mut operation = Some(operation);
 
                     while let Some(mut current) = operation.take() {
-                        user_interface.operate(&self.renderer, &mut current);
+                        for (id, window) in &mut self.windows {
+                            let mut user_interface = UserInterface::build(
+                                program.view(&self.state, *id),
+                                window.size,
+                                window.cache.take().unwrap(),
+                                &mut self.renderer,
+                            );
+
+                            user_interface
+                                .operate(&self.renderer, &mut current);
+
+                            window.cache = Some(user_interface.into_cache());
+                        }
 
                         match current.finish() {
                             widget::operation::Outcome::None => {}
@@ -203,56 +272,89 @@ impl<P: Program + 'static> Emulator<P> {
                             }
                         }
                     }
-
-                    self.cache = Some(user_interface.into_cache());
                 }
                 runtime::Action::Clipboard(action) => {
                     // TODO
                     dbg!(action);
                 }
                 runtime::Action::Window(action) => {
-                    use crate::runtime::window;
+                    use crate::runtime::window::Action;
 
                     match action {
-                        window::Action::Open(id, _settings, sender) => {
+                        Action::Open(id, settings, sender) => {
+                            let _ = self.windows.insert(
+                                id,
+                                Window::new(
+                                    settings.size,
+                                    settings.exit_on_close_request,
+                                ),
+                            );
+
                             self.window = id;
+                            self.cursor = mouse::Cursor::Unavailable;
 
-                            let _ = sender.send(self.window);
+                            self.broadcast(
+                                id,
+                                window::Event::Opened {
+                                    position: None,
+                                    size: settings.size,
+                                },
+                            );
+
+                            let _ = sender.send(id);
                         }
-                        window::Action::GetOldest(sender)
-                        | window::Action::GetLatest(sender) => {
-                            let _ = sender.send(Some(self.window));
+                        Action::Close(id) => {
+                            self.close(id);
                         }
-                        window::Action::GetSize(id, sender) => {
-                            if id == self.window {
-                                let _ = sender.send(self.size);
-                            }
+                        Action::GetOldest(sender) => {
+                            let _ = sender
+                                .send(self.windows.keys().next().copied());
                         }
-                        window::Action::GetMaximized(id, sender) => {
-                            if id == self.window {
-                                let _ = sender.send(false);
-                            }
+                        Action::GetLatest(sender) => {
+                            let _ = sender
+                                .send(self.windows.keys().next_back().copied());
                         }
-                        window::Action::GetMinimized(id, sender) => {
-                            if id == self.window {
-                                let _ = sender.send(None);
+                        Action::Resize(id, size) => {
+                            if let Some(window) = self.windows.get_mut(&id) {
+                                window.size = size;
+                                window.redraw_request =
+                                    window::RedrawRequest::NextFrame;
+
+                                self.broadcast(
+                                    id,
+                                    window::Event::Resized(size),
+                                );
                             }
                         }
-                        window::Action::GetPosition(id, sender) => {
-                            if id == self.window {
-                                let _ = sender.send(Some(Point::ORIGIN));
+                        Action::GetSize(id, sender) => {
+                            if let Some(window) = self.windows.get(&id) {
+                                let _ = sender.send(window.size);
                             }
                         }
-                        window::Action::GetScaleFactor(id, sender) => {
-                            if id == self.window {
-                                let _ = sender.send(1.0);
-                            }
+                        Action::GetMaximized(id, sender)
+                            if self.windows.contains_key(&id) =>
+                        {
+                            let _ = sender.send(false);
                         }
-                        window::Action::GetMode(id, sender) => {
-                            if id == self.window {
-                                let _ =
-                                    sender.send(core::window::Mode::Windowed);
-                            }
+                        Action::GetMinimized(id, sender)
+                            if self.windows.contains_key(&id) =>
+                        {
+                            let _ = sender.send(None);
+                        }
+                        Action::GetPosition(id, sender)
+                            if self.windows.contains_key(&id) =>
+                        {
+                            let _ = sender.send(Some(Point::ORIGIN));
+                        }
+                        Action::GetScaleFactor(id, sender)
+                            if self.windows.contains_key(&id) =>
+                        {
+                            let _ = sender.send(1.0);
+                        }
+                        Action::GetMode(id, sender)
+                            if self.windows.contains_key(&id) =>
+                        {
+                            let _ = sender.send(window::Mode::Windowed);
                         }
                         _ => {
                             // Ignored
@@ -277,6 +379,36 @@ impl<P: Program + 'static> Emulator<P> {
         }
     }
 
+    /// Creates a new [`Emulator`] that replays a recorded session of the
+    /// [`Program`] in [`Mode::Zen`].
+    ///
+    /// Tasks and subscriptions still run, but any messages produced by them or
+    /// by the user interface are discarded, since the session contains them.
+    #[cfg(feature = "session")]
+    pub(crate) fn replaying(
+        sender: mpsc::Sender<Event<P>>,
+        program: &P,
+        size: Size,
+    ) -> Emulator<P> {
+        let mut emulator = Self::new(sender, program, Mode::Zen, size);
+        emulator.is_replaying = true;
+
+        emulator
+    }
+
+    /// Replays a recorded message and redraws every window afterwards.
+    ///
+    /// An [`Event::Ready`] will be produced once the resulting tasks finish.
+    #[cfg(feature = "session")]
+    pub(crate) fn replay(&mut self, program: &P, message: P::Message) {
+        let task = self
+            .runtime
+            .enter(|| program.update(&mut self.state, message));
+
+        self.request_redraws();
+        self.advance_frame(program, task);
+    }
+
     /// Runs an [`Instruction`].
     ///
     /// If the [`Instruction`] executes successfully, an [`Event::Ready`] will be
@@ -284,94 +416,379 @@ impl<P: Program + 'static> Emulator<P> {
     ///
     /// Otherwise, an [`Event::Failed`] will be triggered.
     pub fn run(&mut self, program: &P, instruction: Instruction) {
-        let mut user_interface = UserInterface::build(
-            program.view(&self.state, self.window),
-            self.size,
-            self.cache.take().unwrap(),
+        let succeeded = match &instruction {
+            Instruction::Interact(interaction) => {
+                self.interact(program, interaction)
+            }
+            Instruction::Expect(expectation) => {
+                let is_met = self.expect(program, expectation);
+
+                if is_met {
+                    self.runtime.send(Event::Ready);
+                }
+
+                is_met
+            }
+            Instruction::Time(time) => {
+                match time {
+                    instruction::Time::Wait(duration) => {
+                        self.wait(program, *duration);
+                    }
+                    instruction::Time::Frame => {
+                        self.advance_frame(program, Task::none());
+                    }
+                }
+
+                true
+            }
+            Instruction::Window(control) => {
+                let is_done = match control {
+                    instruction::Window::Focus(title) => {
+                        self.focus(program, title)
+                    }
+                    instruction::Window::Close => self.request_close(),
+                };
+
+                if is_done {
+                    self.runtime.send(Event::Ready);
+                }
+
+                is_done
+            }
+        };
+
+        if !succeeded {
+            self.runtime.send(Event::Failed(instruction));
+        }
+    }
+
+    fn interact(
+        &mut self,
+        program: &P,
+        interaction: &instruction::Interaction,
+    ) -> bool {
+        let Some(window) = self.windows.get_mut(&self.window) else {
+            return false;
+        };
+
+        let mut user_interface = build(
+            program,
+            &self.state,
+            self.window,
+            window,
             &mut self.renderer,
         );
 
-        let mut messages = Vec::new();
+        let Some(events) = interaction.events(|target| match target {
+            instruction::Target::Point(position) => Some(*position),
+            instruction::Target::Text(text) => {
+                use widget::Operation;
 
-        match &instruction {
-            Instruction::Interact(interaction) => {
-                let Some(events) = interaction.events(|target| match target {
-                    instruction::Target::Point(position) => Some(*position),
-                    instruction::Target::Text(text) => {
-                        use widget::Operation;
+                let mut operation = Selector::find(text.as_str());
 
-                        let mut operation = Selector::find(text.as_str());
-
-                        user_interface.operate(
-                            &self.renderer,
-                            &mut widget::operation::black_box(&mut operation),
-                        );
+                user_interface.operate(
+                    &self.renderer,
+                    &mut widget::operation::black_box(&mut operation),
+                );
 
-                        match operation.finish() {
-                            widget::operation::Outcome::Some(text) => {
-                                Some(text?.visible_bounds()?.center())
-                            }
-                            _ => None,
-                        }
+                match operation.finish() {
+                    widget::operation::Outcome::Some(text) => {
+                        Some(text?.visible_bounds()?.center())
                     }
-                }) else {
-                    self.runtime.send(Event::Failed(instruction));
-                    self.cache = Some(user_interface.into_cache());
-                    return;
-                };
+                    _ => None,
+                }
+            }
+            instruction::Target::Id(id) => {
+                use widget::Operation;
+
+                let mut operation =
+                    Selector::find(widget::Id::from(id.clone()));
 
-                for event in &events {
-                    if let core::Event::Mouse(mouse::Event::CursorMoved {
-                        position,
-                    }) = event
-                    {
-                        self.cursor = mouse::Cursor::Available(*position);
+                user_interface.operate(
+                    &self.renderer,
+                    &mut widget::operation::black_box(&mut operation),
+                );
+
+                match operation.finish() {
+                    widget::operation::Outcome::Some(target) => {
+                        Some(target?.visible_bounds()?.center())
                     }
+                    _ => None,
                 }
+            }
+        }) else {
+            window.cache = Some(user_interface.into_cache());
+            return false;
+        };
+
+        for event in &events {
+            if let core::Event::Mouse(mouse::Event::CursorMoved { position }) =
+                event
+            {
+                self.cursor = mouse::Cursor::Available(*position);
+            }
+        }
+
+        let mut messages = Vec::new();
+
+        let interact_span = debug::interact(self.window);
+        let (state, _status) = user_interface.update(
+            &events,
+            self.cursor,
+            &mut self.renderer,
+            &mut self.clipboard,
+            &mut messages,
+        );
+        interact_span.finish();
+
+        window.cache = Some(user_interface.into_cache());
+        window.request_redraw(&state);
+
+        if !messages.is_empty() {
+            self.request_redraws();
+        }
+
+        let task = self.runtime.enter(|| {
+            Task::batch(
+                messages
+                    .into_iter()
+                    .map(|message| program.update(&mut self.state, message)),
+            )
+        });
+
+        self.resubscribe(program);
+        self.wait_for(task);
+
+        true
+    }
+
+    fn expect(
+        &mut self,
+        program: &P,
+        expectation: &instruction::Expectation,
+    ) -> bool {
+        match expectation {
+            instruction::Expectation::Text(text) => {
+                use widget::Operation;
+
+                let Some(window) = self.windows.get_mut(&self.window) else {
+                    return false;
+                };
 
-                let (_state, _status) = user_interface.update(
-                    &events,
-                    self.cursor,
+                let mut user_interface = UserInterface::build(
+                    program.view(&self.state, self.window),
+                    window.size,
+                    window.cache.take().unwrap(),
                     &mut self.renderer,
-                    &mut self.clipboard,
-                    &mut messages,
                 );
 
-                self.cache = Some(user_interface.into_cache());
+                let mut operation = Selector::find(text.as_str());
+
+                user_interface.operate(
+                    &self.renderer,
+                    &mut widget::operation::black_box(&mut operation),
+                );
 
-                let task = self.runtime.enter(|| {
-                    Task::batch(messages.into_iter().map(|message| {
-                        program.update(&mut self.state, message)
-                    }))
-                });
+                window.cache = Some(user_interface.into_cache());
 
-                self.resubscribe(program);
-                self.wait_for(task);
+                matches!(
+                    operation.finish(),
+                    widget::operation::Outcome::Some(Some(_))
+                )
             }
-            Instruction::Expect(expectation) => match expectation {
-                instruction::Expectation::Text(text) => {
-                    use widget::Operation;
+            instruction::Expectation::Windows(count) => {
+                self.windows.len() == *count
+            }
+            instruction::Expectation::Title(title) => {
+                self.windows.contains_key(&self.window)
+                    && program.title(&self.state, self.window) == *title
+            }
+        }
+    }
 
-                    let mut operation = Selector::find(text.as_str());
+    /// Makes the window with the given title the active one.
+    fn focus(&mut self, program: &P, title: &str) -> bool {
+        let Some(id) = self
+            .windows
+            .keys()
+            .find(|id| program.title(&self.state, **id) == title)
+        else {
+            return false;
+        };
 
-                    user_interface.operate(
-                        &self.renderer,
-                        &mut widget::operation::black_box(&mut operation),
-                    );
+        if *id != self.window {
+            self.window = *id;
+            self.cursor = mouse::Cursor::Unavailable;
+        }
 
-                    match operation.finish() {
-                        widget::operation::Outcome::Some(Some(_text)) => {
-                            self.runtime.send(Event::Ready);
-                        }
-                        _ => {
-                            self.runtime.send(Event::Failed(instruction));
-                        }
-                    }
+        true
+    }
 
-                    self.cache = Some(user_interface.into_cache());
-                }
+    /// Requests the active window to close, as if the user had pressed
+    /// its close button.
+    fn request_close(&mut self) -> bool {
+        let Some(window) = self.windows.get(&self.window) else {
+            return false;
+        };
+
+        let exit_on_close_request = window.exit_on_close_request;
+
+        self.broadcast(self.window, window::Event::CloseRequested);
+
+        if exit_on_close_request {
+            self.close(self.window);
+        }
+
+        true
+    }
+
+    fn close(&mut self, id: window::Id) {
+        if self.windows.remove(&id).is_none() {
+            return;
+        }
+
+        self.broadcast(id, window::Event::Closed);
+
+        if id == self.window
+            && let Some(latest) = self.windows.keys().next_back()
+        {
+            self.window = *latest;
+            self.cursor = mouse::Cursor::Unavailable;
+        }
+    }
+
+    /// Advances the virtual clock of the [`Emulator`] by the given [`Duration`].
+    ///
+    /// Any redraws requested by the user interface in the meantime will take
+    /// place at their requested time—or in the next [`FRAME`].
+    fn wait(&mut self, program: &P, duration: Duration) {
+        let deadline = self.clock + duration;
+        let mut tasks = Vec::new();
+
+        while self.clock < deadline {
+            let next = self
+                .windows
+                .values()
+                .map(|window| match window.redraw_request {
+                    window::RedrawRequest::NextFrame => self.clock + FRAME,
+                    window::RedrawRequest::At(at) if at > self.clock => at,
+                    window::RedrawRequest::At(_) => self.clock + FRAME,
+                    window::RedrawRequest::Wait => deadline,
+                })
+                .min()
+                .unwrap_or(deadline);
+
+            self.clock = next.min(deadline);
+            self.tick();
+
+            let due: Vec<_> = self
+                .windows
+                .iter()
+                .filter(|(_, window)| window.is_redraw_due(self.clock))
+                .map(|(id, _)| *id)
+                .collect();
+
+            for id in due {
+                tasks.push(self.redraw(program, id));
+            }
+        }
+
+        self.resubscribe(program);
+        self.wait_for(Task::batch(tasks));
+    }
+
+    /// Advances the virtual clock of the [`Emulator`] by a single [`FRAME`]
+    /// and redraws every window, waiting for the given [`Task`] as well.
+    fn advance_frame(&mut self, program: &P, task: Task<P::Message>) {
+        self.clock += FRAME;
+        self.tick();
+
+        let windows: Vec<_> = self.windows.keys().copied().collect();
+
+        let task = Task::batch(
+            std::iter::once(task)
+                .chain(windows.into_iter().map(|id| self.redraw(program, id)))
+                .collect::<Vec<_>>(),
+        );
+
+        self.resubscribe(program);
+        self.wait_for(task);
+    }
+
+    fn redraw(&mut self, program: &P, id: window::Id) -> Task<P::Message> {
+        let Some(window) = self.windows.get_mut(&id) else {
+            return Task::none();
+        };
+
+        let event = window::Event::RedrawRequested(self.clock);
+
+        let mut user_interface =
+            build(program, &self.state, id, window, &mut self.renderer);
+
+        let mut messages = Vec::new();
+
+        let interact_span = debug::interact(id);
+        let (state, _status) = user_interface.update(
+            slice::from_ref(&core::Event::Window(event.clone())),
+            if id == self.window {
+                self.cursor
+            } else {
+                mouse::Cursor::Unavailable
             },
+            &mut self.renderer,
+            &mut self.clipboard,
+            &mut messages,
+        );
+        interact_span.finish();
+
+        window.cache = Some(user_interface.into_cache());
+        window.redraw_request = window::RedrawRequest::Wait;
+        window.request_redraw(&state);
+
+        self.broadcast(id, event);
+
+        if messages.is_empty() || self.is_replaying {
+            return Task::none();
         }
+
+        self.request_redraws();
+
+        self.runtime.enter(|| {
+            Task::batch(
+                messages
+                    .into_iter()
+                    .map(|message| program.update(&mut self.state, message)),
+            )
+        })
+    }
+
+    fn request_redraws(&mut self) {
+        for window in self.windows.values_mut() {
+            window.redraw_request = window::RedrawRequest::NextFrame;
+        }
+    }
+
+    fn broadcast(&mut self, id: window::Id, event: window::Event) {
+        self.runtime.broadcast(subscription::Event::Interaction {
+            window: id,
+            event: core::Event::Window(event),
+            status: event::Status::Ignored,
+        });
+    }
+
+    /// Notifies the timer subscriptions of the current time of the virtual clock.
+    fn tick(&mut self) {
+        self.timers.advance(self.clock);
+    }
+
+    /// Returns the current time of the virtual clock of the [`Emulator`].
+    pub fn now(&self) -> Instant {
+        self.clock
+    }
+
+    /// Returns the [`window::Id`] of every open window, from oldest to latest.
+    pub fn windows(&self) -> impl Iterator<Item = window::Id> + '_ {
+        self.windows.keys().copied()
     }
 
     fn wait_for(&mut self, task: Task<P::Message>) {
@@ -418,17 +835,18 @@ impl<P: Program + 'static> Emulator<P> {
     }
 
     fn resubscribe(&mut self, program: &P) {
-        self.runtime
-            .track(subscription::into_recipes(self.runtime.enter(|| {
-                program.subscription(&self.state).map(|message| {
-                    Event::Action(Action(Action_::Runtime(
-                        runtime::Action::Output(message),
-                    )))
-                })
-            })));
+        let recipes = subscription::into_recipes(self.runtime.enter(|| {
+            program.subscription(&self.state).map(|message| {
+                Event::Action(Action(Action_::Runtime(
+                    runtime::Action::Output(message),
+                )))
+            })
+        }));
+
+        self.timers.enter(|| self.runtime.track(recipes));
     }
 
-    /// Returns the current view of the [`Emulator`].
+    /// Returns the current view of the active window of the [`Emulator`].
     pub fn view(
         &self,
         program: &P,
@@ -436,52 +854,89 @@ impl<P: Program + 'static> Emulator<P> {
         program.view(&self.state, self.window)
     }
 
-    /// Returns the current theme of the [`Emulator`].
+    /// Returns the current theme of the active window of the [`Emulator`].
     pub fn theme(&self, program: &P) -> Option<P::Theme> {
         program.theme(&self.state, self.window)
     }
 
-    /// Takes a [`window::Screenshot`] of the current state of the [`Emulator`].
+    /// Returns the [`window::Id`] of the active window of the [`Emulator`],
+    /// unless every window has been closed.
+    pub fn window(&self) -> Option<window::Id> {
+        self.windows
+            .contains_key(&self.window)
+            .then_some(self.window)
+    }
+
+    /// Takes a [`window::Screenshot`] of the active window of the [`Emulator`].
+    ///
+    /// # Panics
+    /// Panics if every window has been closed. Use [`Emulator::try_screenshot`]
+    /// to handle that case.
     pub fn screenshot(
         &mut self,
         program: &P,
         theme: &P::Theme,
         scale_factor: f32,
     ) -> window::Screenshot {
+        self.try_screenshot(program, theme, scale_factor)
+            .expect("Take screenshot of active window")
+    }
+
+    /// Takes a [`window::Screenshot`] of the active window of the [`Emulator`].
+    ///
+    /// Fails with [`Error::NoOpenWindows`] if every window has been closed.
+    pub fn try_screenshot(
+        &mut self,
+        program: &P,
+        theme: &P::Theme,
+        scale_factor: f32,
+    ) -> Result<window::Screenshot, Error> {
         use core::renderer::Headless;
 
         let style = program.style(&self.state, theme);
 
-        let mut user_interface = UserInterface::build(
-            program.view(&self.state, self.window),
-            self.size,
-            self.cache.take().unwrap(),
+        let Some(window) = self.windows.get_mut(&self.window) else {
+            return Err(Error::NoOpenWindows);
+        };
+
+        let mut user_interface = build(
+            program,
+            &self.state,
+            self.window,
+            window,
             &mut self.renderer,
         );
 
         // TODO: Nested redraws!
+        let interact_span = debug::interact(self.window);
         let _ = user_interface.update(
             &[core::Event::Window(window::Event::RedrawRequested(
-                Instant::now(),
+                self.clock,
             ))],
             mouse::Cursor::Unavailable,
             &mut self.renderer,
             &mut self.clipboard,
             &mut Vec::new(),
         );
+        interact_span.finish();
 
+        let draw_span = debug::draw(self.window);
         user_interface.draw(
             &mut self.renderer,
             theme,
             &renderer::Style {
                 text_color: style.text_color,
+                ..renderer::Style::default()
             },
             mouse::Cursor::Unavailable,
         );
+        draw_span.finish();
+
+        window.cache = Some(user_interface.into_cache());
 
         let physical_size = Size::new(
-            (self.size.width * scale_factor).round() as u32,
-            (self.size.height * scale_factor).round() as u32,
+            (window.size.width * scale_factor).round() as u32,
+            (window.size.height * scale_factor).round() as u32,
         );
 
         let rgba = self.renderer.screenshot(
@@ -490,19 +945,81 @@ impl<P: Program + 'static> Emulator<P> {
             style.background_color,
         );
 
-        window::Screenshot {
+        Ok(window::Screenshot {
             rgba: Bytes::from(rgba),
             size: physical_size,
             scale_factor,
-        }
+        })
     }
 
-    /// Turns the [`Emulator`] into its internal state.
-    pub fn into_state(self) -> (P::State, core::window::Id) {
+    /// Turns the [`Emulator`] into its internal state and the
+    /// [`window::Id`] of its active window.
+    pub fn into_state(self) -> (P::State, window::Id) {
         (self.state, self.window)
     }
 }
 
+/// Builds the [`UserInterface`] of a window, timing its view and layout.
+fn build<'a, P: Program>(
+    program: &'a P,
+    state: &'a P::State,
+    id: window::Id,
+    window: &mut Window,
+    renderer: &mut P::Renderer,
+) -> UserInterface<'a, P::Message, P::Theme, P::Renderer> {
+    let view_span = debug::view(id);
+    let element = program.view(state, id);
+    view_span.finish();
+
+    let layout_span = debug::layout(id);
+    let user_interface = UserInterface::build(
+        element,
+        window.size,
+        window.cache.take().unwrap(),
+        renderer,
+    );
+    layout_span.finish();
+
+    user_interface
+}
+
+struct Window {
+    size: Size,
+    cache: Option<user_interface::Cache>,
+    redraw_request: window::RedrawRequest,
+    exit_on_close_request: bool,
+}
+
+impl Window {
+    fn new(size: Size, exit_on_close_request: bool) -> Self {
+        Self {
+            size,
+            cache: Some(user_interface::Cache::default()),
+            redraw_request: window::RedrawRequest::NextFrame,
+            exit_on_close_request,
+        }
+    }
+
+    fn request_redraw(&mut self, state: &user_interface::State) {
+        let redraw_request = match state {
+            user_interface::State::Updated { redraw_request, .. } => {
+                *redraw_request
+            }
+            user_interface::State::Outdated => window::RedrawRequest::NextFrame,
+        };
+
+        self.redraw_request = self.redraw_request.min(redraw_request);
+    }
+
+    fn is_redraw_due(&self, now: Instant) -> bool {
+        match self.redraw_request {
+            window::RedrawRequest::NextFrame => true,
+            window::RedrawRequest::At(at) => at <= now,
+            window::RedrawRequest::Wait => false,
+        }
+    }
+}
+
 /// The strategy used by an [`Emulator`] when waiting for tasks to finish.
 ///
 /// A [`Mode`] can be used to make an [`Emulator`] wait for side effects to finish before
@@ -549,3 +1066,396 @@ impl core::Clipboard for Clipboard {
         self.content = Some(contents);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::widget::Text;
+    use crate::core::{Settings, Theme};
+    use crate::runtime::futures::Subscription;
+    use crate::runtime::futures::backend::native::tokio;
+
+    use std::sync::atomic::{self, AtomicUsize};
+
+    #[derive(Default)]
+    struct Ticker {
+        ticks: AtomicUsize,
+    }
+
+    #[derive(Debug, Clone)]
+    struct Tick;
+
+    impl Program for Ticker {
+        type State = ();
+        type Message = Tick;
+        type Theme = Theme;
+        type Renderer = crate::renderer::Renderer;
+        type Executor = tokio::Executor;
+
+        fn name() -> &'static str {
+            "ticker"
+        }
+
+        fn settings(&self) -> Settings {
+            Settings::default()
+        }
+
+        fn window(&self) -> Option<window::Settings> {
+            Some(window::Settings::default())
+        }
+
+        fn boot(&self) -> ((), Task<Tick>) {
+            ((), Task::none())
+        }
+
+        fn update(&self, _state: &mut (), _tick: Tick) -> Task<Tick> {
+            let _ = self.ticks.fetch_add(1, atomic::Ordering::Relaxed);
+
+            Task::none()
+        }
+
+        fn view<'a>(
+            &self,
+            _state: &'a (),
+            _window: window::Id,
+        ) -> Element<'a, Tick, Theme, Self::Renderer> {
+            Text::new("Ticking...").into()
+        }
+
+        fn subscription(&self, _state: &()) -> Subscription<Tick> {
+            tokio::time::every(Duration::from_secs(60)).map(|_| Tick)
+        }
+    }
+
+    impl Ticker {
+        fn ticks(&self) -> usize {
+            self.ticks.load(atomic::Ordering::Relaxed)
+        }
+    }
+
+    #[derive(Default)]
+    struct Dialogs {
+        opened: AtomicUsize,
+    }
+
+    #[derive(Debug, Clone)]
+    enum Dialog {
+        Open,
+        Opened(window::Id),
+    }
+
+    impl Program for Dialogs {
+        type State = Option<window::Id>;
+        type Message = Dialog;
+        type Theme = Theme;
+        type Renderer = crate::renderer::Renderer;
+        type Executor = tokio::Executor;
+
+        fn name() -> &'static str {
+            "dialogs"
+        }
+
+        fn settings(&self) -> Settings {
+            Settings::default()
+        }
+
+        fn window(&self) -> Option<window::Settings> {
+            Some(window::Settings::default())
+        }
+
+        fn boot(&self) -> (Option<window::Id>, Task<Dialog>) {
+            (None, Task::none())
+        }
+
+        fn update(
+            &self,
+            dialog: &mut Option<window::Id>,
+            message: Dialog,
+        ) -> Task<Dialog> {
+            match message {
+                Dialog::Open => {
+                    let (_id, open) =
+                        runtime::window::open(window::Settings::default());
+
+                    open.map(Dialog::Opened)
+                }
+                Dialog::Opened(id) => {
+                    *dialog = Some(id);
+                    let _ = self.opened.fetch_add(1, atomic::Ordering::Relaxed);
+
+                    Task::none()
+                }
+            }
+        }
+
+        fn view<'a>(
+            &self,
+            _dialog: &'a Option<window::Id>,
+            _window: window::Id,
+        ) -> Element<'a, Dialog, Theme, Self::Renderer> {
+            Text::new("Hello!").into()
+        }
+
+        fn title(
+            &self,
+            dialog: &Option<window::Id>,
+            window: window::Id,
+        ) -> String {
+            if *dialog == Some(window) {
+                "dialog".to_owned()
+            } else {
+                "main".to_owned()
+            }
+        }
+    }
+
+    #[cfg(feature = "session")]
+    #[derive(Default)]
+    struct Counter {
+        views: AtomicUsize,
+    }
+
+    #[cfg(feature = "session")]
+    #[derive(Debug, Clone)]
+    enum Message {
+        Add(i64),
+        Added,
+    }
+
+    #[cfg(feature = "session")]
+    impl Program for Counter {
+        type State = i64;
+        type Message = Message;
+        type Theme = Theme;
+        type Renderer = crate::renderer::Renderer;
+        type Executor = tokio::Executor;
+
+        fn name() -> &'static str {
+            "counter"
+        }
+
+        fn settings(&self) -> Settings {
+            Settings::default()
+        }
+
+        fn window(&self) -> Option<window::Settings> {
+            Some(window::Settings::default())
+        }
+
+        fn boot(&self) -> (i64, Task<Message>) {
+            (0, Task::done(Message::Add(100)))
+        }
+
+        fn update(&self, value: &mut i64, message: Message) -> Task<Message> {
+            match message {
+                Message::Add(amount) => {
+                    *value += amount;
+
+                    Task::done(Message::Added)
+                }
+                Message::Added => {
+                    *value *= 10;
+
+                    Task::none()
+                }
+            }
+        }
+
+        fn view<'a>(
+            &self,
+            value: &'a i64,
+            _window: window::Id,
+        ) -> Element<'a, Message, Theme, Self::Renderer> {
+            let _ = self.views.fetch_add(1, atomic::Ordering::Relaxed);
+
+            Text::new(value.to_string()).into()
+        }
+    }
+
+    /// Performs the actions of the [`Emulator`] until the given condition
+    /// holds, giving up after a second of real time.
+    fn settle<P: Program + 'static>(
+        emulator: &mut Emulator<P>,
+        program: &P,
+        receiver: &mut mpsc::Receiver<Event<P>>,
+        condition: impl Fn() -> bool,
+    ) {
+        let start = std::time::Instant::now();
+
+        while !condition() && start.elapsed() < Duration::from_secs(1) {
+            match receiver.try_next() {
+                Ok(Some(Event::Action(action))) => {
+                    emulator.perform(program, action);
+                }
+                Ok(Some(Event::Failed(instruction))) => {
+                    panic!("Instruction failed: {instruction}");
+                }
+                _ => std::thread::sleep(Duration::from_millis(1)),
+            }
+        }
+    }
+
+    /// Performs the actions of the [`Emulator`] until it is ready, returning
+    /// whether the last [`Instruction`] succeeded.
+    fn ready<P: Program + 'static>(
+        emulator: &mut Emulator<P>,
+        program: &P,
+        receiver: &mut mpsc::Receiver<Event<P>>,
+    ) -> bool {
+        use crate::runtime::futures::futures::executor;
+
+        loop {
+            match executor::block_on(receiver.next()) {
+                Some(Event::Action(action)) => {
+                    emulator.perform(program, action);
+                }
+                Some(Event::Ready) => return true,
+                Some(Event::Failed(_)) => return false,
+                None => panic!("emulator runtime stopped"),
+            }
+        }
+    }
+
+    #[test]
+    fn timers_follow_the_virtual_clock() {
+        let (sender, mut receiver) = mpsc::channel(100);
+
+        let program = Ticker::default();
+        let mut emulator = Emulator::new(
+            sender,
+            &program,
+            Mode::Patient,
+            Size::new(200.0, 200.0),
+        );
+
+        let wait = |seconds| {
+            Instruction::Time(instruction::Time::Wait(Duration::from_secs(
+                seconds,
+            )))
+        };
+
+        emulator.run(&program, wait(30));
+        settle(&mut emulator, &program, &mut receiver, || false);
+        assert_eq!(program.ticks(), 0);
+
+        emulator.run(&program, wait(30));
+        settle(&mut emulator, &program, &mut receiver, || {
+            program.ticks() > 0
+        });
+        assert_eq!(program.ticks(), 1);
+
+        emulator.run(&program, wait(150));
+        settle(&mut emulator, &program, &mut receiver, || {
+            program.ticks() > 1
+        });
+        assert_eq!(program.ticks(), 2);
+    }
+
+    #[test]
+    fn windows_are_opened_focused_and_closed() {
+        let (sender, mut receiver) = mpsc::channel(100);
+
+        let program = Dialogs::default();
+        let mut emulator = Emulator::new(
+            sender,
+            &program,
+            Mode::Patient,
+            Size::new(200.0, 200.0),
+        );
+
+        assert!(ready(&mut emulator, &program, &mut receiver));
+
+        let title = |title: &str| {
+            Instruction::Expect(instruction::Expectation::Title(
+                title.to_owned(),
+            ))
+        };
+
+        let windows = |count| {
+            Instruction::Expect(instruction::Expectation::Windows(count))
+        };
+
+        let focus = |title: &str| {
+            Instruction::Window(instruction::Window::Focus(title.to_owned()))
+        };
+
+        emulator.update(&program, Dialog::Open);
+        settle(&mut emulator, &program, &mut receiver, || {
+            program.opened.load(atomic::Ordering::Relaxed) > 0
+        });
+
+        let mut check = |instruction| {
+            emulator.run(&program, instruction);
+            ready(&mut emulator, &program, &mut receiver)
+        };
+
+        assert!(check(windows(2)));
+        assert!(check(title("dialog")));
+
+        assert!(check(focus("main")));
+        assert!(check(title("main")));
+        assert!(!check(focus("settings")));
+        assert!(check(title("main")));
+
+        assert!(check(focus("dialog")));
+        assert!(check(Instruction::Window(instruction::Window::Close)));
+        assert!(check(windows(1)));
+        assert!(check(title("main")));
+    }
+
+    #[test]
+    fn closing_the_last_window_fails_clearly() {
+        let (sender, mut receiver) = mpsc::channel(100);
+
+        let program = Dialogs::default();
+        let mut emulator = Emulator::new(
+            sender,
+            &program,
+            Mode::Patient,
+            Size::new(200.0, 200.0),
+        );
+
+        assert!(ready(&mut emulator, &program, &mut receiver));
+        assert!(emulator.window().is_some());
+
+        let mut check = |instruction| {
+            emulator.run(&program, instruction);
+            ready(&mut emulator, &program, &mut receiver)
+        };
+
+        assert!(check(Instruction::Window(instruction::Window::Close)));
+        assert!(check(Instruction::Expect(
+            instruction::Expectation::Windows(0)
+        )));
+        assert!(!check(Instruction::Expect(instruction::Expectation::Text(
+            "Hello!".to_owned()
+        ))));
+        assert!(!check(Instruction::Window(instruction::Window::Close)));
+
+        assert!(emulator.window().is_none());
+        assert!(matches!(
+            emulator.try_screenshot(&program, &Theme::Dark, 1.0),
+            Err(Error::NoOpenWindows)
+        ));
+    }
+
+    #[cfg(feature = "session")]
+    #[test]
+    fn sessions_are_replayed_with_views() {
+        let program = Counter::default();
+
+        let session = program::session::Session {
+            messages: vec![
+                Message::Add(100),
+                Message::Added,
+                Message::Add(2),
+                Message::Added,
+            ],
+        };
+
+        let value = crate::replay(&program, session, Size::new(200.0, 200.0));
+
+        assert_eq!(value, 10_020);
+        assert!(program.views.load(atomic::Ordering::Relaxed) >= 4);
+    }
+}
@@ -45,6 +45,18 @@ pub enum Error {
         /// The [`Instruction`] that failed.
         instruction: Instruction,
     },
+    /// An [`Instruction`] of an [`Ice`](crate::Ice) test ran after every
+    /// window was closed.
+    #[error("the ice test ({file}) has no open windows to run: {instruction}")]
+    IceWindowsClosed {
+        /// The path of the test.
+        file: PathBuf,
+        /// The [`Instruction`] that failed.
+        instruction: Instruction,
+    },
+    /// The [`Emulator`](crate::Emulator) has no open windows.
+    #[error("the emulator has no open windows")]
+    NoOpenWindows,
     /// The [`Preset`](crate::program::Preset) of a program could not be found.
     #[error(
         "the preset \"{name}\" does not exist (available presets: {available:?})"
@@ -55,6 +67,13 @@ pub enum Error {
         /// The available set of presets.
         available: Vec<String>,
     },
+    /// The spans of a profiled run were not received in time.
+    #[cfg(feature = "profile")]
+    #[error(
+        "the spans of the profiled run were not received in time \
+        (is another beacon server running?)"
+    )]
+    ProfilingTimedOut,
 }
 
 impl From<io::Error> for Error {
@@ -2,7 +2,16 @@
 use crate::Instruction;
 use crate::core::Size;
 use crate::emulator;
-use crate::instruction;
+use crate::instruction::{
+    self, Expectation, Interaction, Keyboard, Mouse, Target, Window,
+};
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// An end-to-end test for iced applications.
 ///
@@ -49,12 +58,58 @@ impl Ice {
     /// expect "0 tasks left"
     /// ```
     ///
+    /// Instructions can also be reused with some simple directives:
+    ///
+    /// ```text
+    /// include "common/login.ice"
+    /// let task = "Make an apple pie"
+    /// repeat 3 {
+    ///     click "What needs to be done?"
+    ///     type "${task}"
+    ///     type enter
+    /// }
+    /// expect "3 tasks left"
+    /// ```
+    ///
+    /// An `include` directive inlines the instructions of another file, which
+    /// must not contain any metadata. Relative paths are resolved from the
+    /// directory of the including file—or the current directory, if unknown.
+    ///
+    /// A `let` directive defines a variable that can be interpolated with `${name}`
+    /// in any string that follows it, including those of included files. Variables
+    /// defined by an included file are scoped to it and do not leak into the includer.
+    ///
+    /// A `repeat` block runs its instructions the given amount of times—at most
+    /// [`MAX_REPEAT`] times, as long as the expanded test stays under
+    /// [`MAX_INSTRUCTIONS`].
+    ///
+    /// All of these directives are expanded during parsing. Therefore, the
+    /// resulting [`Ice`] test only contains plain instructions.
+    ///
     /// This syntax is _very_ experimental and extremely likely to change often.
     /// For this reason, it is reserved for advanced users that want to early test it.
     ///
     /// Currently, in order to use it, you will need to earn the right and prove you understand
     /// its experimental nature by reading the code!
     pub fn parse(content: &str) -> Result<Self, ParseError> {
+        Self::parse_in(content, None)
+    }
+
+    /// Parses an [`Ice`] test from the contents of the file at the given path.
+    ///
+    /// Any `include` directives will be resolved relative to the directory
+    /// of the file.
+    pub fn parse_file(
+        path: impl AsRef<Path>,
+        content: &str,
+    ) -> Result<Self, ParseError> {
+        Self::parse_in(content, Some(path.as_ref()))
+    }
+
+    fn parse_in(
+        content: &str,
+        path: Option<&Path>,
+    ) -> Result<Self, ParseError> {
         let Some((metadata, rest)) = content.split_once("-") else {
             return Err(ParseError::NoMetadata);
         };
@@ -125,19 +180,16 @@ impl Ice {
             return Err(ParseError::MissingMode);
         };
 
-        let instructions = rest
+        let offset = metadata.lines().count() + 1;
+
+        let mut lines = rest
             .lines()
             .skip(1)
             .enumerate()
-            .map(|(i, line)| {
-                Instruction::parse(line).map_err(|error| {
-                    ParseError::InvalidInstruction {
-                        line: metadata.lines().count() + 1 + i,
-                        error,
-                    }
-                })
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+            .map(|(i, line)| (offset + i, line));
+
+        let mut body = Body::new(path);
+        let instructions = body.block(&mut lines, None)?;
 
         Ok(Self {
             viewport,
@@ -148,6 +200,279 @@ impl Ice {
     }
 }
 
+/// The maximum amount of times a `repeat` block of an [`Ice`] test can run.
+pub const MAX_REPEAT: usize = 10_000;
+
+/// The maximum amount of instructions a block of an [`Ice`] test can expand to.
+pub const MAX_INSTRUCTIONS: usize = 100_000;
+
+/// The instructions of an [`Ice`] test while its directives are expanded.
+struct Body {
+    directory: PathBuf,
+    files: Vec<PathBuf>,
+    variables: HashMap<String, String>,
+}
+
+impl Body {
+    fn new(path: Option<&Path>) -> Self {
+        Self {
+            directory: path
+                .and_then(Path::parent)
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            files: path
+                .map(|path| {
+                    fs::canonicalize(path).unwrap_or(path.to_path_buf())
+                })
+                .into_iter()
+                .collect(),
+            variables: HashMap::new(),
+        }
+    }
+
+    fn block(
+        &mut self,
+        lines: &mut dyn Iterator<Item = (usize, &str)>,
+        opening: Option<usize>,
+    ) -> Result<Vec<Instruction>, ParseError> {
+        let mut instructions = Vec::new();
+
+        while let Some((i, line)) = lines.next() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if line == "}" {
+                return if opening.is_some() {
+                    Ok(instructions)
+                } else {
+                    Err(ParseError::UnexpectedBlockEnd { line: i })
+                };
+            }
+
+            if let Some(count) = directive(line, "repeat") {
+                let Some(count) = count
+                    .strip_suffix('{')
+                    .and_then(|count| count.trim().parse::<usize>().ok())
+                    .filter(|count| *count <= MAX_REPEAT)
+                else {
+                    return Err(ParseError::InvalidRepeat {
+                        line: i,
+                        content: line.to_owned(),
+                    });
+                };
+
+                let block = self.block(lines, Some(i))?;
+
+                if block
+                    .len()
+                    .checked_mul(count)
+                    .and_then(|total| total.checked_add(instructions.len()))
+                    .is_none_or(|total| total > MAX_INSTRUCTIONS)
+                {
+                    return Err(ParseError::InvalidRepeat {
+                        line: i,
+                        content: line.to_owned(),
+                    });
+                }
+
+                for _ in 0..count {
+                    instructions.extend(block.iter().cloned());
+                }
+            } else if let Some(definition) = directive(line, "let") {
+                let Some((name, value)) = definition
+                    .split_once('=')
+                    .map(|(name, value)| (name.trim(), value.trim()))
+                    .filter(|(name, _)| is_variable(name))
+                else {
+                    return Err(ParseError::InvalidVariable {
+                        line: i,
+                        content: line.to_owned(),
+                    });
+                };
+
+                let value =
+                    instruction::parse_literal(value).map_err(|error| {
+                        ParseError::InvalidInstruction { line: i, error }
+                    })?;
+
+                let value = self.interpolate(i, &value)?;
+                let _ = self.variables.insert(name.to_owned(), value);
+            } else if let Some(path) = directive(line, "include") {
+                let path =
+                    instruction::parse_literal(path).map_err(|error| {
+                        ParseError::InvalidInstruction { line: i, error }
+                    })?;
+
+                let path = self.interpolate(i, &path)?;
+
+                instructions.extend(self.include(i, Path::new(&path))?);
+            } else {
+                let instruction =
+                    Instruction::parse(line).map_err(|error| {
+                        ParseError::InvalidInstruction { line: i, error }
+                    })?;
+
+                instructions.push(self.expand(i, instruction)?);
+            }
+        }
+
+        match opening {
+            Some(line) => Err(ParseError::UnclosedBlock { line }),
+            None => Ok(instructions),
+        }
+    }
+
+    fn include(
+        &mut self,
+        line: usize,
+        path: &Path,
+    ) -> Result<Vec<Instruction>, ParseError> {
+        let path = self.directory.join(path);
+
+        let content = fs::read_to_string(&path).map_err(|error| {
+            ParseError::IncludeFailed {
+                line,
+                file: path.clone(),
+                error: Arc::new(error),
+            }
+        })?;
+
+        let canonical = fs::canonicalize(&path).unwrap_or(path.clone());
+
+        if self.files.contains(&canonical) {
+            return Err(ParseError::CyclicInclude { line, file: path });
+        }
+
+        let directory = mem::replace(
+            &mut self.directory,
+            path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        );
+        let variables = self.variables.clone();
+        self.files.push(canonical);
+
+        let instructions = self.block(&mut content.lines().enumerate(), None);
+
+        let _ = self.files.pop();
+        self.directory = directory;
+        self.variables = variables;
+
+        instructions.map_err(|error| ParseError::Included {
+            line,
+            file: path,
+            error: Box::new(error),
+        })
+    }
+
+    fn expand(
+        &self,
+        line: usize,
+        instruction: Instruction,
+    ) -> Result<Instruction, ParseError> {
+        let target = |target: Target| {
+            Ok(match target {
+                Target::Text(text) => {
+                    Target::Text(self.interpolate(line, &text)?)
+                }
+                Target::Id(id) => Target::Id(self.interpolate(line, &id)?),
+                Target::Point(point) => Target::Point(point),
+            })
+        };
+
+        Ok(match instruction {
+            Instruction::Interact(Interaction::Mouse(mouse)) => {
+                Instruction::Interact(Interaction::Mouse(match mouse {
+                    Mouse::Move(to) => Mouse::Move(target(to)?),
+                    Mouse::Press { button, target: at } => Mouse::Press {
+                        button,
+                        target: at.map(target).transpose()?,
+                    },
+                    Mouse::Release { button, target: at } => Mouse::Release {
+                        button,
+                        target: at.map(target).transpose()?,
+                    },
+                    Mouse::Click { button, target: at } => Mouse::Click {
+                        button,
+                        target: at.map(target).transpose()?,
+                    },
+                }))
+            }
+            Instruction::Interact(Interaction::Keyboard(keyboard)) => {
+                Instruction::Interact(Interaction::Keyboard(match keyboard {
+                    Keyboard::Typewrite(text) => {
+                        Keyboard::Typewrite(self.interpolate(line, &text)?)
+                    }
+                    keyboard => keyboard,
+                }))
+            }
+            Instruction::Expect(Expectation::Text(text)) => {
+                Instruction::Expect(Expectation::Text(
+                    self.interpolate(line, &text)?,
+                ))
+            }
+            Instruction::Expect(Expectation::Title(title)) => {
+                Instruction::Expect(Expectation::Title(
+                    self.interpolate(line, &title)?,
+                ))
+            }
+            Instruction::Expect(expectation) => {
+                Instruction::Expect(expectation)
+            }
+            Instruction::Time(time) => Instruction::Time(time),
+            Instruction::Window(Window::Focus(title)) => Instruction::Window(
+                Window::Focus(self.interpolate(line, &title)?),
+            ),
+            Instruction::Window(window) => Instruction::Window(window),
+        })
+    }
+
+    fn interpolate(
+        &self,
+        line: usize,
+        text: &str,
+    ) -> Result<String, ParseError> {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find("${")
+            && let Some(end) = rest[start..].find('}')
+        {
+            let name = &rest[start + 2..start + end];
+
+            let Some(value) = self.variables.get(name) else {
+                return Err(ParseError::UnknownVariable {
+                    line,
+                    name: name.to_owned(),
+                });
+            };
+
+            result.push_str(&rest[..start]);
+            result.push_str(value);
+
+            rest = &rest[start + end + 1..];
+        }
+
+        result.push_str(rest);
+
+        Ok(result)
+    }
+}
+
+fn directive<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    line.strip_prefix(keyword)
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+        .map(str::trim)
+}
+
+fn is_variable(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
 impl std::fmt::Display for Ice {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
@@ -234,4 +559,247 @@ pub enum ParseError {
         /// The parse error.
         error: instruction::ParseError,
     },
+
+    /// A `let` directive is invalid.
+    #[error("invalid variable in line {line}: \"{content}\"")]
+    InvalidVariable {
+        /// The number of the invalid line.
+        line: usize,
+        /// The content of the invalid line.
+        content: String,
+    },
+
+    /// A variable was used before being defined.
+    #[error("unknown variable in line {line}: \"{name}\"")]
+    UnknownVariable {
+        /// The number of the invalid line.
+        line: usize,
+        /// The name of the unknown variable.
+        name: String,
+    },
+
+    /// A `repeat` directive is invalid or expands to too many instructions.
+    #[error("invalid repeat in line {line}: \"{content}\"")]
+    InvalidRepeat {
+        /// The number of the invalid line.
+        line: usize,
+        /// The content of the invalid line.
+        content: String,
+    },
+
+    /// A block was never closed.
+    #[error("the block opened in line {line} is never closed")]
+    UnclosedBlock {
+        /// The number of the line opening the block.
+        line: usize,
+    },
+
+    /// A block was closed without being opened.
+    #[error("unexpected end of block in line {line}")]
+    UnexpectedBlockEnd {
+        /// The number of the invalid line.
+        line: usize,
+    },
+
+    /// An included file could not be read.
+    #[error(
+        "the file included in line {line} ({file}) could not be read: {error}"
+    )]
+    IncludeFailed {
+        /// The number of the `include` line.
+        line: usize,
+        /// The path of the included file.
+        file: PathBuf,
+        /// The IO error.
+        error: Arc<io::Error>,
+    },
+
+    /// A file ended up including itself.
+    #[error("the file included in line {line} ({file}) includes itself")]
+    CyclicInclude {
+        /// The number of the `include` line.
+        line: usize,
+        /// The path of the included file.
+        file: PathBuf,
+    },
+
+    /// An included file failed to parse.
+    #[error("in the file included in line {line} ({file}): {error}")]
+    Included {
+        /// The number of the `include` line.
+        line: usize,
+        /// The path of the included file.
+        file: PathBuf,
+        /// The parse error of the included file.
+        error: Box<ParseError>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directives() {
+        let ice = Ice::parse(
+            "viewport: 500x800
+mode: Immediate
+-----
+let task = \"Make an apple pie\"
+let done = \"${task} (done)\"
+let field = \"new task\"
+click #task-input
+click #\"${field}\"
+repeat 2 {
+    type \"${task}\"
+    type enter
+    advance-frame
+}
+wait 300ms
+wait 2s
+expect \"${done}\"
+window \"${task}\"
+expect windows 2
+expect title \"${task}\"
+close-window
+",
+        )
+        .expect("Parse ice test");
+
+        assert_eq!(
+            ice.to_string(),
+            "viewport: 500x800
+mode: Immediate
+-----
+click #task-input
+click #\"new task\"
+type \"Make an apple pie\"
+type enter
+advance-frame
+type \"Make an apple pie\"
+type enter
+advance-frame
+wait 300ms
+wait 2s
+expect \"Make an apple pie (done)\"
+window \"Make an apple pie\"
+expect windows 2
+expect title \"Make an apple pie\"
+close-window
+"
+        );
+
+        assert_eq!(Ice::parse(&ice.to_string()).unwrap(), ice);
+    }
+
+    #[test]
+    fn invalid_directives() {
+        let parse = |body: &str| {
+            Ice::parse(&format!("viewport: 500x800\nmode: Zen\n-----\n{body}"))
+        };
+
+        assert!(matches!(
+            parse("expect \"${unknown}\""),
+            Err(ParseError::UnknownVariable { line: 3, .. })
+        ));
+
+        assert!(matches!(
+            parse("type enter\nrepeat 2 {\ntype tab"),
+            Err(ParseError::UnclosedBlock { line: 4 })
+        ));
+
+        assert!(matches!(
+            parse("}"),
+            Err(ParseError::UnexpectedBlockEnd { line: 3 })
+        ));
+
+        assert!(matches!(
+            parse("repeat many {\n}"),
+            Err(ParseError::InvalidRepeat { line: 3, .. })
+        ));
+
+        assert!(matches!(
+            parse("repeat 1000000 {\ntype enter\n}"),
+            Err(ParseError::InvalidRepeat { line: 3, .. })
+        ));
+
+        assert!(matches!(
+            parse("repeat 10000 {\nrepeat 10000 {\ntype enter\n}\n}"),
+            Err(ParseError::InvalidRepeat { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn includes() {
+        let directory = std::env::temp_dir()
+            .join(format!("iced_test_ice_includes_{}", std::process::id()));
+
+        fs::create_dir_all(directory.join("common")).unwrap();
+
+        fs::write(
+            directory.join("common/login.ice"),
+            "click \"Username\"\ntype \"${user}\"\ninclude \"submit.ice\"\n",
+        )
+        .unwrap();
+
+        fs::write(
+            directory.join("common/submit.ice"),
+            "let submit = \"enter\"\ntype enter\n",
+        )
+        .unwrap();
+
+        fs::write(directory.join("common/broken.ice"), "type enter\nwiggle\n")
+            .unwrap();
+
+        fs::write(
+            directory.join("common/cycle.ice"),
+            "include \"cycle.ice\"\n",
+        )
+        .unwrap();
+
+        let parse = |body: &str| {
+            Ice::parse_file(
+                directory.join("test.ice"),
+                &format!("viewport: 500x800\nmode: Zen\n-----\n{body}"),
+            )
+        };
+
+        let ice = parse("let user = \"hecrj\"\ninclude \"common/login.ice\"")
+            .expect("Parse ice test");
+
+        assert_eq!(
+            ice.instructions,
+            vec![
+                Instruction::parse("click \"Username\"").unwrap(),
+                Instruction::parse("type \"hecrj\"").unwrap(),
+                Instruction::parse("type enter").unwrap(),
+            ]
+        );
+
+        assert!(matches!(
+            parse(
+                "let user = \"hecrj\"\ninclude \"common/login.ice\"\nexpect \"${submit}\""
+            ),
+            Err(ParseError::UnknownVariable { line: 5, .. })
+        ));
+
+        assert!(matches!(
+            parse("include \"common/broken.ice\""),
+            Err(ParseError::Included { line: 3, error, .. })
+                if matches!(*error, ParseError::InvalidInstruction { line: 1, .. })
+        ));
+
+        assert!(matches!(
+            parse("include \"common/cycle.ice\""),
+            Err(ParseError::Included { error, .. })
+                if matches!(*error, ParseError::CyclicInclude { line: 0, .. })
+        ));
+
+        assert!(matches!(
+            parse("include \"common/missing.ice\""),
+            Err(ParseError::IncludeFailed { line: 3, .. })
+        ));
+
+        fs::remove_dir_all(directory).unwrap();
+    }
 }
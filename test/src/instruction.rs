@@ -1,6 +1,7 @@
 //! A step in an end-to-end test.
 use crate::core::keyboard;
 use crate::core::mouse;
+use crate::core::time::Duration;
 use crate::core::{Event, Point};
 use crate::simulator;
 
@@ -15,6 +16,10 @@ pub enum Instruction {
     Interact(Interaction),
     /// A testing [`Expectation`].
     Expect(Expectation),
+    /// A [`Time`] control.
+    Time(Time),
+    /// A [`Window`] control.
+    Window(Window),
 }
 
 impl Instruction {
@@ -29,6 +34,8 @@ impl fmt::Display for Instruction {
         match self {
             Instruction::Interact(interaction) => interaction.fmt(f),
             Instruction::Expect(expectation) => expectation.fmt(f),
+            Instruction::Time(time) => time.fmt(f),
+            Instruction::Window(window) => window.fmt(f),
         }
     }
 }
@@ -371,6 +378,8 @@ pub enum Target {
     Point(Point),
     /// A UI element containing the given text.
     Text(String),
+    /// A UI element with the given [`widget::Id`](crate::core::widget::Id).
+    Id(String),
 }
 
 impl fmt::Display for Target {
@@ -378,6 +387,7 @@ impl fmt::Display for Target {
         match self {
             Self::Point(point) => f.write_str(&format::point(*point)),
             Self::Text(text) => f.write_str(&format::string(text)),
+            Self::Id(id) => f.write_str(&format::id(id)),
         }
     }
 }
@@ -481,6 +491,24 @@ mod format {
     pub fn string(text: &str) -> String {
         format!("\"{}\"", text.escape_default())
     }
+
+    pub fn id(id: &str) -> String {
+        if !id.is_empty() && id.chars().all(parser::is_id_char) {
+            format!("#{id}")
+        } else {
+            format!("#{}", string(id))
+        }
+    }
+
+    pub fn duration(duration: Duration) -> String {
+        let millis = duration.as_millis();
+
+        if millis > 0 && millis.is_multiple_of(1000) {
+            format!("{}s", millis / 1000)
+        } else {
+            format!("{millis}ms")
+        }
+    }
 }
 
 /// A testing assertion.
@@ -491,6 +519,10 @@ mod format {
 pub enum Expectation {
     /// Expect some element to contain some text.
     Text(String),
+    /// Expect a certain amount of open windows.
+    Windows(usize),
+    /// Expect the active window to have the given title.
+    Title(String),
 }
 
 impl fmt::Display for Expectation {
@@ -499,24 +531,86 @@ impl fmt::Display for Expectation {
             Expectation::Text(text) => {
                 write!(f, "expect {}", format::string(text))
             }
+            Expectation::Windows(count) => {
+                write!(f, "expect windows {count}")
+            }
+            Expectation::Title(title) => {
+                write!(f, "expect title {}", format::string(title))
+            }
+        }
+    }
+}
+
+/// A time control.
+///
+/// Time controls advance the virtual clock of an [`Emulator`](crate::Emulator),
+/// which is used to drive animations and any other time-based behavior of
+/// the user interface deterministically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Time {
+    /// Advance the clock by the given [`Duration`], redrawing whenever the
+    /// user interface requests it.
+    Wait(Duration),
+    /// Advance the clock by a single frame and redraw.
+    Frame,
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Time::Wait(duration) => {
+                write!(f, "wait {}", format::duration(*duration))
+            }
+            Time::Frame => f.write_str("advance-frame"),
+        }
+    }
+}
+
+/// A window control.
+///
+/// Window controls change the active window of an [`Emulator`](crate::Emulator);
+/// that is, the window receiving all the other instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Window {
+    /// Make the window with the given title the active one.
+    Focus(String),
+    /// Request the active window to close, as if the user had pressed
+    /// its close button.
+    Close,
+}
+
+impl fmt::Display for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Window::Focus(title) => {
+                write!(f, "window {}", format::string(title))
+            }
+            Window::Close => f.write_str("close-window"),
         }
     }
 }
 
 pub use parser::Error as ParseError;
 
+/// Parses a quoted string literal, resolving any escape sequences.
+pub(crate) fn parse_literal(input: &str) -> Result<String, ParseError> {
+    parser::literal(input)
+}
+
 mod parser {
     use super::*;
 
     use nom::branch::alt;
-    use nom::bytes::complete::tag;
+    use nom::bytes::complete::{tag, take_while1};
     use nom::bytes::{is_not, take_while_m_n};
-    use nom::character::complete::{char, multispace0, multispace1};
+    use nom::character::complete::{
+        char, multispace0, multispace1, u64, usize,
+    };
     use nom::combinator::{map, map_opt, map_res, opt, success, value, verify};
     use nom::error::ParseError;
     use nom::multi::fold;
     use nom::number::float;
-    use nom::sequence::{delimited, preceded, separated_pair};
+    use nom::sequence::{delimited, preceded, separated_pair, terminated};
     use nom::{Finish, IResult, Parser};
 
     /// A parsing error.
@@ -531,10 +625,19 @@ mod parser {
         }
     }
 
+    pub fn literal(input: &str) -> Result<String, Error> {
+        match string.parse_complete(input).finish() {
+            Ok((_rest, string)) => Ok(string),
+            Err(error) => Err(Error(error.cloned())),
+        }
+    }
+
     fn instruction(input: &str) -> IResult<&str, Instruction> {
         alt((
             map(interaction, Instruction::Interact),
             map(expectation, Instruction::Expect),
+            map(time, Instruction::Time),
+            map(window, Instruction::Window),
         ))
         .parse(input)
     }
@@ -584,7 +687,24 @@ mod parser {
     }
 
     fn target(input: &str) -> IResult<&str, Target> {
-        alt((string.map(Target::Text), point.map(Target::Point))).parse(input)
+        alt((
+            string.map(Target::Text),
+            point.map(Target::Point),
+            id.map(Target::Id),
+        ))
+        .parse(input)
+    }
+
+    fn id(input: &str) -> IResult<&str, String> {
+        preceded(
+            char('#'),
+            alt((string, take_while1(is_id_char).map(str::to_owned))),
+        )
+        .parse(input)
+    }
+
+    pub fn is_id_char(c: char) -> bool {
+        c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '/')
     }
 
     fn mouse_button(input: &str) -> IResult<&str, mouse::Button> {
@@ -604,9 +724,38 @@ mod parser {
     }
 
     fn expectation(input: &str) -> IResult<&str, Expectation> {
-        map(preceded(tag("expect "), string), |text| {
-            Expectation::Text(text)
-        })
+        preceded(
+            tag("expect "),
+            alt((
+                map(string, Expectation::Text),
+                map(preceded(tag("windows "), usize), Expectation::Windows),
+                map(preceded(tag("title "), string), Expectation::Title),
+            )),
+        )
+        .parse(input)
+    }
+
+    fn window(input: &str) -> IResult<&str, Window> {
+        alt((
+            map(preceded(tag("window "), string), Window::Focus),
+            value(Window::Close, tag("close-window")),
+        ))
+        .parse(input)
+    }
+
+    fn time(input: &str) -> IResult<&str, Time> {
+        alt((
+            map(preceded(tag("wait "), duration), Time::Wait),
+            value(Time::Frame, tag("advance-frame")),
+        ))
+        .parse(input)
+    }
+
+    fn duration(input: &str) -> IResult<&str, Duration> {
+        alt((
+            map(terminated(u64, tag("ms")), Duration::from_millis),
+            map(terminated(u64, tag("s")), Duration::from_secs),
+        ))
         .parse(input)
     }
 
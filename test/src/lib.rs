@@ -90,11 +90,16 @@ pub use iced_runtime::core;
 
 pub use iced_selector as selector;
 
+use iced_debug as debug;
+
 pub mod emulator;
 pub mod ice;
 pub mod instruction;
 pub mod simulator;
 
+#[cfg(feature = "profile")]
+pub mod profile;
+
 mod error;
 
 pub use emulator::Emulator;
@@ -104,6 +109,9 @@ pub use instruction::Instruction;
 pub use selector::Selector;
 pub use simulator::{Simulator, simulator};
 
+#[cfg(feature = "profile")]
+pub use profile::profile;
+
 use crate::core::Size;
 use crate::core::time::{Duration, Instant};
 use crate::core::window;
@@ -122,10 +130,6 @@ pub fn run(
     program: impl program::Program + 'static,
     tests_dir: impl AsRef<Path>,
 ) -> Result<(), Error> {
-    use crate::runtime::futures::futures::StreamExt;
-    use crate::runtime::futures::futures::channel::mpsc;
-    use crate::runtime::futures::futures::executor;
-
     use std::ffi::OsStr;
     use std::fs;
 
@@ -141,29 +145,9 @@ pub fn run(
 
         let content = fs::read_to_string(file.path())?;
 
-        match Ice::parse(&content) {
+        match Ice::parse_file(file.path(), &content) {
             Ok(ice) => {
-                let preset = if let Some(preset) = &ice.preset {
-                    let Some(preset) = program
-                        .presets()
-                        .iter()
-                        .find(|candidate| candidate.name() == preset)
-                    else {
-                        return Err(Error::PresetNotFound {
-                            name: preset.to_owned(),
-                            available: program
-                                .presets()
-                                .iter()
-                                .map(program::Preset::name)
-                                .map(str::to_owned)
-                                .collect(),
-                        });
-                    };
-
-                    Some(preset)
-                } else {
-                    None
-                };
+                let preset = find_preset(&program, ice.preset.as_deref())?;
 
                 tests.push((file, ice, preset));
             }
@@ -178,48 +162,137 @@ pub fn run(
 
     // TODO: Concurrent runtimes
     for (file, ice, preset) in tests {
-        let (sender, mut receiver) = mpsc::channel(1);
+        execute(&program, &file.path(), ice, preset)?;
+    }
 
-        let mut emulator = Emulator::with_preset(
-            sender,
-            &program,
-            ice.mode,
-            ice.viewport,
-            preset,
-        );
+    Ok(())
+}
 
-        let mut instructions = ice.instructions.into_iter();
+/// Finds the [`Preset`](program::Preset) of the [`Program`](program::Program)
+/// with the given name, if any.
+fn find_preset<'a, P: program::Program>(
+    program: &'a P,
+    name: Option<&str>,
+) -> Result<Option<&'a program::Preset<P::State, P::Message>>, Error> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
 
-        loop {
-            let event = executor::block_on(receiver.next())
-                .expect("emulator runtime should never stop on its own");
+    program
+        .presets()
+        .iter()
+        .find(|candidate| candidate.name() == name)
+        .map(Some)
+        .ok_or_else(|| Error::PresetNotFound {
+            name: name.to_owned(),
+            available: program
+                .presets()
+                .iter()
+                .map(program::Preset::name)
+                .map(str::to_owned)
+                .collect(),
+        })
+}
 
-            match event {
-                emulator::Event::Action(action) => {
-                    emulator.perform(&program, action);
-                }
-                emulator::Event::Failed(instruction) => {
-                    return Err(Error::IceTestingFailed {
-                        file: file.path().to_path_buf(),
-                        instruction,
-                    });
-                }
-                emulator::Event::Ready => {
-                    let Some(instruction) = instructions.next() else {
-                        break;
-                    };
+/// Executes the instructions of the [`Ice`] test in the given file in an
+/// [`Emulator`], failing with the [`Instruction`] that failed, if any.
+fn execute<P: program::Program + 'static>(
+    program: &P,
+    file: &Path,
+    ice: Ice,
+    preset: Option<&program::Preset<P::State, P::Message>>,
+) -> Result<(), Error> {
+    use crate::runtime::futures::futures::StreamExt;
+    use crate::runtime::futures::futures::channel::mpsc;
+    use crate::runtime::futures::futures::executor;
 
-                    emulator.run(&program, instruction);
-                }
+    let (sender, mut receiver) = mpsc::channel(1);
+
+    let mut emulator =
+        Emulator::with_preset(sender, program, ice.mode, ice.viewport, preset);
+
+    let mut instructions = ice.instructions.into_iter();
+
+    loop {
+        let event = executor::block_on(receiver.next())
+            .expect("emulator runtime should never stop on its own");
+
+        match event {
+            emulator::Event::Action(action) => {
+                emulator.perform(program, action);
+            }
+            emulator::Event::Failed(instruction) => {
+                let file = file.to_path_buf();
+
+                return Err(if emulator.window().is_none() {
+                    Error::IceWindowsClosed { file, instruction }
+                } else {
+                    Error::IceTestingFailed { file, instruction }
+                });
+            }
+            emulator::Event::Ready => {
+                let Some(instruction) = instructions.next() else {
+                    return Ok(());
+                };
+
+                emulator.run(program, instruction);
             }
         }
     }
+}
 
-    Ok(())
+/// Replays a recorded [`Session`](program::session::Session) in an [`Emulator`]
+/// of the given [`Program`](program::Program) with the given viewport, returning
+/// the resulting state.
+///
+/// The views of every window are built after each message and any tasks run to
+/// completion; so a session that panicked while rendering panics again. Messages
+/// produced by tasks, subscriptions, or the user interface are discarded, since
+/// they were already recorded.
+#[cfg(feature = "session")]
+pub fn replay<P: program::Program + 'static>(
+    program: &P,
+    session: program::session::Session<P::Message>,
+    viewport: impl Into<Size>,
+) -> P::State {
+    use crate::runtime::futures::futures::StreamExt;
+    use crate::runtime::futures::futures::channel::mpsc;
+    use crate::runtime::futures::futures::executor;
+
+    let (sender, mut receiver) = mpsc::channel(1);
+
+    let mut emulator = Emulator::replaying(sender, program, viewport.into());
+    let mut messages = session.messages.into_iter();
+
+    loop {
+        let event = executor::block_on(receiver.next())
+            .expect("emulator runtime should never stop on its own");
+
+        match event {
+            emulator::Event::Action(action) => {
+                emulator.perform(program, action);
+            }
+            emulator::Event::Failed(_) => {
+                unreachable!(
+                    "no instructions should be executed during a replay"
+                );
+            }
+            emulator::Event::Ready => {
+                let Some(message) = messages.next() else {
+                    return emulator.into_state().0;
+                };
+
+                emulator.replay(program, message);
+            }
+        }
+    }
 }
 
 /// Takes a screenshot of the given [`Program`](program::Program) with the given theme, viewport,
 /// and scale factor after running it for the given [`Duration`].
+///
+/// # Panics
+/// Panics if the [`Program`](program::Program) has no open windows by then.
 pub fn screenshot<P: program::Program + 'static>(
     program: &P,
     theme: &P::Theme,
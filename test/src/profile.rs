@@ -0,0 +1,93 @@
+//! Profile your applications and enforce performance budgets.
+//!
+//! [`profile`] runs an [`Ice`] test in an [`Emulator`](crate::Emulator) while a
+//! beacon server collects the duration of every stage—boot, view, layout,
+//! interact, and draw—together with any custom spans of your application.
+//!
+//! The resulting [`Profile`] can be checked against a [`Budget`] to make
+//! continuous integration catch performance regressions:
+//!
+//! ```no_run
+//! # fn test(program: impl iced_test::program::Program + 'static) -> Result<(), iced_test::Error> {
+//! use iced_test::core::time::milliseconds;
+//! use iced_test::profile::{self, Budget};
+//!
+//! let profile = profile::profile(program, "tests/scroll.ice")?;
+//!
+//! if let Err(exceeded) =
+//!     profile.check(&Budget::new().p95("Layout", milliseconds(2)))
+//! {
+//!     panic!("{exceeded}\n\n{profile}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Only a single beacon server can run per machine. Profiled runs of the same
+//! process are serialized, but any other running beacon server (like `comet`)
+//! must be closed beforehand.
+use crate::core::time::Duration;
+use crate::debug;
+use crate::program::Program;
+use crate::{Error, Ice};
+
+pub use iced_beacon::profile::{
+    Budget, Exceeded, Limit, Profile, Profiler, Violation,
+};
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+/// The maximum amount of time to wait for the spans of a run.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+static PROFILER: LazyLock<Profiler> = LazyLock::new(Profiler::start);
+static RUN: Mutex<()> = Mutex::new(());
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs the [`Ice`] test at the given path for the given [`Program`] and
+/// returns a [`Profile`] of its execution.
+pub fn profile<P: Program + 'static>(
+    program: P,
+    file: impl AsRef<Path>,
+) -> Result<Profile, Error> {
+    let file = file.as_ref();
+    let content = fs::read_to_string(file)?;
+
+    let ice = Ice::parse_file(file, &content).map_err(|error| {
+        Error::IceParsingFailed {
+            file: file.to_path_buf(),
+            error,
+        }
+    })?;
+
+    let preset = crate::find_preset(&program, ice.preset.as_deref())?;
+
+    let _run = RUN.lock().unwrap_or_else(PoisonError::into_inner);
+    let profiler = &*PROFILER;
+
+    debug::init(debug::Metadata {
+        name: P::name(),
+        theme: None,
+        can_time_travel: false,
+    });
+
+    let run = RUNS.fetch_add(1, atomic::Ordering::Relaxed);
+    let start = format!("iced_test::profile::start::{run}");
+    let end = format!("iced_test::profile::end::{run}");
+
+    debug::time(start.as_str()).finish();
+    let result = crate::execute(&program, file, ice, preset);
+    debug::time(end.as_str()).finish();
+
+    let profile = profiler
+        .until(&start, TIMEOUT)
+        .and_then(|_| profiler.until(&end, TIMEOUT))
+        .ok_or(Error::ProfilingTimedOut)?;
+
+    result?;
+
+    Ok(profile)
+}
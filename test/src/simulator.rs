@@ -12,7 +12,7 @@ use crate::core::{Element, Event, Font, Point, Settings, Size, SmolStr};
 use crate::renderer;
 use crate::runtime::UserInterface;
 use crate::runtime::user_interface;
-use crate::selector::Bounded;
+use crate::selector::{Bounded, Outline};
 use crate::{Error, Selector};
 
 use std::borrow::Cow;
@@ -128,6 +128,21 @@ where
         }
     }
 
+    /// Produces the [`Outline`] of the widget tree in the [`Simulator`].
+    pub fn outline(&mut self) -> Outline {
+        let mut operation = crate::selector::outline();
+
+        self.raw.operate(
+            &self.renderer,
+            &mut widget::operation::black_box(&mut operation),
+        );
+
+        match widget::Operation::finish(&operation) {
+            widget::operation::Outcome::Some(outline) => outline,
+            _ => Outline::default(),
+        }
+    }
+
     /// Points the mouse cursor at the given position in the [`Simulator`].
     ///
     /// This does _not_ produce mouse movement events!
@@ -214,6 +229,7 @@ where
             theme,
             &core::renderer::Style {
                 text_color: base.text_color,
+                ..core::renderer::Style::default()
             },
             self.cursor,
         );
@@ -425,3 +441,68 @@ fn load_font(font: impl Into<Cow<'static, [u8]>>) -> Result<(), Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::selector::outline::Node;
+
+    use iced_widget::{column, scrollable, space, text, text_input};
+
+    fn summary(nodes: &[Node], depth: usize, lines: &mut Vec<String>) {
+        for node in nodes {
+            lines.push(format!(
+                "{:indent$}{kind}{id}{text}{hidden}",
+                "",
+                indent = depth * 2,
+                kind = node.kind,
+                id = node
+                    .id
+                    .as_ref()
+                    .map(|id| format!(" #{id}"))
+                    .unwrap_or_default(),
+                text = node
+                    .text
+                    .as_ref()
+                    .map(|text| format!(" {text:?}"))
+                    .unwrap_or_default(),
+                hidden = if node.visible_bounds.is_none() {
+                    " hidden"
+                } else {
+                    ""
+                },
+            ));
+
+            summary(&node.children, depth + 1, lines);
+        }
+    }
+
+    #[test]
+    fn outline() {
+        let mut ui: Simulator<'_, ()> = simulator(column![
+            text("Hello"),
+            text_input("Name", "Ferris").id("name"),
+            scrollable(column![space().height(100), text("Below")])
+                .id("list")
+                .height(50),
+        ]);
+
+        let outline = ui.outline();
+
+        let mut lines = Vec::new();
+        summary(&outline.nodes, 0, &mut lines);
+
+        assert_eq!(outline.len(), 6);
+        assert_eq!(
+            lines,
+            [
+                "container",
+                "  text \"Hello\"",
+                "  text_input #name \"Ferris\"",
+                "  scrollable #list",
+                "    container",
+                "      text \"Below\" hidden",
+            ]
+        );
+    }
+}
@@ -305,7 +305,8 @@ impl<P: Program + 'static> Tester<P> {
                 Task::future(import)
                     .and_then(|file| {
                         task::blocking(move |mut sender| {
-                            let _ = sender.try_send(Ice::parse(
+                            let _ = sender.try_send(Ice::parse_file(
+                                file.path(),
                                 &fs::read_to_string(file.path())
                                     .unwrap_or_default(),
                             ));
@@ -485,30 +486,7 @@ impl<P: Program + 'static> Tester<P> {
                 Task::none()
             }
             Tick::Record(interaction) => {
-                let mut interaction = Some(interaction);
-
-                while let Some(new_interaction) = interaction.take() {
-                    if let Some(Instruction::Interact(last_interaction)) =
-                        self.instructions.pop()
-                    {
-                        let (merged_interaction, new_interaction) =
-                            last_interaction.merge(new_interaction);
-
-                        if let Some(new_interaction) = new_interaction {
-                            self.instructions.push(Instruction::Interact(
-                                merged_interaction,
-                            ));
-
-                            self.instructions
-                                .push(Instruction::Interact(new_interaction));
-                        } else {
-                            interaction = Some(merged_interaction);
-                        }
-                    } else {
-                        self.instructions
-                            .push(Instruction::Interact(new_interaction));
-                    }
-                }
+                recorder::append(&mut self.instructions, interaction);
 
                 Task::none()
             }
@@ -10,9 +10,9 @@ use crate::core::{
     self, Clipboard, Color, Element, Event, Layout, Length, Point, Rectangle,
     Shell, Size, Vector, Widget,
 };
-use crate::test::Selector;
 use crate::test::instruction::{Interaction, Mouse, Target};
 use crate::test::selector;
+use crate::test::{Instruction, Selector};
 
 pub fn recorder<'a, Message, Theme, Renderer>(
     content: impl Into<Element<'a, Message, Theme, Renderer>>,
@@ -438,10 +438,10 @@ fn record<Message>(
         return;
     };
 
-    if let Some((content, visible_bounds)) =
-        find_text(position + (bounds.position() - Point::ORIGIN), operate)
+    if let Some((semantic_target, visible_bounds)) =
+        find_target(position + (bounds.position() - Point::ORIGIN), operate)
     {
-        *target = Target::Text(content);
+        *target = semantic_target;
         *last_hovered = visible_bounds;
     } else {
         *last_hovered = None;
@@ -450,50 +450,137 @@ fn record<Message>(
     shell.publish(on_record(interaction));
 }
 
-fn find_text(
+/// Finds the innermost widget under the given position that can be
+/// targeted unambiguously, either by its [`widget::Id`] or by its text.
+///
+/// Semantic targets are replayed by clicking the center of their visible
+/// bounds, so they are only used if that center lands on the same widget.
+fn find_target(
     position: Point,
     mut operate: impl FnMut(&mut dyn widget::Operation),
-) -> Option<(String, Option<Rectangle>)> {
+) -> Option<(Target, Option<Rectangle>)> {
     use widget::Operation;
 
-    let mut by_position = position.find_all();
-    operate(&mut operation::black_box(&mut by_position));
+    let targets = find_all(position, &mut operate);
+    let innermost = targets.last()?.bounds();
 
-    let operation::Outcome::Some(targets) = by_position.finish() else {
-        return None;
-    };
+    targets.into_iter().rev().find_map(|target| {
+        let visible_bounds = target.visible_bounds();
 
-    let (content, visible_bounds) =
-        targets.into_iter().rev().find_map(|target| {
-            if let selector::Target::Text {
-                content,
-                visible_bounds,
-                ..
-            }
-            | selector::Target::TextInput {
-                content,
-                visible_bounds,
-                ..
-            } = target
+        let center = visible_bounds?.center();
+
+        if find_all(center, &mut operate)
+            .last()
+            .is_none_or(|hit| hit.bounds() != innermost)
+        {
+            return None;
+        }
+
+        if let Some(id) = target.id()
+            && let Some(name) = id.as_str()
+        {
+            let mut by_id = id.clone().find_all();
+            operate(&mut operation::black_box(&mut by_id));
+
+            // A widget may show up more than once (e.g. a text input
+            // is also focusable), but always with the same bounds
+            if let operation::Outcome::Some(matches) = by_id.finish()
+                && matches
+                    .iter()
+                    .all(|other| other.bounds() == target.bounds())
             {
-                Some((content, visible_bounds))
-            } else {
-                None
+                return Some((Target::Id(name.to_owned()), visible_bounds));
             }
-        })?;
+        }
 
-    let mut by_text = content.clone().find_all();
-    operate(&mut operation::black_box(&mut by_text));
+        let (selector::Target::Text { content, .. }
+        | selector::Target::TextInput { content, .. }) = target
+        else {
+            return None;
+        };
 
-    let operation::Outcome::Some(texts) = by_text.finish() else {
-        return None;
-    };
+        let mut by_text = content.clone().find_all();
+        operate(&mut operation::black_box(&mut by_text));
+
+        let operation::Outcome::Some(texts) = by_text.finish() else {
+            return None;
+        };
+
+        (texts.len() == 1).then_some((Target::Text(content), visible_bounds))
+    })
+}
+
+/// Finds all the widgets under the given position; from the outermost to
+/// the innermost.
+fn find_all(
+    position: Point,
+    operate: &mut impl FnMut(&mut dyn widget::Operation),
+) -> Vec<selector::Target> {
+    use widget::Operation;
+
+    let mut by_position = position.find_all();
+    operate(&mut operation::black_box(&mut by_position));
+
+    match by_position.finish() {
+        operation::Outcome::Some(targets) => targets,
+        _ => Vec::new(),
+    }
+}
 
-    if texts.len() > 1 {
-        return None;
+/// Appends the given [`Interaction`] to a list of instructions, merging it
+/// with the last one when possible.
+///
+/// Interactions are kept in the order they arrive.
+pub fn append(instructions: &mut Vec<Instruction>, interaction: Interaction) {
+    // Cursor jitter while typing should not split the typed text into
+    // multiple instructions, so we drop any movements that do not change
+    // the target of the last mouse interaction
+    if let Interaction::Mouse(Mouse::Move(target)) = &interaction
+        && let Some(Instruction::Interact(Interaction::Mouse(last))) =
+            instructions.iter().rev().find(|instruction| {
+                !matches!(
+                    instruction,
+                    Instruction::Interact(Interaction::Keyboard(_))
+                )
+            })
+        && let Mouse::Move(last_target)
+        | Mouse::Press {
+            target: Some(last_target),
+            ..
+        }
+        | Mouse::Release {
+            target: Some(last_target),
+            ..
+        }
+        | Mouse::Click {
+            target: Some(last_target),
+            ..
+        } = last
+        && last_target == target
+    {
+        return;
     }
 
-    Some((content, visible_bounds))
+    let mut interaction = Some(interaction);
+
+    while let Some(new_interaction) = interaction.take() {
+        if let Some(Instruction::Interact(_)) = instructions.last()
+            && let Some(Instruction::Interact(last_interaction)) =
+                instructions.pop()
+        {
+            let (merged_interaction, new_interaction) =
+                last_interaction.merge(new_interaction);
+
+            if let Some(new_interaction) = new_interaction {
+                instructions.push(Instruction::Interact(merged_interaction));
+                instructions.push(Instruction::Interact(new_interaction));
+            } else {
+                interaction = Some(merged_interaction);
+            }
+        } else {
+            instructions.push(Instruction::Interact(new_interaction));
+        }
+    }
 }
 
 fn highlight(theme: &impl theme::Base) -> Color {
@@ -502,3 +589,220 @@ fn highlight(theme: &impl theme::Base) -> Color {
         .map(|palette| palette.primary)
         .unwrap_or(Color::from_rgb(0.0, 0.0, 1.0))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::instruction;
+    use crate::test::simulator::{self, Simulator};
+    use crate::widget::{button, column, scrollable, space, text, text_input};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Record(Interaction),
+        Press(&'static str),
+        Input(String),
+    }
+
+    fn view<'a>()
+    -> Element<'a, Message, core::Theme, crate::test::renderer::Renderer> {
+        column![
+            button(text("Save")).on_press(Message::Press("save")),
+            scrollable(
+                column![
+                    button(text("Item")).on_press(Message::Press("item 1")),
+                    button(text("Item")).on_press(Message::Press("item 2")),
+                    space().height(200),
+                ]
+                .width(200)
+            )
+            .id("items")
+            .height(300),
+            scrollable(
+                text_input("Name", "").id("name").on_input(Message::Input)
+            )
+            .id("form"),
+        ]
+        .into()
+    }
+
+    /// Records the given positions being clicked, each followed by the
+    /// given text being typed; returning the recorded instructions and
+    /// the produced messages.
+    fn record(clicks: &[(Point, &str)]) -> (Vec<Instruction>, Vec<Message>) {
+        let mut simulator =
+            Simulator::new(recorder(view()).on_record(Message::Record));
+
+        for (position, text) in clicks {
+            simulator.point_at(*position);
+
+            let _ = simulator.simulate(
+                std::iter::once(Event::Mouse(mouse::Event::CursorMoved {
+                    position: *position,
+                }))
+                .chain(simulator::click())
+                .chain(simulator::typewrite(text)),
+            );
+        }
+
+        let mut instructions = Vec::new();
+        let mut messages = Vec::new();
+
+        for message in simulator.into_messages() {
+            if let Message::Record(interaction) = message {
+                append(&mut instructions, interaction);
+            } else {
+                messages.push(message);
+            }
+        }
+
+        (instructions, messages)
+    }
+
+    /// Writes the given instructions as a script, parses it back, and
+    /// replays it; returning the produced messages.
+    fn replay(instructions: &[Instruction]) -> Vec<Message> {
+        let mut simulator = Simulator::new(view());
+
+        for instruction in instructions {
+            let Instruction::Interact(interaction) =
+                Instruction::parse(&instruction.to_string())
+                    .expect("Parse instruction")
+            else {
+                panic!("{instruction} is not an interaction");
+            };
+
+            let events = interaction
+                .events(|target| match target {
+                    Target::Point(position) => Some(*position),
+                    Target::Text(text) => simulator
+                        .find(text.as_str())
+                        .ok()?
+                        .visible_bounds()
+                        .map(|bounds| bounds.center()),
+                    Target::Id(id) => simulator
+                        .find(widget::Id::from(id.clone()))
+                        .ok()?
+                        .visible_bounds()
+                        .map(|bounds| bounds.center()),
+                })
+                .expect("Find target");
+
+            for event in events {
+                if let Event::Mouse(mouse::Event::CursorMoved { position }) =
+                    &event
+                {
+                    simulator.point_at(*position);
+                }
+
+                let _ = simulator.simulate([event]);
+            }
+        }
+
+        simulator.into_messages().collect()
+    }
+
+    fn center(simulator: &mut Simulator<'_, Message>, selector: &str) -> Point {
+        simulator
+            .find(selector)
+            .expect("Find target")
+            .visible_bounds()
+            .expect("Target is visible")
+            .center()
+    }
+
+    #[test]
+    fn unique_texts_and_ids_are_targeted() {
+        let mut simulator = Simulator::new(view());
+        let save = center(&mut simulator, "Save");
+        let name = center(&mut simulator, "Name");
+
+        let (instructions, messages) = record(&[(save, ""), (name, "Hi")]);
+
+        assert_eq!(
+            instructions,
+            [
+                Instruction::Interact(Interaction::Mouse(Mouse::Click {
+                    button: mouse::Button::Left,
+                    target: Some(Target::Text("Save".to_owned())),
+                })),
+                Instruction::Interact(Interaction::Mouse(Mouse::Click {
+                    button: mouse::Button::Left,
+                    target: Some(Target::Id("name".to_owned())),
+                })),
+                Instruction::Interact(Interaction::Keyboard(
+                    instruction::Keyboard::Typewrite("Hi".to_owned())
+                )),
+            ]
+        );
+
+        assert_eq!(messages[0], Message::Press("save"));
+        assert_eq!(replay(&instructions), messages);
+    }
+
+    #[test]
+    fn nested_ids_are_only_targeted_if_they_land_on_the_same_widget() {
+        let mut simulator = Simulator::new(view());
+        let item = simulator
+            .find("Item")
+            .expect("Find target")
+            .visible_bounds()
+            .expect("Target is visible")
+            .center();
+
+        // The text is ambiguous and the center of the scrollable with an
+        // id lands on empty space, so the position is kept
+        let (instructions, messages) = record(&[(item, "")]);
+
+        assert_eq!(
+            instructions,
+            [Instruction::Interact(Interaction::Mouse(Mouse::Click {
+                button: mouse::Button::Left,
+                target: Some(Target::Point(item)),
+            }))]
+        );
+
+        assert_eq!(messages, [Message::Press("item 1")]);
+        assert_eq!(replay(&instructions), messages);
+    }
+
+    #[test]
+    fn interactions_are_kept_in_order() {
+        let typewrite = |text: &str| {
+            Interaction::Keyboard(instruction::Keyboard::Typewrite(
+                text.to_owned(),
+            ))
+        };
+
+        let move_to = |target| Interaction::Mouse(Mouse::Move(target));
+
+        let mut instructions = Vec::new();
+
+        append(&mut instructions, move_to(Target::Id("name".to_owned())));
+        append(&mut instructions, typewrite("a"));
+        append(&mut instructions, move_to(Target::Id("name".to_owned())));
+        append(&mut instructions, typewrite("b"));
+        append(&mut instructions, move_to(Target::Text("Save".to_owned())));
+        append(&mut instructions, typewrite("c"));
+        append(
+            &mut instructions,
+            Interaction::Keyboard(instruction::Keyboard::Press(
+                instruction::Key::Enter,
+            )),
+        );
+
+        assert_eq!(
+            instructions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "move #name",
+                "type \"ab\"",
+                "move \"Save\"",
+                "type \"c\"",
+                "press enter",
+            ]
+        );
+    }
+}
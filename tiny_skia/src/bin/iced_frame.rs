@@ -0,0 +1,58 @@
+//! Renders a frame captured with the `capture` feature into a PNG image.
+//!
+//! ```text
+//! iced_frame <capture.json> <output.png>
+//! ```
+use iced_tiny_skia::Renderer;
+use iced_tiny_skia::core::{Font, Pixels, Size};
+use iced_tiny_skia::graphics::Viewport;
+use iced_tiny_skia::graphics::capture::Frame;
+use iced_tiny_skia::window::compositor;
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::process;
+
+fn main() {
+    let mut args = env::args().skip(1);
+
+    let (Some(input), Some(output)) = (args.next(), args.next()) else {
+        eprintln!("Usage: iced_frame <capture.json> <output.png>");
+        process::exit(2);
+    };
+
+    if let Err(error) = render(&input, &output) {
+        eprintln!("Failed to render {input}: {error}");
+        process::exit(1);
+    }
+}
+
+fn render(input: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let frame = Frame::from_json(&fs::read_to_string(input)?)?;
+
+    let mut renderer = Renderer::new(Font::DEFAULT, Pixels(16.0));
+    renderer.replay(&frame);
+
+    let viewport = Viewport::with_physical_size(
+        Size::new(frame.width, frame.height),
+        frame.scale_factor,
+    );
+
+    let rgba =
+        compositor::screenshot(&mut renderer, &viewport, frame.background);
+
+    let mut encoder = png::Encoder::new(
+        io::BufWriter::new(fs::File::create(output)?),
+        frame.width,
+        frame.height,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&rgba)?;
+
+    Ok(())
+}
@@ -0,0 +1,540 @@
+use crate::Renderer;
+use crate::core::renderer::Quad;
+use crate::core::{
+    Background, Border, Color, Pixels, Point, Rectangle, Shadow, Size,
+    Transformation, alignment, border, text,
+};
+use crate::engine;
+use crate::graphics::Viewport;
+use crate::graphics::capture;
+use crate::graphics::gradient;
+use crate::graphics::text::Text;
+use crate::layer::{Item, Layer};
+use crate::primitive::Primitive;
+
+use std::fmt::Write;
+
+/// The color used to outline captured images, since their pixels are not
+/// part of a capture.
+const IMAGE_OUTLINE: Color = Color::from_rgba(0.5, 0.5, 0.5, 0.8);
+
+pub fn frame(
+    layers: &[Layer],
+    viewport: &Viewport,
+    background: Color,
+) -> capture::Frame {
+    let size = viewport.physical_size();
+    let viewport_bounds = Rectangle::with_size(viewport.logical_size());
+
+    capture::Frame {
+        width: size.width,
+        height: size.height,
+        scale_factor: viewport.scale_factor(),
+        background,
+        layers: layers
+            .iter()
+            .filter_map(|layer| {
+                let bounds = layer.bounds.intersection(&viewport_bounds)?;
+
+                Some(self::layer(layer, bounds))
+            })
+            .collect(),
+    }
+}
+
+fn layer(layer: &Layer, bounds: Rectangle) -> capture::Layer {
+    capture::Layer {
+        bounds,
+        quads: layer
+            .quads
+            .iter()
+            .map(|(quad, background)| capture::Quad::new(quad, background))
+            .collect(),
+        paths: layer
+            .primitives
+            .iter()
+            .filter_map(|item| {
+                Some((item, item.clip_bounds().intersection(&bounds)?))
+            })
+            .flat_map(|(item, clip_bounds)| {
+                item.as_slice().iter().filter_map(move |primitive| {
+                    path(primitive, item.transformation(), clip_bounds)
+                })
+            })
+            .collect(),
+        // tiny-skia cannot draw meshes
+        meshes: Vec::new(),
+        images: layer
+            .images
+            .iter()
+            .filter_map(|image| {
+                let mut image =
+                    capture::Image::new(image, Transformation::IDENTITY);
+
+                image.clip_bounds = image.clip_bounds.intersection(&bounds)?;
+
+                Some(image)
+            })
+            .collect(),
+        text: layer
+            .text
+            .iter()
+            .flat_map(|item| {
+                item.as_slice().iter().filter_map(|text| {
+                    capture::Text::new(text, item.transformation())
+                })
+            })
+            .collect(),
+        uncaptured: 0,
+    }
+}
+
+fn path(
+    primitive: &Primitive,
+    transformation: Transformation,
+    clip_bounds: Rectangle,
+) -> Option<capture::Path> {
+    let (path, paint, gradient, stroke_width) = match primitive {
+        Primitive::Fill {
+            path,
+            paint,
+            gradient,
+            ..
+        } => (path, paint, gradient, None),
+        Primitive::Stroke {
+            path,
+            paint,
+            stroke,
+            gradient,
+        } => (
+            path,
+            paint,
+            gradient,
+            Some(stroke.width * transformation.scale_factor()),
+        ),
+    };
+
+    let path = path
+        .clone()
+        .transform(engine::into_transform(transformation))?;
+
+    let fill = match (paint.shader.clone(), gradient) {
+        (tiny_skia::Shader::SolidColor(color), _) => {
+            capture::Fill::Color(from_color(color))
+        }
+        (_, Some(gradient)) => {
+            let mut gradient = capture::Gradient::from(*gradient);
+
+            for point in [&mut gradient.start, &mut gradient.end] {
+                let transformed =
+                    Point::new(point[0], point[1]) * transformation;
+
+                *point = [transformed.x, transformed.y];
+            }
+
+            capture::Fill::Gradient(gradient)
+        }
+        _ => return None,
+    };
+
+    Some(match stroke_width {
+        Some(width) => capture::Path {
+            data: to_svg(&path),
+            fill: None,
+            stroke: Some((fill, width)),
+            clip_bounds,
+        },
+        None => capture::Path {
+            data: to_svg(&path),
+            fill: Some(fill),
+            stroke: None,
+            clip_bounds,
+        },
+    })
+}
+
+/// Replaces the layers of the [`Renderer`] with the ones of a captured
+/// [`capture::Frame`].
+pub fn replay(renderer: &mut Renderer, frame: &capture::Frame) {
+    let bounds = Rectangle::with_size(
+        Size::new(frame.width as f32, frame.height as f32)
+            * (1.0 / frame.scale_factor),
+    );
+
+    renderer.layers.reset(bounds);
+
+    for captured in &frame.layers {
+        renderer.layers.push_clip(captured.bounds);
+
+        let (layer, _) = renderer.layers.current_mut();
+
+        for quad in &captured.quads {
+            layer.quads.push((
+                Quad {
+                    bounds: quad.bounds,
+                    border: Border {
+                        color: quad.border_color,
+                        width: quad.border_width,
+                        radius: {
+                            let [
+                                top_left,
+                                top_right,
+                                bottom_right,
+                                bottom_left,
+                            ] = quad.border_radius;
+
+                            border::Radius {
+                                top_left,
+                                top_right,
+                                bottom_right,
+                                bottom_left,
+                            }
+                        },
+                    },
+                    shadow: Shadow {
+                        color: quad.shadow_color,
+                        offset: quad.shadow_offset.into(),
+                        blur_radius: quad.shadow_blur_radius,
+                    },
+                    snap: false,
+                },
+                quad.background.background(quad.bounds),
+            ));
+        }
+
+        for image in &captured.images {
+            layer.quads.push((
+                Quad {
+                    bounds: image.bounds,
+                    border: Border {
+                        color: IMAGE_OUTLINE,
+                        width: 1.0,
+                        radius: border::Radius::default(),
+                    },
+                    ..Quad::default()
+                },
+                Background::Color(Color::TRANSPARENT),
+            ));
+        }
+
+        for path in &captured.paths {
+            let Some(primitive) = primitive(path) else {
+                continue;
+            };
+
+            layer.primitives.push(Item::Group(
+                vec![primitive],
+                path.clip_bounds,
+                Transformation::IDENTITY,
+            ));
+        }
+
+        // tiny-skia cannot draw meshes, so every triangle is filled
+        // with the average color of its vertices
+        for mesh in &captured.meshes {
+            let triangles =
+                mesh.indices.chunks_exact(3).filter_map(|indices| {
+                    let vertices = indices
+                        .iter()
+                        .map(|index| {
+                            let index = *index as usize;
+
+                            Some((
+                                *mesh.vertices.get(index)?,
+                                *mesh.colors.get(index)?,
+                            ))
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+
+                    let mut builder = tiny_skia::PathBuilder::new();
+                    builder.move_to(vertices[0].0[0], vertices[0].0[1]);
+                    builder.line_to(vertices[1].0[0], vertices[1].0[1]);
+                    builder.line_to(vertices[2].0[0], vertices[2].0[1]);
+                    builder.close();
+
+                    let color = vertices.iter().fold(
+                        Color::TRANSPARENT,
+                        |sum, (_, color)| Color {
+                            r: sum.r + color.r / 3.0,
+                            g: sum.g + color.g / 3.0,
+                            b: sum.b + color.b / 3.0,
+                            a: sum.a + color.a / 3.0,
+                        },
+                    );
+
+                    Some(Primitive::Fill {
+                        path: builder.finish()?,
+                        paint: tiny_skia::Paint {
+                            shader: tiny_skia::Shader::SolidColor(
+                                engine::into_color(color),
+                            ),
+                            anti_alias: true,
+                            ..tiny_skia::Paint::default()
+                        },
+                        rule: tiny_skia::FillRule::Winding,
+                        gradient: None,
+                    })
+                });
+
+            layer.primitives.push(Item::Group(
+                triangles.collect(),
+                mesh.clip_bounds,
+                Transformation::IDENTITY,
+            ));
+        }
+
+        for text in &captured.text {
+            let Some(content) = &text.content else {
+                continue;
+            };
+
+            let size = text.size.map_or(renderer.default_text_size, Pixels);
+
+            layer.text.push(Item::Live(Text::Cached {
+                content: content.clone(),
+                bounds: text.bounds,
+                color: text.color,
+                size,
+                line_height: text::LineHeight::default().to_absolute(size),
+                font: renderer.default_font,
+                align_x: text::Alignment::Left,
+                align_y: alignment::Vertical::Top,
+                shaping: text::Shaping::Advanced,
+                clip_bounds: text.bounds,
+            }));
+        }
+
+        renderer.layers.pop_clip();
+    }
+}
+
+fn primitive(path: &capture::Path) -> Option<Primitive> {
+    let paint = |fill: &capture::Fill| {
+        let (shader, gradient) = match fill {
+            capture::Fill::Color(color) => (
+                tiny_skia::Shader::SolidColor(engine::into_color(*color)),
+                None,
+            ),
+            capture::Fill::Gradient(gradient) => {
+                let linear = gradient.stops.iter().fold(
+                    gradient::Linear::new(
+                        Point::new(gradient.start[0], gradient.start[1]),
+                        Point::new(gradient.end[0], gradient.end[1]),
+                    ),
+                    |linear, (offset, color)| linear.add_stop(*offset, *color),
+                );
+
+                let shader = tiny_skia::LinearGradient::new(
+                    tiny_skia::Point::from_xy(
+                        gradient.start[0],
+                        gradient.start[1],
+                    ),
+                    tiny_skia::Point::from_xy(gradient.end[0], gradient.end[1]),
+                    gradient
+                        .stops
+                        .iter()
+                        .map(|(offset, color)| {
+                            tiny_skia::GradientStop::new(
+                                *offset,
+                                engine::into_color(*color),
+                            )
+                        })
+                        .collect(),
+                    tiny_skia::SpreadMode::Pad,
+                    tiny_skia::Transform::identity(),
+                )
+                .unwrap_or(tiny_skia::Shader::SolidColor(
+                    tiny_skia::Color::TRANSPARENT,
+                ));
+
+                (shader, Some(linear))
+            }
+        };
+
+        (
+            tiny_skia::Paint {
+                shader,
+                anti_alias: true,
+                ..tiny_skia::Paint::default()
+            },
+            gradient,
+        )
+    };
+
+    let data = from_svg(&path.data)?;
+
+    if let Some((fill, width)) = &path.stroke {
+        let (paint, gradient) = paint(fill);
+
+        Some(Primitive::Stroke {
+            path: data,
+            paint,
+            stroke: tiny_skia::Stroke {
+                width: *width,
+                ..tiny_skia::Stroke::default()
+            },
+            gradient,
+        })
+    } else {
+        let (paint, gradient) = paint(path.fill.as_ref()?);
+
+        Some(Primitive::Fill {
+            path: data,
+            paint,
+            rule: tiny_skia::FillRule::Winding,
+            gradient,
+        })
+    }
+}
+
+fn from_color(color: tiny_skia::Color) -> Color {
+    // `engine::into_color` swaps the red and blue channels
+    Color::from_rgba(color.blue(), color.green(), color.red(), color.alpha())
+}
+
+fn to_svg(path: &tiny_skia::Path) -> String {
+    let mut data = String::new();
+
+    for segment in path.segments() {
+        let _ = match segment {
+            tiny_skia::PathSegment::MoveTo(p) => {
+                write!(data, "M {} {} ", p.x, p.y)
+            }
+            tiny_skia::PathSegment::LineTo(p) => {
+                write!(data, "L {} {} ", p.x, p.y)
+            }
+            tiny_skia::PathSegment::QuadTo(a, p) => {
+                write!(data, "Q {} {} {} {} ", a.x, a.y, p.x, p.y)
+            }
+            tiny_skia::PathSegment::CubicTo(a, b, p) => write!(
+                data,
+                "C {} {} {} {} {} {} ",
+                a.x, a.y, b.x, b.y, p.x, p.y
+            ),
+            tiny_skia::PathSegment::Close => write!(data, "Z "),
+        };
+    }
+
+    data.truncate(data.trim_end().len());
+    data
+}
+
+/// Parses the SVG path data produced by [`to_svg`].
+fn from_svg(data: &str) -> Option<tiny_skia::Path> {
+    let mut builder = tiny_skia::PathBuilder::new();
+    let mut tokens = data.split_whitespace();
+
+    while let Some(command) = tokens.next() {
+        let mut next = || tokens.next()?.parse::<f32>().ok();
+
+        match command {
+            "M" => builder.move_to(next()?, next()?),
+            "L" => builder.line_to(next()?, next()?),
+            "Q" => builder.quad_to(next()?, next()?, next()?, next()?),
+            "C" => builder.cubic_to(
+                next()?,
+                next()?,
+                next()?,
+                next()?,
+                next()?,
+                next()?,
+            ),
+            "Z" => builder.close(),
+            _ => return None,
+        }
+    }
+
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn svg_path_data_round_trips() {
+        let mut builder = tiny_skia::PathBuilder::new();
+        builder.move_to(0.0, 0.5);
+        builder.line_to(10.0, 0.0);
+        builder.quad_to(10.0, 10.0, 5.0, 10.0);
+        builder.cubic_to(2.0, 10.0, 0.0, 8.0, 0.0, 5.0);
+        builder.close();
+
+        let path = builder.finish().unwrap();
+        let data = to_svg(&path);
+
+        assert_eq!(data, "M 0 0.5 L 10 0 Q 10 10 5 10 C 2 10 0 8 0 5 Z");
+        assert_eq!(from_svg(&data), Some(path));
+
+        assert_eq!(from_svg("M 0 0 X 1 1"), None);
+        assert_eq!(from_svg("M 0 0 L 1"), None);
+    }
+
+    #[cfg(feature = "geometry")]
+    #[test]
+    fn gradient_paths_are_captured() {
+        use crate::Geometry;
+        use crate::graphics::geometry::frame::Backend;
+        use crate::graphics::geometry::{self, Path, Stroke};
+
+        let linear =
+            gradient::Linear::new(Point::ORIGIN, Point::new(20.0, 0.0))
+                .add_stop(0.0, Color::BLACK)
+                .add_stop(1.0, Color::WHITE);
+
+        let mut frame = crate::geometry::Frame::new(Rectangle::with_size(
+            Size::new(100.0, 100.0),
+        ));
+
+        frame.translate(crate::core::Vector::new(10.0, 0.0));
+        frame.fill_rectangle(Point::ORIGIN, Size::new(20.0, 10.0), linear);
+        frame.stroke(
+            &Path::line(Point::ORIGIN, Point::new(0.0, 10.0)),
+            Stroke::default().with_width(2.0).with_color(Color::BLACK),
+        );
+        frame.stroke(
+            &Path::line(Point::ORIGIN, Point::new(20.0, 0.0)),
+            Stroke {
+                style: geometry::Style::Gradient(linear.into()),
+                ..Stroke::default()
+            },
+        );
+
+        let Geometry::Live { primitives, .. } = frame.into_geometry() else {
+            panic!("frame is live");
+        };
+
+        let clip_bounds = Rectangle::with_size(Size::new(200.0, 200.0));
+
+        let paths: Vec<_> = primitives
+            .iter()
+            .filter_map(|primitive| {
+                path(primitive, Transformation::scale(2.0), clip_bounds)
+            })
+            .collect();
+
+        let gradient = capture::Fill::Gradient(capture::Gradient {
+            start: [20.0, 0.0],
+            end: [60.0, 0.0],
+            stops: vec![(0.0, Color::BLACK), (1.0, Color::WHITE)],
+        });
+
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0].fill, Some(gradient.clone()));
+        assert_eq!(
+            paths[1].stroke,
+            Some((capture::Fill::Color(Color::BLACK), 4.0))
+        );
+        assert_eq!(paths[2].stroke, Some((gradient, 2.0)));
+
+        // Replayed paths are captured again as they were
+        for captured in paths {
+            let replayed = primitive(&captured).unwrap();
+
+            assert_eq!(
+                path(&replayed, Transformation::IDENTITY, clip_bounds),
+                Some(captured)
+            );
+        }
+    }
+}
@@ -475,7 +475,9 @@ impl Engine {
         layer_bounds: Rectangle,
     ) {
         match primitive {
-            Primitive::Fill { path, paint, rule } => {
+            Primitive::Fill {
+                path, paint, rule, ..
+            } => {
                 let physical_bounds = {
                     let bounds = path.bounds();
 
@@ -508,6 +510,7 @@ impl Engine {
                 path,
                 paint,
                 stroke,
+                ..
             } => {
                 let physical_bounds = {
                     let bounds = path.bounds();
@@ -640,7 +643,7 @@ pub fn into_color(color: Color) -> tiny_skia::Color {
         .expect("Convert color from iced to tiny_skia")
 }
 
-fn into_transform(transformation: Transformation) -> tiny_skia::Transform {
+pub fn into_transform(transformation: Transformation) -> tiny_skia::Transform {
     let translation = transformation.translation();
 
     tiny_skia::Transform {
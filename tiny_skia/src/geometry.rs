@@ -111,6 +111,8 @@ impl geometry::frame::Backend for Frame {
             path,
             paint,
             rule: into_fill_rule(fill.rule),
+            #[cfg(feature = "capture")]
+            gradient: into_gradient(fill.style, self.transform),
         });
     }
 
@@ -138,6 +140,8 @@ impl geometry::frame::Backend for Frame {
             path,
             paint,
             rule: into_fill_rule(fill.rule),
+            #[cfg(feature = "capture")]
+            gradient: into_gradient(fill.style, self.transform),
         });
     }
 
@@ -158,6 +162,8 @@ impl geometry::frame::Backend for Frame {
             path,
             paint,
             stroke: skia_stroke,
+            #[cfg(feature = "capture")]
+            gradient: into_gradient(stroke.style, self.transform),
         });
     }
 
@@ -424,6 +430,31 @@ fn convert_path(path: &Path) -> Option<tiny_skia::Path> {
     result
 }
 
+/// Returns the linear gradient of the given [`Style`], if any, with the
+/// given transform applied.
+#[cfg(feature = "capture")]
+fn into_gradient(
+    style: Style,
+    transform: tiny_skia::Transform,
+) -> Option<graphics::gradient::Linear> {
+    let Style::Gradient(Gradient::Linear(linear)) = style else {
+        return None;
+    };
+
+    let mut points = [
+        tiny_skia::Point::from_xy(linear.start.x, linear.start.y),
+        tiny_skia::Point::from_xy(linear.end.x, linear.end.y),
+    ];
+
+    transform.map_points(&mut points);
+
+    Some(graphics::gradient::Linear {
+        start: Point::new(points[0].x, points[0].y),
+        end: Point::new(points[1].x, points[1].y),
+        ..linear
+    })
+}
+
 pub fn into_paint(style: Style) -> tiny_skia::Paint<'static> {
     tiny_skia::Paint {
         shader: match style {
@@ -17,6 +17,9 @@ mod vector;
 #[cfg(feature = "geometry")]
 pub mod geometry;
 
+#[cfg(feature = "capture")]
+mod capture;
+
 use iced_debug as debug;
 pub use iced_graphics as graphics;
 pub use iced_graphics::core;
@@ -37,6 +40,8 @@ use crate::graphics::Viewport;
 use crate::graphics::compositor;
 use crate::graphics::text::{Editor, Paragraph};
 
+use std::path::Path;
+
 /// A [`tiny-skia`] graphics renderer for [`iced`].
 ///
 /// [`tiny-skia`]: https://github.com/RazrFalcon/tiny-skia
@@ -64,6 +69,26 @@ impl Renderer {
         self.layers.as_slice()
     }
 
+    /// Captures the current layers of the [`Renderer`] as a
+    /// [`graphics::capture::Frame`].
+    #[cfg(feature = "capture")]
+    pub fn capture(
+        &mut self,
+        viewport: &Viewport,
+        background_color: Color,
+    ) -> graphics::capture::Frame {
+        self.layers.flush();
+
+        capture::frame(self.layers.as_slice(), viewport, background_color)
+    }
+
+    /// Replaces the current layers of the [`Renderer`] with the ones of a
+    /// captured [`graphics::capture::Frame`]; so it can be drawn again.
+    #[cfg(feature = "capture")]
+    pub fn replay(&mut self, frame: &graphics::capture::Frame) {
+        capture::replay(self, frame);
+    }
+
     pub fn draw(
         &mut self,
         pixels: &mut tiny_skia::PixmapMut<'_>,
@@ -248,6 +273,14 @@ impl core::Renderer for Renderer {
         #[cfg(not(feature = "image"))]
         callback(Err(core::image::Error::Unsupported))
     }
+
+    fn invalidate_asset(&mut self, _path: &Path) {
+        #[cfg(feature = "image")]
+        self.engine.raster_pipeline.invalidate(_path);
+
+        #[cfg(feature = "svg")]
+        self.engine.vector_pipeline.invalidate(_path);
+    }
 }
 
 impl core::text::Renderer for Renderer {
@@ -10,6 +10,12 @@ pub enum Primitive {
         paint: tiny_skia::Paint<'static>,
         /// The fill rule to follow.
         rule: tiny_skia::FillRule,
+        /// The linear gradient of the paint, if any.
+        ///
+        /// tiny-skia does not expose the stops of its gradients, so they
+        /// are kept to capture them.
+        #[cfg(feature = "capture")]
+        gradient: Option<crate::graphics::gradient::Linear>,
     },
     /// A path stroked with some paint.
     Stroke {
@@ -19,6 +25,12 @@ pub enum Primitive {
         paint: tiny_skia::Paint<'static>,
         /// The stroke settings.
         stroke: tiny_skia::Stroke,
+        /// The linear gradient of the paint, if any.
+        ///
+        /// tiny-skia does not expose the stops of its gradients, so they
+        /// are kept to capture them.
+        #[cfg(feature = "capture")]
+        gradient: Option<crate::graphics::gradient::Linear>,
     },
 }
 
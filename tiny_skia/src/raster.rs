@@ -5,6 +5,7 @@ use crate::graphics;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::cell::RefCell;
 use std::collections::hash_map;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub struct Pipeline {
@@ -81,12 +82,19 @@ impl Pipeline {
     pub fn trim_cache(&mut self) {
         self.cache.borrow_mut().trim();
     }
+
+    pub fn invalidate(&mut self, path: &Path) {
+        self.cache
+            .get_mut()
+            .remove(&graphics::image::canonicalize(path));
+    }
 }
 
 #[derive(Debug, Default)]
 struct Cache {
     entries: FxHashMap<raster::Id, Option<Entry>>,
     hits: FxHashSet<raster::Id>,
+    paths: FxHashMap<raster::Id, PathBuf>,
 }
 
 impl Cache {
@@ -97,6 +105,11 @@ impl Cache {
         let id = handle.id();
 
         if let hash_map::Entry::Vacant(entry) = self.entries.entry(id) {
+            if let raster::Handle::Path(_, path) = handle {
+                let _ =
+                    self.paths.insert(id, graphics::image::canonicalize(path));
+            }
+
             let image = match graphics::image::load(handle) {
                 Ok(image) => image,
                 Err(error) => {
@@ -142,8 +155,22 @@ impl Cache {
             .expect("Image should be allocated"))
     }
 
+    /// Removes the images loaded from the given canonical path.
+    fn remove(&mut self, path: &Path) {
+        self.paths.retain(|id, loaded| {
+            let retain = loaded.as_path() != path;
+
+            if !retain {
+                let _ = self.entries.remove(id);
+            }
+
+            retain
+        });
+    }
+
     fn trim(&mut self) {
         self.entries.retain(|key, _| self.hits.contains(key));
+        self.paths.retain(|key, _| self.entries.contains_key(key));
         self.hits.clear();
     }
 }
@@ -154,3 +181,32 @@ struct Entry {
     height: u32,
     pixels: Vec<u32>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_are_invalidated_through_any_path() {
+        let mut cache = Cache::default();
+
+        // Not images; so they are cached as errors
+        let _ = cache.allocate(&raster::Handle::from_path("src/raster.rs"));
+        let _ = cache
+            .allocate(&raster::Handle::from_path("./src/../src/raster.rs"));
+        let _ = cache.allocate(&raster::Handle::from_path("src/vector.rs"));
+
+        assert_eq!(cache.entries.len(), 3);
+
+        cache
+            .remove(&graphics::image::canonicalize(Path::new("src/raster.rs")));
+
+        assert_eq!(cache.entries.len(), 1);
+        assert_eq!(cache.paths.len(), 1);
+        assert!(
+            cache
+                .entries
+                .contains_key(&raster::Handle::from_path("src/vector.rs").id())
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::svg::{Data, Handle};
 use crate::core::{Color, Rectangle, Size};
+use crate::graphics;
 
 use resvg::usvg;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -8,6 +9,7 @@ use tiny_skia::Transform;
 use std::cell::RefCell;
 use std::collections::hash_map;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 #[derive(Debug)]
@@ -61,6 +63,12 @@ impl Pipeline {
     pub fn trim_cache(&mut self) {
         self.cache.borrow_mut().trim();
     }
+
+    pub fn invalidate(&mut self, path: &Path) {
+        self.cache
+            .get_mut()
+            .remove(&graphics::image::canonicalize(path));
+    }
 }
 
 #[derive(Default)]
@@ -69,6 +77,7 @@ struct Cache {
     tree_hits: FxHashSet<u64>,
     rasters: FxHashMap<RasterKey, tiny_skia::Pixmap>,
     raster_hits: FxHashSet<RasterKey>,
+    paths: FxHashMap<u64, PathBuf>,
     fontdb: Option<Arc<usvg::fontdb::Database>>,
 }
 
@@ -101,6 +110,11 @@ impl Cache {
         };
 
         if let hash_map::Entry::Vacant(entry) = self.trees.entry(id) {
+            if let Data::Path(path) = handle.data() {
+                let _ =
+                    self.paths.insert(id, graphics::image::canonicalize(path));
+            }
+
             let svg = match handle.data() {
                 Data::Path(path) => {
                     fs::read_to_string(path).ok().and_then(|contents| {
@@ -203,9 +217,24 @@ impl Cache {
         self.rasters.get(&key).map(tiny_skia::Pixmap::as_ref)
     }
 
+    /// Removes the svgs loaded from the given canonical path.
+    fn remove(&mut self, path: &Path) {
+        self.paths.retain(|id, loaded| {
+            let retain = loaded.as_path() != path;
+
+            if !retain {
+                let _ = self.trees.remove(id);
+                self.rasters.retain(|key, _| key.id != *id);
+            }
+
+            retain
+        });
+    }
+
     fn trim(&mut self) {
         self.trees.retain(|key, _| self.tree_hits.contains(key));
         self.rasters.retain(|key, _| self.raster_hits.contains(key));
+        self.paths.retain(|key, _| self.trees.contains_key(key));
 
         self.tree_hits.clear();
         self.raster_hits.clear();
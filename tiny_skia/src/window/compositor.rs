@@ -184,6 +184,11 @@ pub fn present(
         })
         .unwrap_or_else(|| vec![Rectangle::with_size(viewport.logical_size())]);
 
+    #[cfg(feature = "capture")]
+    crate::debug::frame_captured(|| {
+        renderer.capture(viewport, background_color).to_json()
+    });
+
     if damage.is_empty() {
         return Ok(());
     }
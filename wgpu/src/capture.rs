@@ -0,0 +1,92 @@
+use crate::core::{Color, Rectangle};
+use crate::graphics::Viewport;
+use crate::graphics::capture;
+use crate::layer::Layer;
+
+pub fn frame(
+    layers: &[Layer],
+    viewport: &Viewport,
+    background: Color,
+) -> capture::Frame {
+    let size = viewport.physical_size();
+    let viewport_bounds = Rectangle::with_size(viewport.logical_size());
+
+    capture::Frame {
+        width: size.width,
+        height: size.height,
+        scale_factor: viewport.scale_factor(),
+        background,
+        layers: layers
+            .iter()
+            .filter(|layer| !layer.is_empty())
+            .filter_map(|layer| {
+                let bounds = layer.bounds.intersection(&viewport_bounds)?;
+
+                Some(self::layer(layer, bounds))
+            })
+            .collect(),
+    }
+}
+
+fn layer(layer: &Layer, bounds: Rectangle) -> capture::Layer {
+    capture::Layer {
+        bounds,
+        quads: layer.quads.capture(),
+        // Paths are tessellated into meshes before reaching the layer, and
+        // polygon paths are not drawn at all
+        paths: Vec::new(),
+        meshes: layer
+            .triangles
+            .iter()
+            .flat_map(|item| {
+                let (meshes, transformation) = item.meshes();
+
+                meshes.iter().filter_map(move |mesh| {
+                    let mut mesh = capture::Mesh::new(mesh, transformation);
+
+                    mesh.clip_bounds =
+                        mesh.clip_bounds.intersection(&bounds)?;
+
+                    Some(mesh)
+                })
+            })
+            .collect(),
+        images: images(layer, bounds),
+        text: layer
+            .text
+            .iter()
+            .flat_map(|item| {
+                let (text, transformation) = item.text();
+
+                text.iter().filter_map(move |text| {
+                    capture::Text::new(text, transformation)
+                })
+            })
+            .collect(),
+        // Custom primitives draw themselves
+        uncaptured: layer.primitives.len(),
+    }
+}
+
+#[cfg(any(feature = "image", feature = "svg"))]
+fn images(layer: &Layer, bounds: Rectangle) -> Vec<capture::Image> {
+    use crate::core::Transformation;
+
+    layer
+        .images
+        .iter()
+        .filter_map(|image| {
+            let mut image =
+                capture::Image::new(image, Transformation::IDENTITY);
+
+            image.clip_bounds = image.clip_bounds.intersection(&bounds)?;
+
+            Some(image)
+        })
+        .collect()
+}
+
+#[cfg(not(any(feature = "image", feature = "svg")))]
+fn images(_layer: &Layer, _bounds: Rectangle) -> Vec<capture::Image> {
+    Vec::new()
+}
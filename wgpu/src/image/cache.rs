@@ -1,5 +1,5 @@
 use crate::core::{self, Size};
-use crate::graphics::Shell;
+use crate::graphics::{self, Shell};
 use crate::image::atlas::{self, Atlas};
 
 #[cfg(all(feature = "image", not(target_arch = "wasm32")))]
@@ -306,6 +306,29 @@ impl Cache {
         self.vector.trim(&mut self.atlas); // TODO: Concurrency
     }
 
+    pub fn invalidate(&mut self, path: &std::path::Path) {
+        let path = graphics::image::canonicalize(path);
+
+        #[cfg(feature = "image")]
+        {
+            self.receive();
+
+            for id in self.raster.cache.loaded_from(&path) {
+                self.raster
+                    .cache
+                    .remove(id, &mut self.atlas, |_bind_group| {
+                        #[cfg(not(target_arch = "wasm32"))]
+                        self.worker.drop(_bind_group);
+                    });
+            }
+        }
+
+        #[cfg(feature = "svg")]
+        for id in self.vector.loaded_from(&path) {
+            self.vector.remove(id, &mut self.atlas);
+        }
+    }
+
     #[cfg(feature = "image")]
     fn receive(&mut self) {
         #[cfg(not(target_arch = "wasm32"))]
@@ -4,6 +4,7 @@ use crate::graphics;
 use crate::image::atlas::{self, Atlas};
 
 use rustc_hash::{FxHashMap, FxHashSet};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 
 pub type Image = graphics::image::Buffer;
@@ -54,6 +55,7 @@ impl Memory {
 pub struct Cache {
     map: FxHashMap<image::Id, Memory>,
     hits: FxHashSet<image::Id>,
+    paths: FxHashMap<image::Id, PathBuf>,
     should_trim: bool,
 }
 
@@ -68,6 +70,10 @@ impl Cache {
         let _ = self.map.insert(handle.id(), memory);
         let _ = self.hits.insert(handle.id());
 
+        if let image::Handle::Path(id, path) = handle {
+            let _ = self.paths.insert(*id, graphics::image::canonicalize(path));
+        }
+
         self.should_trim = true;
     }
 
@@ -75,6 +81,37 @@ impl Cache {
         self.map.contains_key(&handle.id())
     }
 
+    /// Returns the ids of the cached images loaded from the given
+    /// canonical path.
+    pub fn loaded_from(&self, path: &Path) -> Vec<image::Id> {
+        self.paths
+            .iter()
+            .filter(|(_, loaded)| loaded.as_path() == path)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    pub fn remove(
+        &mut self,
+        id: image::Id,
+        atlas: &mut Atlas,
+        on_drop: impl FnOnce(Arc<wgpu::BindGroup>),
+    ) {
+        let _ = self.hits.remove(&id);
+        let _ = self.paths.remove(&id);
+
+        if let Some(Memory::Device {
+            entry, bind_group, ..
+        }) = self.map.remove(&id)
+        {
+            if let Some(bind_group) = bind_group {
+                on_drop(bind_group);
+            } else {
+                atlas.remove(&entry);
+            }
+        }
+    }
+
     pub fn trim(
         &mut self,
         atlas: &mut Atlas,
@@ -117,6 +154,9 @@ impl Cache {
             retain
         });
 
+        let map = &self.map;
+        self.paths.retain(|id, _| map.contains_key(id));
+
         self.hits.clear();
         self.should_trim = false;
     }
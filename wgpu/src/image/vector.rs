@@ -1,11 +1,13 @@
 use crate::core::svg;
 use crate::core::{Color, Size};
+use crate::graphics;
 use crate::image::atlas::{self, Atlas};
 
 use resvg::tiny_skia;
 use resvg::usvg;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Entry in cache corresponding to an svg handle
@@ -37,6 +39,7 @@ pub struct Cache {
     rasterized: FxHashMap<(u64, u32, u32, ColorFilter), atlas::Entry>,
     svg_hits: FxHashSet<u64>,
     rasterized_hits: FxHashSet<(u64, u32, u32, ColorFilter)>,
+    paths: FxHashMap<u64, PathBuf>,
     should_trim: bool,
     fontdb: Option<Arc<usvg::fontdb::Database>>,
 }
@@ -67,6 +70,12 @@ impl Cache {
             ..usvg::Options::default()
         };
 
+        if let svg::Data::Path(path) = handle.data() {
+            let _ = self
+                .paths
+                .insert(handle.id(), graphics::image::canonicalize(path));
+        }
+
         let svg = match handle.data() {
             svg::Data::Path(path) => fs::read_to_string(path)
                 .ok()
@@ -184,6 +193,33 @@ impl Cache {
         }
     }
 
+    /// Returns the ids of the cached svgs loaded from the given canonical
+    /// path.
+    pub fn loaded_from(&self, path: &Path) -> Vec<u64> {
+        self.paths
+            .iter()
+            .filter(|(_, loaded)| loaded.as_path() == path)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Remove the vector and raster data of an svg
+    pub fn remove(&mut self, id: u64, atlas: &mut Atlas) {
+        let _ = self.svgs.remove(&id);
+        let _ = self.svg_hits.remove(&id);
+        let _ = self.paths.remove(&id);
+
+        self.rasterized.retain(|(key, ..), entry| {
+            let retain = *key != id;
+
+            if !retain {
+                atlas.remove(entry);
+            }
+
+            retain
+        });
+    }
+
     /// Load svg and upload raster data
     pub fn trim(&mut self, atlas: &mut Atlas) {
         if !self.should_trim {
@@ -194,6 +230,10 @@ impl Cache {
         let rasterized_hits = &self.rasterized_hits;
 
         self.svgs.retain(|k, _| svg_hits.contains(k));
+
+        let svgs = &self.svgs;
+        self.paths.retain(|k, _| svgs.contains_key(k));
+
         self.rasterized.retain(|k, entry| {
             let retain = rasterized_hits.contains(k);
 
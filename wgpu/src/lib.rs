@@ -37,6 +37,9 @@ mod quad;
 mod text;
 mod triangle;
 
+#[cfg(feature = "capture")]
+mod capture;
+
 #[cfg(any(feature = "image", feature = "svg"))]
 #[path = "image/mod.rs"]
 mod image;
@@ -666,6 +669,16 @@ impl Renderer {
                 })
                 .count()
         });
+
+        #[cfg(feature = "capture")]
+        debug::frame_captured(|| {
+            capture::frame(
+                self.layers.as_slice(),
+                viewport,
+                clear_color.unwrap_or(Color::TRANSPARENT),
+            )
+            .to_json()
+        });
     }
 }
 
@@ -716,6 +729,11 @@ impl core::Renderer for Renderer {
             .get_mut()
             .allocate_image(_handle, _callback);
     }
+
+    fn invalidate_asset(&mut self, _path: &std::path::Path) {
+        #[cfg(any(feature = "svg", feature = "image"))]
+        self.image_cache.get_mut().invalidate(_path);
+    }
 }
 
 impl core::text::Renderer for Renderer {
@@ -310,6 +310,56 @@ impl Batch {
         self.gradients.append(&mut batch.gradients);
         self.order.append(&mut batch.order);
     }
+
+    /// Captures the quads of the [`Batch`] in drawing order.
+    #[cfg(feature = "capture")]
+    pub fn capture(&self) -> Vec<graphics::capture::Quad> {
+        use graphics::capture;
+
+        let mut solids = self.solids.iter();
+        let mut gradients = self.gradients.iter();
+
+        self.order
+            .iter()
+            .flat_map(|(kind, count)| std::iter::repeat_n(*kind, *count))
+            .filter_map(|kind| {
+                let (quad, background) = match kind {
+                    Kind::Solid => {
+                        let solid = solids.next()?;
+
+                        (
+                            &solid.quad,
+                            capture::Fill::Color(color::unpack(solid.color)),
+                        )
+                    }
+                    Kind::Gradient => {
+                        let gradient = gradients.next()?;
+
+                        (
+                            &gradient.quad,
+                            capture::Fill::Gradient(
+                                gradient.gradient.unpack().into(),
+                            ),
+                        )
+                    }
+                };
+
+                Some(capture::Quad {
+                    bounds: Rectangle::new(
+                        quad.position.into(),
+                        quad.size.into(),
+                    ),
+                    background,
+                    border_color: color::unpack(quad.border_color),
+                    border_width: quad.border_width,
+                    border_radius: quad.border_radius,
+                    shadow_color: color::unpack(quad.shadow_color),
+                    shadow_offset: quad.shadow_offset,
+                    shadow_blur_radius: quad.shadow_blur_radius,
+                })
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
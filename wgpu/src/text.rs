@@ -33,6 +33,23 @@ pub enum Item {
     },
 }
 
+impl Item {
+    /// Returns the text of the [`Item`] and its [`Transformation`].
+    #[cfg(feature = "capture")]
+    pub fn text(&self) -> (&[Text], Transformation) {
+        match self {
+            Item::Group {
+                transformation,
+                text,
+            } => (text, *transformation),
+            Item::Cached {
+                transformation,
+                cache,
+            } => (&cache.text, *transformation),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Cache {
     id: Id,
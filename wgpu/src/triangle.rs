@@ -28,6 +28,23 @@ pub enum Item {
     },
 }
 
+impl Item {
+    /// Returns the meshes of the [`Item`] and their [`Transformation`].
+    #[cfg(feature = "capture")]
+    pub fn meshes(&self) -> (&[Mesh], Transformation) {
+        match self {
+            Item::Group {
+                transformation,
+                meshes,
+            } => (meshes, *transformation),
+            Item::Cached {
+                transformation,
+                cache,
+            } => (&cache.batch, *transformation),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Cache {
     id: Id,
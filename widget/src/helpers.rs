@@ -31,6 +31,8 @@ use std::borrow::Borrow;
 use std::ops::RangeInclusive;
 
 pub use crate::table::table;
+pub use crate::tree_view::tree_view;
+pub use crate::virtual_list::virtual_list;
 
 /// Creates a [`Column`] with the given children.
 ///
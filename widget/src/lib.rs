@@ -41,7 +41,9 @@ pub mod text_editor;
 pub mod text_input;
 pub mod toggler;
 pub mod tooltip;
+pub mod tree_view;
 pub mod vertical_slider;
+pub mod virtual_list;
 
 pub mod ext_container;
 
@@ -111,7 +113,11 @@ pub use toggler::Toggler;
 #[doc(no_inline)]
 pub use tooltip::Tooltip;
 #[doc(no_inline)]
+pub use tree_view::TreeView;
+#[doc(no_inline)]
 pub use vertical_slider::VerticalSlider;
+#[doc(no_inline)]
+pub use virtual_list::VirtualList;
 
 #[cfg(feature = "wgpu")]
 pub mod shader;
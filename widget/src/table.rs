@@ -1,15 +1,25 @@
 //! Display tables.
 use crate::core;
 use crate::core::alignment;
+use crate::core::keyboard;
+use crate::core::keyboard::key;
 use crate::core::layout;
 use crate::core::mouse;
 use crate::core::overlay;
 use crate::core::renderer;
+use crate::core::touch;
 use crate::core::widget;
+use crate::core::widget::operation;
+use crate::core::widget::operation::scrollable::AbsoluteOffset;
 use crate::core::{
-    Alignment, Background, Element, Layout, Length, Pixels, Rectangle, Size,
-    Widget,
+    Alignment, Background, Color, Element, Layout, Length, Pixels, Point,
+    Rectangle, Size, Vector, Widget,
 };
+use crate::virtual_list::{RowHeight, Rows};
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::ops::Range;
 
 /// Creates a new [`Table`] with the given columns and rows.
 ///
@@ -45,6 +55,9 @@ where
         width: Length::Shrink,
         align_x: alignment::Horizontal::Left,
         align_y: alignment::Vertical::Top,
+        sort: None,
+        on_sort: None,
+        on_resize: None,
     }
 }
 
@@ -53,7 +66,8 @@ pub struct Table<'a, Message, Theme = crate::Theme, Renderer = crate::Renderer>
 where
     Theme: Catalog,
 {
-    columns: Vec<Column_>,
+    id: Option<widget::Id>,
+    columns: Vec<Column_<'a, Message>>,
     cells: Vec<Element<'a, Message, Theme, Renderer>>,
     width: Length,
     height: Length,
@@ -61,13 +75,27 @@ where
     padding_y: f32,
     separator_x: f32,
     separator_y: f32,
+    selected: Option<usize>,
+    on_select: Option<Box<dyn Fn(usize) -> Message + 'a>>,
+    sticky_header: bool,
+    virtual_rows: Option<VirtualRows<'a, Message, Theme, Renderer>>,
     class: Theme::Class<'a>,
 }
 
-struct Column_ {
+/// The rows of a virtualized [`Table`], built as they come into view.
+struct VirtualRows<'a, Message, Theme, Renderer> {
+    count: usize,
+    height: RowHeight,
+    views: Vec<View<'a, 'a, usize, Message, Theme, Renderer>>,
+}
+
+struct Column_<'a, Message> {
     width: Length,
     align_x: alignment::Horizontal,
     align_y: alignment::Vertical,
+    sort: Option<Sort>,
+    on_sort: Option<Box<dyn Fn(Sort) -> Message + 'a>>,
+    on_resize: Option<Box<dyn Fn(f32) -> Message + 'a>>,
 }
 
 impl<'a, Message, Theme, Renderer> Table<'a, Message, Theme, Renderer>
@@ -91,12 +119,63 @@ where
         let columns = columns.into_iter();
         let rows = rows.into_iter();
 
+        let (mut table, views) =
+            Self::with_columns(columns, rows.size_hint().0);
+
+        for row in rows {
+            for view in &views {
+                let cell = view(row.clone());
+                let size_hint = cell.as_widget().size_hint();
+
+                table.height = table.height.enclose(size_hint.height);
+                table.cells.push(cell);
+            }
+        }
+
+        table
+    }
+
+    /// Creates a new virtualized [`Table`] with the given columns and amount
+    /// of rows.
+    ///
+    /// Unlike [`Table::new`], the view function of each [`Column`] receives the
+    /// index of a row and it is only called for the rows that are in view; so
+    /// the [`Table`] can hold a huge amount of rows. It should be placed inside
+    /// a [`Scrollable`].
+    ///
+    /// Since only the rows in view are laid out, [`Length::Shrink`] columns
+    /// fit the contents of those rows alone and may change their width while
+    /// scrolling. Prefer fixed or fluid widths for the columns of a
+    /// virtualized [`Table`].
+    ///
+    /// [`Scrollable`]: crate::Scrollable
+    pub fn virtualized(
+        columns: impl IntoIterator<
+            Item = Column<'a, 'a, usize, Message, Theme, Renderer>,
+        >,
+        count: usize,
+        row_height: impl Into<RowHeight>,
+    ) -> Self {
+        let (mut table, views) = Self::with_columns(columns.into_iter(), 0);
+
+        table.virtual_rows = Some(VirtualRows {
+            count,
+            height: row_height.into(),
+            views,
+        });
+
+        table
+    }
+
+    /// Creates a [`Table`] with the headers of the given columns and room for
+    /// the given amount of rows; returning the view functions of the columns.
+    fn with_columns<'b, T>(
+        columns: impl Iterator<Item = Column<'a, 'b, T, Message, Theme, Renderer>>,
+        rows: usize,
+    ) -> (Self, Vec<View<'a, 'b, T, Message, Theme, Renderer>>) {
         let mut width = Length::Shrink;
-        let mut height = Length::Shrink;
 
-        let mut cells = Vec::with_capacity(
-            columns.size_hint().0 * (1 + rows.size_hint().0),
-        );
+        let mut cells = Vec::with_capacity(columns.size_hint().0 * (1 + rows));
 
         let (mut columns, views): (Vec<_>, Vec<_>) = columns
             .map(|column| {
@@ -109,40 +188,46 @@ where
                         width: column.width,
                         align_x: column.align_x,
                         align_y: column.align_y,
+                        sort: column.sort,
+                        on_sort: column.on_sort,
+                        on_resize: column.on_resize,
                     },
                     column.view,
                 )
             })
             .collect();
 
-        for row in rows {
-            for view in &views {
-                let cell = view(row.clone());
-                let size_hint = cell.as_widget().size_hint();
-
-                height = height.enclose(size_hint.height);
-
-                cells.push(cell);
-            }
-        }
-
         if width == Length::Shrink
             && let Some(first) = columns.first_mut()
         {
             first.width = Length::Fill;
         }
 
-        Self {
+        let table = Self {
+            id: None,
             columns,
             cells,
             width,
-            height,
+            height: Length::Shrink,
             padding_x: 10.0,
             padding_y: 5.0,
             separator_x: 1.0,
             separator_y: 1.0,
+            selected: None,
+            on_select: None,
+            sticky_header: false,
+            virtual_rows: None,
             class: Theme::default(),
-        }
+        };
+
+        (table, views)
+    }
+
+    /// Sets the [`widget::Id`] of the [`Table`]; which can be used to
+    /// focus it or to [`scroll_to`] one of its rows.
+    pub fn id(mut self, id: impl Into<widget::Id>) -> Self {
+        self.id = Some(id.into());
+        self
     }
 
     /// Sets the width of the [`Table`].
@@ -188,11 +273,419 @@ where
         self.separator_y = separator.into().0;
         self
     }
+
+    /// Sets the index of the selected row of the [`Table`], if any.
+    pub fn selected(mut self, row: impl Into<Option<usize>>) -> Self {
+        self.selected = row.into();
+        self
+    }
+
+    /// Sets the message that should be produced when a row of the [`Table`]
+    /// is selected; either by clicking it or with the arrow keys.
+    ///
+    /// The closure will receive the index of the row. The [`Table`] cannot
+    /// scroll by itself; run [`scroll_to`] with the selected row to keep it
+    /// in view.
+    pub fn on_select(
+        mut self,
+        on_select: impl Fn(usize) -> Message + 'a,
+    ) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Sets whether the header of the [`Table`] should stick to the top of
+    /// the visible area when scrolled; like inside a [`Scrollable`].
+    ///
+    /// [`Scrollable`]: crate::Scrollable
+    pub fn sticky_header(mut self, sticky: bool) -> Self {
+        self.sticky_header = sticky;
+        self
+    }
+
+    /// Returns the horizontal position of the contents of the given column.
+    fn column_x(&self, metrics: &Metrics, column: usize) -> f32 {
+        self.padding_x
+            + metrics.columns[..column]
+                .iter()
+                .map(|width| width + self.padding_x * 2.0 + self.separator_x)
+                .sum::<f32>()
+    }
+
+    /// Returns the vertical position of the contents of the given row; where
+    /// the header is the first row.
+    fn row_y(&self, metrics: &Metrics, row: usize) -> f32 {
+        let offset = if row > 0 { metrics.offset } else { 0.0 };
+
+        self.padding_y
+            + offset
+            + metrics.rows[..row]
+                .iter()
+                .map(|height| height + self.padding_y * 2.0 + self.separator_y)
+                .sum::<f32>()
+    }
+
+    /// Returns the row at the given vertical position, relative to the
+    /// [`Table`].
+    fn row_at(&self, metrics: &Metrics, y: f32) -> Option<usize> {
+        let mut bottom = 0.0;
+
+        for (row, height) in metrics.rows.iter().enumerate() {
+            if row == 1 {
+                bottom += metrics.offset;
+
+                // Above the first row in view
+                if y < bottom {
+                    return None;
+                }
+            }
+
+            bottom += height + self.padding_y * 2.0 + self.separator_y;
+
+            if y < bottom {
+                return Some(row);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the row of the selected data, if in view; where the header is
+    /// the first row.
+    fn selected_row(&self, metrics: &Metrics) -> Option<usize> {
+        self.selected
+            .and_then(|row| row.checked_sub(metrics.first))
+            .map(|row| row + 1)
+            .filter(|row| *row < metrics.rows.len())
+    }
+
+    /// Returns the amount of rows of the [`Table`], excluding the header.
+    fn row_count(&self, metrics: &Metrics) -> usize {
+        self.virtual_rows.as_ref().map_or_else(
+            || metrics.rows.len().saturating_sub(1),
+            |rows| rows.count,
+        )
+    }
+
+    /// Returns the virtual rows intersecting the given vertical range,
+    /// relative to the [`Table`].
+    fn visible_rows(
+        &self,
+        state: &State,
+        top: f32,
+        bottom: f32,
+    ) -> Range<usize> {
+        // The first row starts right below the header
+        let start = self.header_height(&state.metrics) + self.padding_y;
+
+        state.rows.visible(
+            top - start - self.padding_y,
+            bottom - start + self.padding_y,
+        )
+    }
+
+    /// Builds the cells of the virtual rows in view, reusing the state of
+    /// the ones that were already in view.
+    fn build_rows(&mut self, tree: &mut widget::Tree, max_height: f32) {
+        let Some(virtual_rows) = &self.virtual_rows else {
+            return;
+        };
+
+        let columns = self.columns.len();
+        let state = tree.state.downcast_mut::<State>();
+
+        state.rows.update(
+            virtual_rows.count,
+            virtual_rows.height,
+            self.padding_y * 2.0 + self.separator_y,
+        );
+
+        // Until the table is in view, only the rows fitting its limits are built
+        let visible = match state.viewport {
+            Some(viewport) => self.visible_rows(
+                state,
+                viewport.y,
+                viewport.y + viewport.height,
+            ),
+            None if max_height.is_finite() => {
+                self.visible_rows(state, 0.0, max_height)
+            }
+            None => 0..0,
+        };
+
+        self.cells.truncate(columns);
+
+        for row in visible.clone() {
+            for view in &virtual_rows.views {
+                self.cells.push(view(row));
+            }
+        }
+
+        // Body trees are keyed by their row and column
+        let body = tree.children.split_off(columns.min(tree.children.len()));
+
+        let mut trees: HashMap<(usize, usize), widget::Tree> =
+            state.keys.drain(..).zip(body).collect();
+
+        for (i, cell) in self.cells[columns..].iter().enumerate() {
+            let key = (visible.start + i / columns, i % columns);
+
+            tree.children
+                .push(if let Some(mut tree) = trees.remove(&key) {
+                    tree.diff(cell.as_widget());
+                    tree
+                } else {
+                    widget::Tree::new(cell.as_widget())
+                });
+
+            state.keys.push(key);
+        }
+
+        state.visible = visible;
+    }
+
+    /// Returns the column at the given horizontal position, relative to the
+    /// [`Table`].
+    fn column_at(&self, metrics: &Metrics, x: f32) -> Option<usize> {
+        let mut right = 0.0;
+
+        metrics.columns.iter().position(|width| {
+            right += width + self.padding_x * 2.0 + self.separator_x;
+            x < right
+        })
+    }
+
+    /// Returns the resizable column with its divider at the given horizontal
+    /// position, relative to the [`Table`].
+    fn divider_at(&self, metrics: &Metrics, x: f32) -> Option<usize> {
+        (0..metrics.columns.len()).find(|column| {
+            let divider = self.column_x(metrics, *column)
+                + metrics.columns[*column]
+                + self.padding_x
+                + self.separator_x / 2.0;
+
+            self.columns[*column].on_resize.is_some()
+                && (x - divider).abs() <= DIVIDER_MARGIN
+        })
+    }
+
+    /// Returns the height of the header, including its padding.
+    fn header_height(&self, metrics: &Metrics) -> f32 {
+        metrics.rows.first().map_or(0.0, |height| {
+            height + self.padding_y * 2.0 + self.separator_y
+        })
+    }
+
+    /// Returns the vertical offset of the header needed to keep it in view.
+    fn header_offset(
+        &self,
+        metrics: &Metrics,
+        bounds: Rectangle,
+        viewport: &Rectangle,
+    ) -> f32 {
+        if !self.sticky_header {
+            return 0.0;
+        }
+
+        (viewport.y - bounds.y)
+            .min(bounds.height - self.header_height(metrics))
+            .max(0.0)
+    }
+
+    /// Draws the line separators between the cells within the given bounds.
+    fn draw_separators(
+        &self,
+        renderer: &mut Renderer,
+        metrics: &Metrics,
+        style: &Style,
+        bounds: Rectangle,
+    ) {
+        if self.separator_x > 0.0 {
+            let mut x = self.padding_x;
+
+            for width in
+                &metrics.columns[..metrics.columns.len().saturating_sub(1)]
+            {
+                x += width + self.padding_x;
+
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: Rectangle {
+                            x: bounds.x + x,
+                            y: bounds.y,
+                            width: self.separator_x,
+                            height: bounds.height,
+                        },
+                        snap: true,
+                        ..renderer::Quad::default()
+                    },
+                    style.separator_x,
+                );
+
+                x += self.separator_x + self.padding_x;
+            }
+        }
+
+        if self.separator_y > 0.0 {
+            let mut y = self.padding_y;
+
+            for (row, height) in metrics.rows
+                [..metrics.rows.len().saturating_sub(1)]
+                .iter()
+                .enumerate()
+            {
+                if row == 1 {
+                    y += metrics.offset;
+                }
+
+                y += height + self.padding_y;
+
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: Rectangle {
+                            x: bounds.x,
+                            y: bounds.y + y,
+                            width: bounds.width,
+                            height: self.separator_y,
+                        },
+                        snap: true,
+                        ..renderer::Quad::default()
+                    },
+                    style.separator_y,
+                );
+
+                y += self.separator_y + self.padding_y;
+            }
+        }
+    }
+}
+
+/// Draws the indicator of the given [`Sort`], centered vertically at the
+/// given position.
+fn draw_indicator<Renderer>(
+    renderer: &mut Renderer,
+    sort: Sort,
+    position: Point,
+    color: Color,
+) where
+    Renderer: core::Renderer,
+{
+    // A small triangle, one line at a time
+    for line in 0..4u8 {
+        let width = f32::from(line * 2 + 1);
+        let y = match sort {
+            Sort::Ascending => position.y - 2.0 + f32::from(line),
+            Sort::Descending => position.y + 1.0 - f32::from(line),
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    x: position.x + 3.0 - f32::from(line),
+                    y,
+                    width,
+                    height: 1.0,
+                },
+                snap: true,
+                ..renderer::Quad::default()
+            },
+            color,
+        );
+    }
+}
+
+/// The distance to a column divider that can be dragged.
+const DIVIDER_MARGIN: f32 = 4.0;
+
+/// The minimum width of a resized column.
+const MIN_COLUMN_WIDTH: f32 = 10.0;
+
+struct State {
+    metrics: Metrics,
+    is_focused: bool,
+    resizing: Option<Resizing>,
+    rows: Rows,
+    visible: Range<usize>,
+    viewport: Option<Rectangle>,
+    keys: Vec<(usize, usize)>,
+    is_virtual: bool,
+    padding_y: f32,
+    spacing_y: f32,
+    sticky_header: bool,
+}
+
+impl State {
+    /// Returns the vertical span of the given row, excluding the header and
+    /// relative to the [`Table`].
+    fn row_span(&self, row: usize) -> Option<(f32, f32)> {
+        let header = self.metrics.rows.first()? + self.spacing_y;
+
+        if self.is_virtual {
+            if row >= self.rows.count() {
+                return None;
+            }
+
+            let top = header + self.rows.offset(row);
+
+            return Some((
+                top,
+                top + self.rows.height(row) + self.padding_y * 2.0,
+            ));
+        }
+
+        let height = *self.metrics.rows.get(row + 1)?;
+
+        let top = header
+            + self.metrics.rows[1..=row]
+                .iter()
+                .map(|height| height + self.spacing_y)
+                .sum::<f32>();
+
+        Some((top, top + height + self.padding_y * 2.0))
+    }
+
+    /// Returns the height of the header that sticks to the top, if any.
+    fn sticky_height(&self) -> f32 {
+        if self.sticky_header {
+            self.metrics
+                .rows
+                .first()
+                .map_or(0.0, |height| height + self.spacing_y)
+        } else {
+            0.0
+        }
+    }
 }
 
 struct Metrics {
     columns: Vec<f32>,
     rows: Vec<f32>,
+    /// The index of the first row in view, if virtualized.
+    first: usize,
+    /// The vertical offset of the first row in view, if virtualized.
+    offset: f32,
+}
+
+/// A column being resized.
+#[derive(Debug, Clone, Copy)]
+struct Resizing {
+    column: usize,
+    origin: f32,
+    width: f32,
+}
+
+impl operation::Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.is_focused = false;
+    }
 }
 
 impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -209,13 +702,27 @@ where
     }
 
     fn tag(&self) -> widget::tree::Tag {
-        widget::tree::Tag::of::<Metrics>()
+        widget::tree::Tag::of::<State>()
     }
 
     fn state(&self) -> widget::tree::State {
-        widget::tree::State::new(Metrics {
-            columns: Vec::new(),
-            rows: Vec::new(),
+        widget::tree::State::new(State {
+            metrics: Metrics {
+                columns: Vec::new(),
+                rows: Vec::new(),
+                first: 0,
+                offset: 0.0,
+            },
+            is_focused: false,
+            resizing: None,
+            rows: Rows::new(0, RowHeight::Fixed(0.0), 0.0),
+            visible: 0..0,
+            viewport: None,
+            keys: Vec::new(),
+            is_virtual: false,
+            padding_y: 0.0,
+            spacing_y: 0.0,
+            sticky_header: false,
         })
     }
 
@@ -227,7 +734,16 @@ where
     }
 
     fn diff(&self, state: &mut widget::Tree) {
-        state.diff_children(&self.cells);
+        // The rows of a virtualized table are diffed once built, in layout
+        if self.virtual_rows.is_some()
+            && state.children.len() >= self.cells.len()
+        {
+            for (tree, cell) in state.children.iter_mut().zip(&self.cells) {
+                tree.diff(cell.as_widget());
+            }
+        } else {
+            state.diff_children(&self.cells);
+        }
     }
 
     fn layout(
@@ -236,14 +752,37 @@ where
         renderer: &Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        let metrics = tree.state.downcast_mut::<Metrics>();
-        let columns = self.columns.len();
-        let rows = self.cells.len() / columns;
-
         let limits = limits.width(self.width).height(self.height);
         let available = limits.max();
         let table_fluid = self.width.fluid();
 
+        self.build_rows(tree, available.height);
+
+        let state = tree.state.downcast_mut::<State>();
+
+        state.is_virtual = self.virtual_rows.is_some();
+        state.padding_y = self.padding_y;
+        state.spacing_y = self.padding_y * 2.0 + self.separator_y;
+        state.sticky_header = self.sticky_header;
+
+        let State {
+            metrics,
+            rows: virtual_rows,
+            visible,
+            ..
+        } = state;
+
+        let columns = self.columns.len();
+        let rows = self.cells.len() / columns;
+
+        let fixed_height = match &self.virtual_rows {
+            Some(VirtualRows {
+                height: RowHeight::Fixed(height),
+                ..
+            }) => Some(*height),
+            _ => None,
+        };
+
         let mut cells = Vec::with_capacity(self.cells.len());
         cells.resize(self.cells.len(), layout::Node::default());
 
@@ -299,9 +838,14 @@ where
                 continue;
             }
 
+            let max_height = match fixed_height {
+                Some(height) if row > 0 => height,
+                _ => available.height - y,
+            };
+
             let limits = layout::Limits::new(
                 Size::ZERO,
-                Size::new(available.width - x, available.height - y),
+                Size::new(available.width - x, max_height),
             )
             .width(width);
 
@@ -389,6 +933,11 @@ where
                 height_unit * height_factor as f32
             };
 
+            let max_height = match fixed_height {
+                Some(height) if row > 0 => max_height.min(height),
+                _ => max_height,
+            };
+
             let limits = layout::Limits::new(
                 Size::ZERO,
                 Size::new(max_width, max_height),
@@ -413,6 +962,23 @@ where
             x += size.width + spacing_x;
         }
 
+        // Virtual rows are placed at their offset, with a fixed height if needed
+        if self.virtual_rows.is_some() {
+            for (row, height) in metrics.rows.iter_mut().enumerate().skip(1) {
+                if let Some(fixed_height) = fixed_height {
+                    *height = fixed_height;
+                }
+
+                virtual_rows.measure(visible.start + row - 1, *height);
+            }
+
+            metrics.first = visible.start;
+            metrics.offset = virtual_rows.offset(visible.start);
+        } else {
+            metrics.first = 0;
+            metrics.offset = 0.0;
+        }
+
         // THIRD PASS
         // Position each cell
         let mut x = self.padding_x;
@@ -428,6 +994,10 @@ where
                 if row > 0 {
                     y += metrics.rows[row - 1] + spacing_y;
                 }
+
+                if row == 1 {
+                    y += metrics.offset;
+                }
             }
 
             let Column_ {
@@ -444,18 +1014,28 @@ where
             x += metrics.columns[column] + spacing_x;
         }
 
+        let height = if self.virtual_rows.is_some() {
+            let header = metrics.rows.first().copied().unwrap_or_default();
+            let body = virtual_rows.total();
+
+            if body > 0.0 {
+                self.padding_y + header + spacing_y + body + self.padding_y
+            } else {
+                self.padding_y + header + self.padding_y
+            }
+        } else {
+            y + metrics
+                .rows
+                .last()
+                .copied()
+                .map(|height| height + self.padding_y)
+                .unwrap_or_default()
+        };
+
         let intrinsic = limits.resolve(
             self.width,
             self.height,
-            Size::new(
-                x - spacing_x + self.padding_x,
-                y + metrics
-                    .rows
-                    .last()
-                    .copied()
-                    .map(|height| height + self.padding_y)
-                    .unwrap_or_default(),
-            ),
+            Size::new(x - spacing_x + self.padding_x, height),
         );
 
         layout::Node::with_children(intrinsic, cells)
@@ -472,17 +1052,165 @@ where
         shell: &mut core::Shell<'_, Message>,
         viewport: &Rectangle,
     ) {
-        for ((cell, state), layout) in self
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+        let columns = self.columns.len();
+
+        if self.virtual_rows.is_some() {
+            state.viewport =
+                viewport.intersection(&bounds).map(|viewport| Rectangle {
+                    x: viewport.x - bounds.x,
+                    y: viewport.y - bounds.y,
+                    ..viewport
+                });
+
+            let visible = state.viewport.map_or(0..0, |viewport| {
+                self.visible_rows(
+                    state,
+                    viewport.y,
+                    viewport.y + viewport.height,
+                )
+            });
+
+            if visible != state.visible {
+                shell.invalidate_layout();
+                shell.request_redraw();
+            }
+        }
+
+        let offset = self.header_offset(&state.metrics, bounds, viewport);
+        let header = Rectangle {
+            y: bounds.y + offset,
+            height: self.header_height(&state.metrics),
+            ..bounds
+        };
+        let is_over_header = cursor.is_over(header);
+
+        for (i, ((cell, tree), layout)) in self
             .cells
             .iter_mut()
             .zip(&mut tree.children)
             .zip(layout.children())
+            .enumerate()
         {
+            let cursor = if i < columns {
+                cursor + Vector::new(0.0, -offset)
+            } else if is_over_header {
+                cursor.levitate()
+            } else {
+                cursor
+            };
+
             cell.as_widget_mut().update(
-                state, event, layout, cursor, renderer, clipboard, shell,
+                tree, event, layout, cursor, renderer, clipboard, shell,
                 viewport,
             );
         }
+
+        // Clicks elsewhere unfocus the table, even if captured
+        if let core::Event::Mouse(mouse::Event::ButtonPressed(_))
+        | core::Event::Touch(touch::Event::FingerPressed { .. }) = event
+            && !cursor.is_over(bounds)
+        {
+            state.is_focused = false;
+        }
+
+        if let Some(resizing) = state.resizing {
+            match event {
+                core::Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    let width = (resizing.width + position.x - resizing.origin)
+                        .max(MIN_COLUMN_WIDTH);
+
+                    if let Some(on_resize) =
+                        &self.columns[resizing.column].on_resize
+                    {
+                        shell.publish(on_resize(width));
+                    }
+
+                    shell.capture_event();
+                }
+                core::Event::Mouse(mouse::Event::ButtonReleased(
+                    mouse::Button::Left,
+                )) => {
+                    state.resizing = None;
+                    shell.capture_event();
+                }
+                _ => {}
+            }
+
+            return;
+        }
+
+        if shell.is_event_captured() {
+            return;
+        }
+
+        match event {
+            core::Event::Mouse(mouse::Event::ButtonPressed(
+                mouse::Button::Left,
+            )) => {
+                let Some(position) = cursor.position_over(bounds) else {
+                    return;
+                };
+
+                let x = position.x - bounds.x;
+                let metrics = &state.metrics;
+
+                if is_over_header {
+                    if let Some(column) = self.divider_at(metrics, x) {
+                        state.resizing = Some(Resizing {
+                            column,
+                            origin: position.x,
+                            width: metrics.columns[column],
+                        });
+
+                        shell.capture_event();
+                    } else if let Some(column) = self.column_at(metrics, x)
+                        && let Some(on_sort) = &self.columns[column].on_sort
+                    {
+                        let sort = self.columns[column]
+                            .sort
+                            .map_or(Sort::Ascending, Sort::reverse);
+
+                        shell.publish(on_sort(sort));
+                        shell.capture_event();
+                    }
+                } else if let Some(on_select) = &self.on_select
+                    && let Some(row) =
+                        self.row_at(metrics, position.y - bounds.y)
+                    && row > 0
+                {
+                    state.is_focused = true;
+
+                    shell.publish(on_select(metrics.first + row - 1));
+                    shell.capture_event();
+                }
+            }
+            core::Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                ..
+            }) if state.is_focused => {
+                let Some(on_select) = &self.on_select else {
+                    return;
+                };
+
+                let rows = self.row_count(&state.metrics);
+                let last = rows.saturating_sub(1);
+
+                let row = match (key, self.selected) {
+                    _ if rows == 0 => return,
+                    (key::Named::ArrowDown, Some(row)) => (row + 1).min(last),
+                    (key::Named::ArrowUp, Some(row)) => row.saturating_sub(1),
+                    (key::Named::ArrowDown | key::Named::Home, _) => 0,
+                    (key::Named::ArrowUp | key::Named::End, _) => last,
+                    _ => return,
+                };
+
+                shell.publish(on_select(row));
+                shell.capture_event();
+            }
+            _ => {}
+        }
     }
 
     fn draw(
@@ -495,66 +1223,169 @@ where
         cursor: mouse::Cursor,
         viewport: &Rectangle,
     ) {
-        for ((cell, state), layout) in
-            self.cells.iter().zip(&tree.children).zip(layout.children())
-        {
-            cell.as_widget()
-                .draw(state, renderer, theme, style, layout, cursor, viewport);
-        }
-
+        let state = tree.state.downcast_ref::<State>();
+        let metrics = &state.metrics;
         let bounds = layout.bounds();
-        let metrics = tree.state.downcast_ref::<Metrics>();
+        let columns = self.columns.len();
+        let defaults = style;
         let style = theme.style(&self.class);
 
-        if self.separator_x > 0.0 {
-            let mut x = self.padding_x;
-
-            for width in
-                &metrics.columns[..metrics.columns.len().saturating_sub(1)]
-            {
-                x += width + self.padding_x;
-
+        let offset = self.header_offset(metrics, bounds, viewport);
+        let header = Rectangle {
+            y: bounds.y + offset,
+            height: self.header_height(metrics),
+            ..bounds
+        };
+
+        let row_bounds = |row: usize| Rectangle {
+            x: bounds.x,
+            y: bounds.y + self.row_y(metrics, row) - self.padding_y,
+            width: bounds.width,
+            height: metrics.rows[row] + self.padding_y * 2.0,
+        };
+
+        let hovered = cursor
+            .position_over(bounds)
+            .filter(|_| {
+                self.on_select.is_some()
+                    && state.resizing.is_none()
+                    && !cursor.is_over(header)
+            })
+            .and_then(|position| self.row_at(metrics, position.y - bounds.y))
+            .filter(|row| *row > 0);
+
+        for (row, background) in [
+            (hovered, style.hovered_row),
+            (self.selected_row(metrics), style.selected_row),
+        ] {
+            if let Some(row) = row.filter(|row| *row < metrics.rows.len()) {
                 renderer.fill_quad(
                     renderer::Quad {
-                        bounds: Rectangle {
-                            x: bounds.x + x,
-                            y: bounds.y,
-                            width: self.separator_x,
-                            height: bounds.height,
-                        },
+                        bounds: row_bounds(row),
                         snap: true,
                         ..renderer::Quad::default()
                     },
-                    style.separator_x,
+                    background,
                 );
-
-                x += self.separator_x + self.padding_x;
             }
         }
 
-        if self.separator_y > 0.0 {
-            let mut y = self.padding_y;
+        for ((cell, state), layout) in self
+            .cells
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .skip(columns)
+        {
+            cell.as_widget().draw(
+                state,
+                renderer,
+                theme,
+                defaults,
+                layout,
+                if cursor.is_over(header) {
+                    cursor.levitate()
+                } else {
+                    cursor
+                },
+                viewport,
+            );
+        }
 
-            for height in &metrics.rows[..metrics.rows.len().saturating_sub(1)]
-            {
-                y += height + self.padding_y;
+        self.draw_separators(renderer, metrics, &style, bounds);
+
+        let draw_header = |renderer: &mut Renderer| {
+            if offset > 0.0 {
+                let header = Rectangle {
+                    y: bounds.y,
+                    ..header
+                };
+
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: header,
+                        snap: true,
+                        ..renderer::Quad::default()
+                    },
+                    style.header,
+                );
+
+                self.draw_separators(
+                    renderer,
+                    &Metrics {
+                        columns: metrics.columns.clone(),
+                        rows: metrics.rows[..1].to_vec(),
+                        first: 0,
+                        offset: 0.0,
+                    },
+                    &style,
+                    header,
+                );
 
                 renderer.fill_quad(
                     renderer::Quad {
                         bounds: Rectangle {
-                            x: bounds.x,
-                            y: bounds.y + y,
-                            width: bounds.width,
+                            y: header.y + header.height - self.separator_y,
                             height: self.separator_y,
+                            ..header
                         },
                         snap: true,
                         ..renderer::Quad::default()
                     },
                     style.separator_y,
                 );
+            }
 
-                y += self.separator_y + self.padding_y;
+            for ((cell, state), layout) in self
+                .cells
+                .iter()
+                .zip(&tree.children)
+                .zip(layout.children())
+                .take(columns)
+            {
+                cell.as_widget().draw(
+                    state,
+                    renderer,
+                    theme,
+                    defaults,
+                    layout,
+                    cursor + Vector::new(0.0, -offset),
+                    viewport,
+                );
             }
+
+            for (column, sort) in self
+                .columns
+                .iter()
+                .enumerate()
+                .filter_map(|(i, column)| Some((i, column.sort?)))
+            {
+                let Some(height) = metrics.rows.first() else {
+                    break;
+                };
+
+                draw_indicator(
+                    renderer,
+                    sort,
+                    Point::new(
+                        bounds.x
+                            + self.column_x(metrics, column)
+                            + metrics.columns[column]
+                            + 1.0,
+                        bounds.y + self.padding_y + height / 2.0,
+                    ),
+                    style.indicator,
+                );
+            }
+        };
+
+        if offset > 0.0 {
+            renderer.with_layer(header, |renderer| {
+                renderer
+                    .with_translation(Vector::new(0.0, offset), draw_header);
+            });
+        } else {
+            draw_header(renderer);
         }
     }
 
@@ -566,17 +1397,63 @@ where
         viewport: &Rectangle,
         renderer: &Renderer,
     ) -> mouse::Interaction {
-        self.cells
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+
+        if state.resizing.is_some() {
+            return mouse::Interaction::ResizingHorizontally;
+        }
+
+        let offset = self.header_offset(&state.metrics, bounds, viewport);
+        let header = Rectangle {
+            y: bounds.y + offset,
+            height: self.header_height(&state.metrics),
+            ..bounds
+        };
+
+        if let Some(position) = cursor.position_over(header) {
+            let x = position.x - bounds.x;
+
+            if self.divider_at(&state.metrics, x).is_some() {
+                return mouse::Interaction::ResizingHorizontally;
+            }
+        }
+
+        let interaction = self
+            .cells
             .iter()
             .zip(&tree.children)
             .zip(layout.children())
-            .map(|((cell, state), layout)| {
+            .enumerate()
+            .map(|(i, ((cell, state), layout))| {
+                let cursor = if i < self.columns.len() {
+                    cursor + Vector::new(0.0, -offset)
+                } else if cursor.is_over(header) {
+                    cursor.levitate()
+                } else {
+                    cursor
+                };
+
                 cell.as_widget().mouse_interaction(
                     state, layout, cursor, viewport, renderer,
                 )
             })
             .max()
-            .unwrap_or_default()
+            .unwrap_or_default();
+
+        if interaction != mouse::Interaction::None {
+            return interaction;
+        }
+
+        if let Some(position) = cursor.position_over(header)
+            && let Some(column) =
+                self.column_at(&state.metrics, position.x - bounds.x)
+            && self.columns[column].on_sort.is_some()
+        {
+            return mouse::Interaction::Pointer;
+        }
+
+        interaction
     }
 
     fn operate(
@@ -586,6 +1463,14 @@ where
         renderer: &Renderer,
         operation: &mut dyn widget::Operation,
     ) {
+        let state = tree.state.downcast_mut::<State>();
+
+        operation.custom(self.id.as_ref(), layout.bounds(), state);
+
+        if self.on_select.is_some() {
+            operation.focusable(self.id.as_ref(), layout.bounds(), state);
+        }
+
         for ((cell, state), layout) in self
             .cells
             .iter_mut()
@@ -628,6 +1513,142 @@ where
     }
 }
 
+/// Scrolls the closest scrollable around the [`Table`] with the given
+/// [`widget::Id`] just enough to show the row at the given index.
+pub fn scroll_to<T>(
+    id: impl Into<widget::Id>,
+    row: usize,
+) -> impl operation::Operation<T> {
+    struct Find {
+        target: widget::Id,
+        row: usize,
+        scrollables: usize,
+        pending: Option<(usize, Rectangle)>,
+        ancestors: Vec<(usize, Rectangle)>,
+        found: Option<Reveal>,
+    }
+
+    impl<T> operation::Operation<T> for Find {
+        fn traverse(
+            &mut self,
+            operate: &mut dyn FnMut(&mut dyn operation::Operation<T>),
+        ) {
+            let pending = self.pending.take();
+
+            if let Some(scrollable) = pending {
+                self.ancestors.push(scrollable);
+            }
+
+            operate(self);
+
+            if pending.is_some() {
+                let _ = self.ancestors.pop();
+            }
+        }
+
+        fn scrollable(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            content_bounds: Rectangle,
+            _translation: Vector,
+            _state: &mut dyn operation::Scrollable,
+        ) {
+            self.pending = Some((self.scrollables, content_bounds));
+            self.scrollables += 1;
+        }
+
+        fn custom(
+            &mut self,
+            id: Option<&widget::Id>,
+            bounds: Rectangle,
+            state: &mut dyn Any,
+        ) {
+            if self.found.is_some() || id != Some(&self.target) {
+                return;
+            }
+
+            let Some(state) = state.downcast_ref::<State>() else {
+                return;
+            };
+
+            if let Some((scrollable, content_bounds)) = self.ancestors.last()
+                && let Some((top, bottom)) = state.row_span(self.row)
+            {
+                let y = bounds.y - content_bounds.y;
+
+                self.found = Some(Reveal {
+                    target: *scrollable,
+                    top: y + top,
+                    bottom: y + bottom,
+                    header: state.sticky_height(),
+                    scrollables: 0,
+                });
+            }
+        }
+
+        fn finish(&self) -> operation::Outcome<T> {
+            match self.found {
+                Some(reveal) => operation::Outcome::Chain(Box::new(reveal)),
+                None => operation::Outcome::None,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    struct Reveal {
+        target: usize,
+        top: f32,
+        bottom: f32,
+        header: f32,
+        scrollables: usize,
+    }
+
+    impl<T> operation::Operation<T> for Reveal {
+        fn traverse(
+            &mut self,
+            operate: &mut dyn FnMut(&mut dyn operation::Operation<T>),
+        ) {
+            operate(self);
+        }
+
+        fn scrollable(
+            &mut self,
+            _id: Option<&widget::Id>,
+            bounds: Rectangle,
+            _content_bounds: Rectangle,
+            translation: Vector,
+            state: &mut dyn operation::Scrollable,
+        ) {
+            if self.scrollables == self.target {
+                let y = if self.top < translation.y + self.header {
+                    self.top - self.header
+                } else if self.bottom > translation.y + bounds.height {
+                    self.bottom - bounds.height
+                } else {
+                    translation.y
+                };
+
+                state.scroll_to(AbsoluteOffset {
+                    x: translation.x,
+                    y: y.max(0.0),
+                });
+            }
+
+            self.scrollables += 1;
+        }
+    }
+
+    Find {
+        target: id.into(),
+        row,
+        scrollables: 0,
+        pending: None,
+        ancestors: Vec::new(),
+        found: None,
+    }
+}
+
 /// A vertical visualization of some data with a header.
 pub struct Column<
     'a,
@@ -638,12 +1659,19 @@ pub struct Column<
     Renderer = crate::Renderer,
 > {
     header: Element<'a, Message, Theme, Renderer>,
-    view: Box<dyn Fn(T) -> Element<'a, Message, Theme, Renderer> + 'b>,
+    view: View<'a, 'b, T, Message, Theme, Renderer>,
     width: Length,
     align_x: alignment::Horizontal,
     align_y: alignment::Vertical,
+    sort: Option<Sort>,
+    on_sort: Option<Box<dyn Fn(Sort) -> Message + 'a>>,
+    on_resize: Option<Box<dyn Fn(f32) -> Message + 'a>>,
 }
 
+/// The view function of a [`Column`].
+type View<'a, 'b, T, Message, Theme, Renderer> =
+    Box<dyn Fn(T) -> Element<'a, Message, Theme, Renderer> + 'b>;
+
 impl<'a, 'b, T, Message, Theme, Renderer>
     Column<'a, 'b, T, Message, Theme, Renderer>
 {
@@ -670,6 +1698,56 @@ impl<'a, 'b, T, Message, Theme, Renderer>
         self.align_y = alignment.into();
         self
     }
+
+    /// Sets the current [`Sort`] of the [`Column`], shown next to its
+    /// header.
+    pub fn sorted(mut self, sort: impl Into<Option<Sort>>) -> Self {
+        self.sort = sort.into();
+        self
+    }
+
+    /// Sets the message that should be produced when the header of the
+    /// [`Column`] is clicked.
+    ///
+    /// The closure will receive the [`Sort`] that should be applied next;
+    /// which is the reverse of the current one, or ascending if the
+    /// [`Column`] is not sorted.
+    pub fn on_sort(mut self, on_sort: impl Fn(Sort) -> Message + 'a) -> Self {
+        self.on_sort = Some(Box::new(on_sort));
+        self
+    }
+
+    /// Sets the message that should be produced when the divider at the
+    /// right of the [`Column`] is dragged.
+    ///
+    /// The closure will receive the new width of the [`Column`]; which
+    /// should be applied with [`Column::width`].
+    pub fn on_resize(
+        mut self,
+        on_resize: impl Fn(f32) -> Message + 'a,
+    ) -> Self {
+        self.on_resize = Some(Box::new(on_resize));
+        self
+    }
+}
+
+/// The direction in which a [`Column`] is sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sort {
+    /// From the lowest to the highest value.
+    Ascending,
+    /// From the highest to the lowest value.
+    Descending,
+}
+
+impl Sort {
+    /// Returns the opposite [`Sort`].
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
 }
 
 /// The appearance of a [`Table`].
@@ -679,6 +1757,15 @@ pub struct Style {
     pub separator_x: Background,
     /// The background color of the vertical line separator between cells.
     pub separator_y: Background,
+    /// The background of the header, when it sticks to the top.
+    pub header: Background,
+    /// The background of the row under the cursor, when rows can be
+    /// selected.
+    pub hovered_row: Background,
+    /// The background of the selected row.
+    pub selected_row: Background,
+    /// The [`Color`] of the indicator of a sorted column.
+    pub indicator: Color,
 }
 
 /// The theme catalog of a [`Table`].
@@ -722,5 +1809,323 @@ pub fn default(theme: &crate::Theme) -> Style {
     Style {
         separator_x: separator,
         separator_y: separator,
+        header: palette.background.base.color.into(),
+        hovered_row: palette.background.weak.color.into(),
+        selected_row: palette.primary.weak.color.into(),
+        indicator: palette.background.base.text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Space;
+    use crate::core::clipboard;
+    use crate::core::keyboard::key::{NativeCode, Physical};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Sort(usize, Sort),
+        Resize(usize, f32),
+        Select(usize),
+    }
+
+    type Table = super::Table<'static, Message, crate::Theme, ()>;
+
+    fn cell() -> Element<'static, Message, crate::Theme, ()> {
+        Space::new().width(50).height(20).into()
+    }
+
+    fn table(rows: usize, sort: Option<Sort>) -> Table {
+        let columns = (0..2).map(|i| {
+            column(cell(), |_row: usize| cell())
+                .sorted(sort.filter(|_| i == 1))
+                .on_sort(move |sort| Message::Sort(i, sort))
+                .on_resize(move |width| Message::Resize(i, width))
+        });
+
+        super::table(columns, 0..rows).on_select(Message::Select)
+    }
+
+    struct Harness {
+        table: Table,
+        tree: widget::Tree,
+        node: layout::Node,
+        viewport: Rectangle,
+        cursor: Point,
+    }
+
+    impl Harness {
+        fn new(table: Table) -> Self {
+            let tree = widget::Tree::new(
+                &table as &dyn Widget<Message, crate::Theme, ()>,
+            );
+
+            let mut harness = Self {
+                table,
+                tree,
+                node: layout::Node::default(),
+                viewport: Rectangle::new(
+                    Point::ORIGIN,
+                    Size::new(400.0, 400.0),
+                ),
+                cursor: Point::ORIGIN,
+            };
+
+            harness.layout();
+            harness
+        }
+
+        fn layout(&mut self) {
+            self.node = self.table.layout(
+                &mut self.tree,
+                &(),
+                &layout::Limits::new(
+                    Size::ZERO,
+                    Size::new(400.0, f32::INFINITY),
+                ),
+            );
+        }
+
+        fn state(&self) -> &State {
+            self.tree.state.downcast_ref::<State>()
+        }
+
+        fn send(&mut self, event: core::Event, captured: bool) -> Vec<Message> {
+            let mut messages = Vec::new();
+            let mut shell = core::Shell::new(&mut messages);
+
+            if captured {
+                shell.capture_event();
+            }
+
+            self.table.update(
+                &mut self.tree,
+                &event,
+                Layout::new(&self.node),
+                mouse::Cursor::Available(self.cursor),
+                &(),
+                &mut clipboard::Null,
+                &mut shell,
+                &self.viewport,
+            );
+
+            messages
+        }
+
+        fn move_to(&mut self, x: f32, y: f32) -> Vec<Message> {
+            self.cursor = Point::new(x, y);
+
+            self.send(
+                core::Event::Mouse(mouse::Event::CursorMoved {
+                    position: self.cursor,
+                }),
+                false,
+            )
+        }
+
+        fn click(&mut self, x: f32, y: f32) -> Vec<Message> {
+            let _ = self.move_to(x, y);
+
+            self.send(
+                core::Event::Mouse(mouse::Event::ButtonPressed(
+                    mouse::Button::Left,
+                )),
+                false,
+            )
+        }
+
+        fn release(&mut self) -> Vec<Message> {
+            self.send(
+                core::Event::Mouse(mouse::Event::ButtonReleased(
+                    mouse::Button::Left,
+                )),
+                false,
+            )
+        }
+
+        fn press(&mut self, key: key::Named) -> Vec<Message> {
+            self.send(
+                core::Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(key),
+                    modified_key: keyboard::Key::Named(key),
+                    physical_key: Physical::Unidentified(
+                        NativeCode::Unidentified,
+                    ),
+                    location: keyboard::Location::Standard,
+                    modifiers: keyboard::Modifiers::default(),
+                    text: None,
+                }),
+                false,
+            )
+        }
+    }
+
+    fn virtualized(count: usize) -> Table {
+        let columns = (0..2).map(|i| {
+            column(cell(), |_row: usize| cell())
+                .on_sort(move |sort| Message::Sort(i, sort))
+        });
+
+        Table::virtualized(columns, count, 20.0).on_select(Message::Select)
+    }
+
+    // Rows are 20 tall, with 5 of padding on each side and a separator of 1
+    const ROW: f32 = 31.0;
+
+    #[test]
+    fn sorting_and_resizing() {
+        let mut harness = Harness::new(table(3, Some(Sort::Ascending)));
+        let metrics = &harness.state().metrics;
+
+        let first = harness.table.column_x(metrics, 0) + metrics.columns[0];
+        let second = harness.table.column_x(metrics, 1) + 10.0;
+        let divider = first + 10.5;
+        let width = metrics.columns[0];
+
+        assert_eq!(
+            harness.click(second, 10.0),
+            [Message::Sort(1, Sort::Descending)]
+        );
+        assert_eq!(
+            harness.click(5.0, 10.0),
+            [Message::Sort(0, Sort::Ascending)]
+        );
+
+        assert!(harness.click(divider + 2.0, 10.0).is_empty());
+        assert_eq!(
+            harness.move_to(divider + 32.0, 10.0),
+            [Message::Resize(0, width + 30.0)]
+        );
+        assert_eq!(
+            harness.move_to(-500.0, 10.0),
+            [Message::Resize(0, MIN_COLUMN_WIDTH)]
+        );
+
+        assert!(harness.release().is_empty());
+        assert!(harness.move_to(divider + 50.0, 10.0).is_empty());
+    }
+
+    #[test]
+    fn selecting_rows() {
+        let mut harness = Harness::new(table(4, None));
+
+        assert_eq!(harness.click(20.0, ROW * 3.0 + 10.0), [Message::Select(2)]);
+        assert!(harness.state().is_focused);
+
+        harness.table.selected = Some(2);
+
+        assert_eq!(harness.press(key::Named::ArrowDown), [Message::Select(3)]);
+        assert_eq!(harness.press(key::Named::ArrowUp), [Message::Select(1)]);
+        assert_eq!(harness.press(key::Named::Home), [Message::Select(0)]);
+        assert_eq!(harness.press(key::Named::End), [Message::Select(3)]);
+
+        harness.table.selected = Some(3);
+
+        assert_eq!(harness.press(key::Named::ArrowDown), [Message::Select(3)]);
+
+        // Another widget captures a click outside of the table
+        harness.cursor = Point::new(20.0, 1000.0);
+
+        let _ = harness.send(
+            core::Event::Mouse(mouse::Event::ButtonPressed(
+                mouse::Button::Left,
+            )),
+            true,
+        );
+
+        assert!(!harness.state().is_focused);
+        assert!(harness.press(key::Named::ArrowDown).is_empty());
+    }
+
+    #[test]
+    fn sticky_header() {
+        let mut harness = Harness::new(table(20, None).sticky_header(true));
+        let bounds = Layout::new(&harness.node).bounds();
+        let metrics = &harness.state().metrics;
+
+        let offset = |y: f32| {
+            harness.table.header_offset(
+                metrics,
+                bounds,
+                &Rectangle {
+                    y,
+                    ..harness.viewport
+                },
+            )
+        };
+
+        assert_eq!(offset(0.0), 0.0);
+        assert_eq!(offset(100.0), 100.0);
+        assert_eq!(offset(10_000.0), bounds.height - ROW);
+
+        // The header covers the rows under it
+        harness.viewport.y = ROW * 5.0;
+
+        assert_eq!(
+            harness.click(5.0, ROW * 5.0 + 10.0),
+            [Message::Sort(0, Sort::Ascending)]
+        );
+        assert_eq!(harness.click(5.0, ROW * 6.0 + 10.0), [Message::Select(5)]);
+
+        assert_eq!(harness.state().sticky_height(), ROW);
+        assert_eq!(
+            harness.state().row_span(5),
+            Some((ROW * 6.0, ROW * 7.0 - 1.0))
+        );
+        assert_eq!(harness.state().row_span(20), None);
+    }
+
+    #[test]
+    fn virtualized_rows() {
+        let mut harness = Harness::new(virtualized(100_000));
+
+        // Nothing is built until the table is in view
+        assert_eq!(harness.table.cells.len(), 2);
+        assert_eq!(harness.node.size().height, ROW * 100_001.0 - 1.0);
+
+        harness.viewport.y = ROW * 1_000.0;
+
+        let _ = harness.move_to(20.0, harness.viewport.y + 50.0);
+        harness.layout();
+
+        let metrics = &harness.state().metrics;
+
+        assert_eq!(metrics.first, 999);
+        assert_eq!(metrics.offset, ROW * 999.0);
+        assert_eq!(harness.table.cells.len(), 2 + 13 * 2);
+        assert_eq!(harness.state().keys.first(), Some(&(999, 0)));
+        assert_eq!(harness.state().keys.last(), Some(&(1011, 1)));
+
+        assert_eq!(
+            harness.click(20.0, ROW * 1_001.0 + 10.0),
+            [Message::Select(1_000)]
+        );
+        assert_eq!(harness.press(key::Named::End), [Message::Select(99_999)]);
+
+        let selected_row = |harness: &mut Harness, row| {
+            harness.table.selected = Some(row);
+            harness.table.selected_row(&harness.state().metrics)
+        };
+
+        assert_eq!(selected_row(&mut harness, 1_000), Some(2));
+        assert_eq!(selected_row(&mut harness, 5), None);
+        assert_eq!(selected_row(&mut harness, 99_999), None);
+
+        assert_eq!(
+            harness.state().row_span(50_000),
+            Some((ROW * 50_001.0, ROW * 50_002.0 - 1.0))
+        );
+        assert_eq!(harness.state().row_span(100_000), None);
+
+        // Scrolling keeps the trees of the rows still in view
+        harness.viewport.y += ROW * 2.0;
+
+        let _ = harness.move_to(20.0, harness.viewport.y + 50.0);
+        harness.layout();
+
+        assert_eq!(harness.state().metrics.first, 1_001);
+        assert_eq!(harness.state().keys.first(), Some(&(1_001, 0)));
+        assert_eq!(harness.tree.children.len(), harness.table.cells.len());
     }
 }
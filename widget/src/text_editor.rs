@@ -31,17 +31,31 @@
 //!     }
 //! }
 //! ```
+mod decoration;
+mod gutter;
+mod search;
+
+pub use decoration::{Decoration, Underline};
+pub use gutter::{LineNumbers, Marker};
+pub use search::{Match, Query};
+
+use decoration::Change;
+use gutter::Gutter;
+use search::Matcher;
+
 use crate::core::alignment;
+use crate::core::border;
 use crate::core::clipboard::{self, Clipboard};
 use crate::core::input_method;
 use crate::core::keyboard;
 use crate::core::keyboard::key;
 use crate::core::layout::{self, Layout};
 use crate::core::mouse;
+use crate::core::overlay;
 use crate::core::renderer;
-use crate::core::text::editor::{Cursor, Editor as _};
+use crate::core::text::editor::{Cursor, Direction, Editor as _};
 use crate::core::text::highlighter::{self, Highlighter};
-use crate::core::text::{self, LineHeight, Text, Wrapping};
+use crate::core::text::{self, LineHeight, Paragraph as _, Text, Wrapping};
 use crate::core::time::{Duration, Instant};
 use crate::core::widget::operation;
 use crate::core::widget::{self, Widget};
@@ -53,7 +67,10 @@ use crate::core::{
 
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
 use std::fmt;
+use std::iter;
 use std::ops::DerefMut;
 use std::ops::Range;
 use std::sync::Arc;
@@ -124,6 +141,11 @@ pub struct TextEditor<
         &Highlighter::Highlight,
         &Theme,
     ) -> highlighter::Format<Renderer::Font>,
+    line_numbers: Option<LineNumbers>,
+    marker: Option<Box<dyn Fn(usize) -> Option<Marker> + 'a>>,
+    on_gutter_click: Option<Box<dyn Fn(usize) -> Message + 'a>>,
+    folding: Option<Vec<Range<usize>>>,
+    highlight_current_line: bool,
     last_status: Option<Status>,
 }
 
@@ -155,6 +177,11 @@ where
             highlighter_format: |_highlight, _theme| {
                 highlighter::Format::default()
             },
+            line_numbers: None,
+            marker: None,
+            on_gutter_click: None,
+            folding: None,
+            highlight_current_line: false,
             last_status: None,
         }
     }
@@ -300,10 +327,64 @@ where
             on_edit: self.on_edit,
             highlighter_settings: settings,
             highlighter_format: to_format,
+            line_numbers: self.line_numbers,
+            marker: self.marker,
+            on_gutter_click: self.on_gutter_click,
+            folding: self.folding,
+            highlight_current_line: self.highlight_current_line,
             last_status: self.last_status,
         }
     }
 
+    /// Shows the given kind of [`LineNumbers`] in the gutter of the
+    /// [`TextEditor`].
+    pub fn line_numbers(mut self, line_numbers: LineNumbers) -> Self {
+        self.line_numbers = Some(line_numbers);
+        self
+    }
+
+    /// Sets the closure that produces the [`Marker`] of every line in the
+    /// gutter of the [`TextEditor`]; like breakpoints or diagnostics.
+    pub fn marker(
+        mut self,
+        marker: impl Fn(usize) -> Option<Marker> + 'a,
+    ) -> Self {
+        self.marker = Some(Box::new(marker));
+        self
+    }
+
+    /// Sets the message that should be produced when the gutter of the
+    /// [`TextEditor`] is clicked next to the line with the given index.
+    pub fn on_gutter_click(
+        mut self,
+        on_gutter_click: impl Fn(usize) -> Message + 'a,
+    ) -> Self {
+        self.on_gutter_click = Some(Box::new(on_gutter_click));
+        self
+    }
+
+    /// Enables code folding in the [`TextEditor`] for the given ranges of
+    /// lines, along with the ones found by its [`Highlighter`].
+    ///
+    /// The first line of every range stays visible when folded, while the
+    /// rest are hidden; see [`Action::Fold`].
+    ///
+    /// [`Highlighter`]: text::Highlighter
+    pub fn folding(
+        mut self,
+        ranges: impl IntoIterator<Item = Range<usize>>,
+    ) -> Self {
+        self.folding = Some(ranges.into_iter().collect());
+        self
+    }
+
+    /// Sets whether the line with the cursor should be highlighted in the
+    /// [`TextEditor`].
+    pub fn highlight_current_line(mut self, highlight: bool) -> Self {
+        self.highlight_current_line = highlight;
+        self
+    }
+
     /// Sets the closure to produce key bindings on key presses.
     ///
     /// See [`Binding`] for the list of available bindings.
@@ -350,7 +431,7 @@ where
         let bounds = layout.bounds();
         let internal = self.content.0.borrow_mut();
 
-        let text_bounds = bounds.shrink(self.padding);
+        let text_bounds = bounds.shrink(self.text_padding(state.gutter));
         let translation = text_bounds.position() - Point::ORIGIN;
 
         let cursor = match internal.editor.cursor() {
@@ -373,6 +454,236 @@ where
             preedit: state.preedit.as_ref().map(input_method::Preedit::as_ref),
         }
     }
+
+    /// Draws the gutter and the highlight of the current line.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_gutter(
+        &self,
+        state: &State<Highlighter>,
+        internal: &Internal<Renderer>,
+        renderer: &mut Renderer,
+        style: &Style,
+        bounds: Rectangle,
+        text_bounds: Rectangle,
+        font: Renderer::Font,
+    ) {
+        let gutter = state.gutter;
+        let text_size =
+            self.text_size.unwrap_or_else(|| renderer.default_size());
+        let line_height = f32::from(self.line_height.to_absolute(text_size));
+
+        let visible_lines = internal.editor.visible_lines();
+        let cursor_line = internal.editor.cursor_position().0;
+
+        // The gutter is clipped to the text vertically
+        let area = Rectangle {
+            y: text_bounds.y,
+            height: text_bounds.height,
+            ..bounds
+        };
+
+        if self.highlight_current_line
+            && let Some((_, line)) =
+                visible_lines.iter().find(|(line, _)| *line == cursor_line)
+            && let Some(highlight) = area.intersection(&Rectangle {
+                x: bounds.x + gutter.width(),
+                y: text_bounds.y + line.y,
+                width: bounds.width - gutter.width(),
+                height: line.height,
+            })
+        {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: highlight,
+                    ..renderer::Quad::default()
+                },
+                style.current_line,
+            );
+        }
+
+        if gutter.width() == 0.0 {
+            return;
+        }
+
+        let area = Rectangle {
+            width: gutter.width(),
+            ..area
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    width: gutter.width(),
+                    ..bounds
+                }
+                .shrink(style.border.width),
+                ..renderer::Quad::default()
+            },
+            style.gutter,
+        );
+
+        let fold_ranges = self.fold_ranges(state);
+
+        let label = |content: String, x: f32, y: f32, align_x, color| {
+            (
+                Text {
+                    content,
+                    bounds: Size::new(
+                        gutter.numbers.max(gutter.folds),
+                        line_height,
+                    ),
+                    size: text_size,
+                    line_height: self.line_height,
+                    font,
+                    align_x,
+                    align_y: alignment::Vertical::Top,
+                    shaping: text::Shaping::Basic,
+                    wrapping: Wrapping::None,
+                },
+                Point::new(x, y),
+                color,
+            )
+        };
+
+        for (line, line_bounds) in visible_lines {
+            let y = text_bounds.y + line_bounds.y;
+
+            if y + line_bounds.height < area.y || y > area.y + area.height {
+                continue;
+            }
+
+            match self.marker.as_ref().and_then(|marker| marker(line)) {
+                Some(Marker::Dot(color)) => {
+                    let diameter = gutter.markers * 0.6;
+
+                    renderer.fill_quad(
+                        renderer::Quad {
+                            bounds: Rectangle {
+                                x: bounds.x + (gutter.markers - diameter) / 2.0,
+                                y: y + (line_height - diameter) / 2.0,
+                                width: diameter,
+                                height: diameter,
+                            },
+                            border: border::rounded(diameter / 2.0),
+                            ..renderer::Quad::default()
+                        },
+                        color,
+                    );
+                }
+                Some(Marker::Bar(color)) => {
+                    if let Some(bar) = area.intersection(&Rectangle {
+                        x: bounds.x + gutter.markers / 2.0 - 1.5,
+                        y,
+                        width: 3.0,
+                        height: line_bounds.height,
+                    }) {
+                        renderer.fill_quad(
+                            renderer::Quad {
+                                bounds: bar,
+                                ..renderer::Quad::default()
+                            },
+                            color,
+                        );
+                    }
+                }
+                None => {}
+            }
+
+            if let Some(line_numbers) = self.line_numbers {
+                let (text, position, color) = label(
+                    line_numbers.label(line, cursor_line),
+                    bounds.x + gutter.markers + gutter.numbers,
+                    y,
+                    text::Alignment::Right,
+                    if line == cursor_line {
+                        style.current_line_number
+                    } else {
+                        style.line_number
+                    },
+                );
+
+                renderer.fill_text(text, position, color, area);
+            }
+
+            let indicator =
+                if internal.folds.iter().any(|lines| lines.start == line) {
+                    Some("+")
+                } else if fold_ranges.iter().any(|lines| lines.start == line) {
+                    Some("-")
+                } else {
+                    None
+                };
+
+            if let Some(indicator) = indicator {
+                let (text, position, color) = label(
+                    indicator.to_owned(),
+                    bounds.x
+                        + gutter.markers
+                        + gutter.numbers
+                        + gutter.folds / 2.0,
+                    y,
+                    text::Alignment::Center,
+                    style.line_number,
+                );
+
+                renderer.fill_text(text, position, color, area);
+            }
+        }
+    }
+
+    /// Returns the [`Padding`] of the text, next to the given [`Gutter`].
+    fn text_padding(&self, gutter: Gutter) -> Padding {
+        Padding {
+            left: self.padding.left + gutter.width(),
+            ..self.padding
+        }
+    }
+
+    /// Returns the ranges of lines of the [`Content`] that can be folded.
+    fn fold_ranges(&self, state: &State<Highlighter>) -> Vec<Range<usize>> {
+        let Some(folding) = &self.folding else {
+            return Vec::new();
+        };
+
+        folding
+            .iter()
+            .cloned()
+            .chain(state.highlighter.borrow().fold_ranges())
+            .filter(|lines| lines.len() > 1)
+            .collect()
+    }
+
+    /// Returns the index and the bounds of the text of the [`Decoration`]
+    /// with a tooltip at the given position, if any.
+    fn hovered_decoration(
+        &self,
+        state: &State<Highlighter>,
+        bounds: Rectangle,
+        position: Point,
+    ) -> Option<(usize, Rectangle)> {
+        let text_bounds = bounds.shrink(self.text_padding(state.gutter));
+
+        if !text_bounds.contains(position) {
+            return None;
+        }
+
+        let translation = text_bounds.position() - Point::ORIGIN;
+        let internal = self.content.0.borrow();
+
+        internal
+            .decorations
+            .iter()
+            .enumerate()
+            .filter(|(_, decoration)| decoration.tooltip.is_some())
+            .find_map(|(i, decoration)| {
+                internal
+                    .decoration_bounds(decoration)
+                    .into_iter()
+                    .map(|bounds| bounds + translation)
+                    .find(|bounds| bounds.contains(position))
+                    .map(|bounds| (i, bounds))
+            })
+    }
 }
 
 /// The content of a [`TextEditor`].
@@ -385,6 +696,12 @@ where
     R: text::Renderer,
 {
     editor: R::Editor,
+    cursors: Vec<Selection>,
+    box_base: Option<Vec<Selection>>,
+    folds: Vec<Range<usize>>,
+    decorations: Vec<Decoration>,
+    history: History,
+    search: Option<Query>,
     is_dirty: bool,
 }
 
@@ -401,27 +718,216 @@ where
     pub fn with_text(text: &str) -> Self {
         Self(RefCell::new(Internal {
             editor: R::Editor::with_text(text),
+            cursors: Vec::new(),
+            box_base: None,
+            folds: Vec::new(),
+            decorations: Vec::new(),
+            history: History::new(History::DEFAULT_DEPTH),
+            search: None,
             is_dirty: true,
         }))
     }
 
     /// Performs an [`Action`] on the [`Content`].
+    ///
+    /// Edits are recorded in the history of the [`Content`]; so they can
+    /// be undone with [`Action::Undo`] and redone with [`Action::Redo`].
+    ///
+    /// Edits and motions are applied at every cursor of the [`Content`].
     pub fn perform(&mut self, action: Action) {
         let internal = self.0.get_mut();
 
-        internal.editor.perform(action);
+        if !matches!(action, Action::SelectBox { .. } | Action::Scroll { .. }) {
+            internal.box_base = None;
+        }
+
+        match action {
+            Action::Undo => internal.undo(),
+            Action::Redo => internal.redo(),
+            Action::Edit(edit) => internal.edit(edit),
+            Action::Move(
+                motion @ (Motion::NextMatch | Motion::PreviousMatch),
+            ) => {
+                internal.history.group = None;
+                internal.cursors.clear();
+                internal.jump(motion, false);
+            }
+            Action::Select(
+                motion @ (Motion::NextMatch | Motion::PreviousMatch),
+            ) => {
+                internal.history.group = None;
+                internal.cursors.clear();
+                internal.jump(motion, true);
+            }
+            Action::Move(_)
+            | Action::Select(_)
+            | Action::SelectWord
+            | Action::SelectLine => {
+                internal.history.group = None;
+                let _ = internal.perform_all(&action);
+            }
+            Action::AddCursor(point) => {
+                internal.history.group = None;
+                internal.add_cursor(point);
+            }
+            Action::ClearCursors => {
+                internal.history.group = None;
+                internal.cursors.clear();
+            }
+            Action::SelectNextOccurrence => {
+                internal.history.group = None;
+                internal.select_next_occurrence();
+            }
+            Action::SelectBox { from, to } => {
+                internal.history.group = None;
+                internal.select_box(from, to);
+            }
+            Action::Scroll { .. } => internal.editor.perform(action),
+            Action::Fold(lines) => {
+                internal.history.group = None;
+                internal.fold(lines);
+            }
+            Action::Unfold(line) => {
+                internal.history.group = None;
+                internal.unfold_line(line);
+            }
+            Action::Click(_) | Action::Drag(_) | Action::SelectAll => {
+                internal.history.group = None;
+                internal.cursors.clear();
+                internal.editor.perform(action);
+            }
+        }
+
+        internal.is_dirty = true;
+    }
+
+    /// Sets the maximum amount of edits that can be undone in the
+    /// [`Content`].
+    ///
+    /// Consecutive edits of the same kind are grouped together; so a
+    /// single undo reverts a word typed at once. A depth of `0` disables
+    /// the history.
+    ///
+    /// By default, the last 100 groups of edits are kept.
+    pub fn set_history_depth(&mut self, depth: usize) {
+        self.0.get_mut().history.set_depth(depth);
+    }
+
+    /// Sets the active search [`Query`] of the [`Content`].
+    ///
+    /// The matches of the active search are highlighted in the
+    /// [`TextEditor`] and can be selected with [`Motion::NextMatch`]
+    /// and [`Motion::PreviousMatch`].
+    pub fn set_search(&mut self, query: Option<Query>) {
+        let internal = self.0.get_mut();
+
+        internal.search = query;
+        internal.is_dirty = true;
+    }
+
+    /// Returns the active search [`Query`] of the [`Content`], if any.
+    pub fn search(&self) -> Option<Query> {
+        self.0.borrow().search.clone()
+    }
+
+    /// Finds all the matches of the given [`Query`] in the [`Content`].
+    pub fn find(&self, query: &Query) -> Vec<Match> {
+        let Some(matcher) = Matcher::new(query) else {
+            return Vec::new();
+        };
+
+        let internal = self.0.borrow();
+
+        (0..internal.line_count())
+            .filter_map(|i| Some((i, internal.line(i)?)))
+            .flat_map(|(i, line)| {
+                matcher
+                    .find(&line.text)
+                    .into_iter()
+                    .map(move |range| Match { line: i, range })
+            })
+            .collect()
+    }
+
+    /// Finds all the matches of the active search in the [`Content`].
+    pub fn matches(&self) -> Vec<Match> {
+        self.search()
+            .map(|query| self.find(&query))
+            .unwrap_or_default()
+    }
+
+    /// Replaces the given [`Match`] in the [`Content`].
+    ///
+    /// If the active search is a regular expression, its captures are
+    /// expanded in the replacement; like `$1` or `${name}`.
+    pub fn replace(&mut self, target: &Match, replacement: &str) {
+        let internal = self.0.get_mut();
+        let matcher = internal.search.as_ref().and_then(Matcher::new);
+
+        internal.replace(
+            matcher.as_ref(),
+            std::slice::from_ref(target),
+            replacement,
+        );
+        internal.is_dirty = true;
+    }
+
+    /// Replaces all the matches of the active search in the [`Content`],
+    /// returning the amount of replaced matches.
+    ///
+    /// All the replacements are undone at once.
+    pub fn replace_all(&mut self, replacement: &str) -> usize {
+        let targets = self.matches();
+        let internal = self.0.get_mut();
+        let matcher = internal.search.as_ref().and_then(Matcher::new);
+
+        internal.replace(matcher.as_ref(), &targets, replacement);
+        internal.is_dirty = true;
+
+        targets.len()
+    }
+
+    /// Sets the [`Decoration`]s of the [`Content`]; like the diagnostics
+    /// of a language server.
+    ///
+    /// Decorations are kept in place as the [`Content`] is edited, until
+    /// they are replaced.
+    pub fn set_decorations(
+        &mut self,
+        decorations: impl IntoIterator<Item = Decoration>,
+    ) {
+        let internal = self.0.get_mut();
+
+        internal.decorations = decorations.into_iter().collect();
         internal.is_dirty = true;
     }
 
+    /// Returns the current [`Decoration`]s of the [`Content`].
+    pub fn decorations(&self) -> Vec<Decoration> {
+        self.0.borrow().decorations.clone()
+    }
+
+    /// Returns whether the [`Content`] has any edits to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.0.borrow().history.undo.is_empty()
+    }
+
+    /// Returns whether the [`Content`] has any undone edits to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.0.borrow().history.redo.is_empty()
+    }
+
     /// Returns the amount of lines of the [`Content`].
     pub fn line_count(&self) -> usize {
-        self.0.borrow().editor.line_count()
+        self.0.borrow().line_count()
     }
 
     /// Returns the text of the line at the given index, if it exists.
+    ///
+    /// Folded lines are included.
     pub fn line(&self, index: usize) -> Option<Line<'_>> {
         let internal = self.0.borrow();
-        let line = internal.editor.line(index)?;
+        let line = internal.line(index)?;
 
         Some(Line {
             text: Cow::Owned(line.text.into_owned()),
@@ -439,22 +945,7 @@ where
 
     /// Returns the text of the [`Content`].
     pub fn text(&self) -> String {
-        let mut contents = String::new();
-        let mut lines = self.lines().peekable();
-
-        while let Some(line) = lines.next() {
-            contents.push_str(&line.text);
-
-            if lines.peek().is_some() {
-                contents.push_str(if line.ending == LineEnding::None {
-                    LineEnding::default().as_str()
-                } else {
-                    line.ending.as_str()
-                });
-            }
-        }
-
-        contents
+        self.0.borrow().text()
     }
 
     /// Returns the kind of [`LineEnding`] used for separating lines in the [`Content`].
@@ -463,80 +954,1112 @@ where
     }
 
     /// Returns the selected text of the [`Content`].
+    ///
+    /// The selections of multiple cursors are joined in a line each.
     pub fn selection(&self) -> Option<String> {
-        self.0.borrow().editor.selection()
+        let internal = self.0.borrow();
+
+        if internal.cursors.is_empty() {
+            return internal.editor.selection();
+        }
+
+        let mut selections = internal.selections();
+
+        selections.sort_by_key(Selection::start);
+
+        let texts: Vec<String> = selections
+            .into_iter()
+            .filter(|selection| selection.anchor.is_some())
+            .map(|selection| {
+                internal.text_between(selection.start(), selection.end())
+            })
+            .collect();
+
+        (!texts.is_empty()).then(|| texts.join(LineEnding::default().as_str()))
     }
 
     /// Returns the current cursor position of the [`Content`].
+    ///
+    /// If the [`Content`] has multiple cursors, this is the position of
+    /// the last one added.
     pub fn cursor_position(&self) -> (usize, usize) {
-        self.0.borrow().editor.cursor_position()
+        let internal = self.0.borrow();
+
+        internal.selection().position
     }
-}
 
-impl<Renderer> Clone for Content<Renderer>
-where
-    Renderer: text::Renderer,
-{
-    fn clone(&self) -> Self {
-        Self::with_text(&self.text())
+    /// Returns the positions of all the cursors of the [`Content`],
+    /// starting with the one returned by [`cursor_position`].
+    ///
+    /// [`cursor_position`]: Self::cursor_position
+    pub fn cursor_positions(&self) -> Vec<(usize, usize)> {
+        let internal = self.0.borrow();
+
+        internal
+            .selections()
+            .into_iter()
+            .map(|selection| selection.position)
+            .collect()
     }
-}
 
-impl<Renderer> Default for Content<Renderer>
-where
-    Renderer: text::Renderer,
-{
-    fn default() -> Self {
-        Self::new()
+    /// Returns the ranges of lines that are currently folded in the
+    /// [`Content`].
+    ///
+    /// The first line of every range is visible, while the rest are hidden.
+    pub fn folds(&self) -> Vec<Range<usize>> {
+        self.0.borrow().folds.clone()
     }
 }
 
-impl<Renderer> fmt::Debug for Content<Renderer>
+impl<R> Internal<R>
 where
-    Renderer: text::Renderer,
-    Renderer::Editor: fmt::Debug,
+    R: text::Renderer,
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let internal = self.0.borrow();
+    fn line_count(&self) -> usize {
+        self.editor.line_count()
+    }
 
-        f.debug_struct("Content")
-            .field("editor", &internal.editor)
-            .field("is_dirty", &internal.is_dirty)
-            .finish()
+    fn line(&self, index: usize) -> Option<Line<'_>> {
+        self.editor.line(index)
     }
-}
 
-/// The state of a [`TextEditor`].
-#[derive(Debug)]
-pub struct State<Highlighter: text::Highlighter> {
-    focus: Option<Focus>,
-    preedit: Option<input_method::Preedit>,
-    last_click: Option<mouse::Click>,
-    drag_click: Option<mouse::click::Kind>,
-    partial_scroll: f32,
-    highlighter: RefCell<Highlighter>,
-    highlighter_settings: Highlighter::Settings,
-    highlighter_format_address: usize,
-}
+    /// Returns the index of the fold hiding the given line, if any.
+    fn fold_at(&self, line: usize) -> Option<usize> {
+        self.folds
+            .iter()
+            .position(|fold| line > fold.start && line < fold.end)
+    }
 
-#[derive(Debug, Clone)]
-struct Focus {
-    updated_at: Instant,
-    now: Instant,
-    is_window_focused: bool,
-}
+    fn line_length(&self, line: usize) -> usize {
+        self.editor.line(line).map_or(0, |line| line.text.len())
+    }
 
-impl Focus {
-    const CURSOR_BLINK_INTERVAL_MILLIS: u128 = 500;
+    fn text(&self) -> String {
+        let mut contents = String::new();
+        let mut lines = (0..)
+            .map(|i| self.line(i))
+            .take_while(Option::is_some)
+            .flatten()
+            .peekable();
 
-    fn now() -> Self {
-        let now = Instant::now();
+        while let Some(line) = lines.next() {
+            contents.push_str(&line.text);
 
-        Self {
-            updated_at: now,
-            now,
-            is_window_focused: true,
-        }
+            if lines.peek().is_some() {
+                contents.push_str(if line.ending == LineEnding::None {
+                    LineEnding::default().as_str()
+                } else {
+                    line.ending.as_str()
+                });
+            }
+        }
+
+        contents
+    }
+
+    /// Returns the given lines of the editor, with their endings.
+    fn editor_lines(&self, lines: Range<usize>) -> Vec<(String, LineEnding)> {
+        lines
+            .map_while(|i| self.editor.line(i))
+            .map(|line| (line.text.into_owned(), line.ending))
+            .collect()
+    }
+
+    /// Returns the bounds of the text of the given [`Decoration`] in the
+    /// editor, up to its first folded line.
+    fn decoration_bounds(&self, decoration: &Decoration) -> Vec<Rectangle> {
+        if self.fold_at(decoration.start.0).is_some() {
+            return Vec::new();
+        }
+
+        let end = match self.fold_at(decoration.end.0) {
+            Some(index) => {
+                let line = self.folds[index].start;
+
+                (line, self.line_length(line))
+            }
+            None => decoration.end,
+        };
+
+        match self.editor.cursor_at(end, Some(decoration.start)) {
+            Some(Cursor::Selection(bounds)) => bounds,
+            Some(Cursor::Caret(_)) | None => Vec::new(),
+        }
+    }
+
+    /// Moves the [`Decoration`]s along with the given [`Change`].
+    fn track(&mut self, change: &Change) {
+        self.decorations
+            .retain_mut(|decoration| change.apply(decoration));
+    }
+
+    fn text_between(
+        &self,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> String {
+        let mut text = String::new();
+
+        for index in start.0..=end.0 {
+            let Some(line) = self.line(index) else {
+                break;
+            };
+
+            let from = if index == start.0 { start.1 } else { 0 };
+            let to = if index == end.0 {
+                end.1
+            } else {
+                line.text.len()
+            };
+
+            text.push_str(line.text.get(from..to).unwrap_or_default());
+
+            if index != end.0 {
+                text.push_str(if line.ending == LineEnding::None {
+                    LineEnding::default().as_str()
+                } else {
+                    line.ending.as_str()
+                });
+            }
+        }
+
+        text
+    }
+
+    /// Returns the selection of the main cursor.
+    fn selection(&self) -> Selection {
+        Selection {
+            position: self.editor.cursor_position(),
+            anchor: self.editor.selection_anchor(),
+        }
+    }
+
+    /// Returns the selections of all the cursors, starting with the main
+    /// one.
+    fn selections(&self) -> Vec<Selection> {
+        iter::once(self.selection())
+            .chain(self.cursors.iter().copied())
+            .collect()
+    }
+
+    fn select(&mut self, selection: Selection) {
+        self.editor.set_cursor(selection.position, selection.anchor);
+    }
+
+    /// Performs the given [`Action`] at the main cursor, returning the
+    /// [`Revision`] of the text if the [`Action`] is an edit recorded in
+    /// the history.
+    ///
+    /// Edits that touch the lines hidden by a fold, or the end of the line
+    /// before them, unfold it first. Decorations are moved along with the
+    /// edited text.
+    fn perform_one(&mut self, action: Action) -> Option<Revision> {
+        let Action::Edit(edit) = &action else {
+            self.editor.perform(action);
+            return None;
+        };
+
+        let is_recording = self.history.depth > 0;
+
+        if !is_recording && self.folds.is_empty() && self.decorations.is_empty()
+        {
+            self.editor.perform(action);
+            return None;
+        }
+
+        let selection = self.selection();
+        let mut start = selection.start();
+        let mut end = selection.end();
+
+        if selection.anchor.is_none() {
+            match edit {
+                Edit::Backspace if start.1 == 0 && start.0 > 0 => {
+                    start = (start.0 - 1, self.line_length(start.0 - 1));
+                }
+                Edit::Delete if end.1 >= self.line_length(end.0) => {
+                    end = (end.0 + 1, 0);
+                }
+                _ => {}
+            }
+        }
+
+        while let Some(index) = self.folds.iter().position(|fold| {
+            let last = fold.end - 1;
+
+            start <= (last, self.line_length(last))
+                && (fold.start, self.line_length(fold.start)) <= end
+        }) {
+            self.unfold(index);
+        }
+
+        let old = (is_recording || !self.decorations.is_empty())
+            .then(|| self.editor_lines(start.0..end.0 + 1));
+        let line_count = self.editor.line_count();
+
+        self.editor.perform(action);
+
+        let delta = self.editor.line_count() as isize - line_count as isize;
+
+        for fold in &mut self.folds {
+            if fold.start >= end.0 {
+                fold.start = fold.start.saturating_add_signed(delta);
+                fold.end = fold.end.saturating_add_signed(delta);
+            }
+        }
+
+        let old = old?;
+        let new =
+            self.editor_lines(start.0..end.0.saturating_add_signed(delta) + 1);
+        let change = Change::new(start.0, start, &old, &new);
+
+        self.track(&change);
+
+        change.replaced().filter(|_| is_recording).map(
+            |(start, end, removed)| Revision {
+                start,
+                end,
+                removed: removed.to_owned(),
+            },
+        )
+    }
+
+    /// Folds the given range of lines.
+    fn fold(&mut self, lines: Range<usize>) {
+        let end = lines.end.min(self.line_count());
+
+        if end <= lines.start + 1 {
+            return;
+        }
+
+        // Folds inside the range are merged into the new one
+        self.reveal(lines.start);
+
+        while let Some(index) = self
+            .folds
+            .iter()
+            .position(|fold| lines.contains(&fold.start))
+        {
+            self.unfold(index);
+        }
+
+        let line = lines.start;
+        let length = self.line_length(line);
+        let selections = self.selections();
+
+        self.editor.perform(Action::Fold(line..end));
+
+        let index = self.folds.partition_point(|fold| fold.start < line);
+        self.folds.insert(index, line..end);
+
+        // Cursors inside the fold move to its first line
+        self.restore_selections(selections, |(i, column)| {
+            if i > line && i < end {
+                (line, length)
+            } else {
+                (i, column)
+            }
+        });
+    }
+
+    /// Unfolds the fold at the given index.
+    fn unfold(&mut self, index: usize) {
+        let fold = self.folds.remove(index);
+
+        self.editor.perform(Action::Unfold(fold.start));
+    }
+
+    /// Unfolds the lines hidden by the fold at the given line, if any.
+    fn unfold_line(&mut self, line: usize) {
+        if let Some(index) =
+            self.folds.iter().position(|fold| fold.start == line)
+        {
+            self.unfold(index);
+        } else {
+            self.reveal(line);
+        }
+    }
+
+    /// Unfolds the given line if it is hidden.
+    fn reveal(&mut self, line: usize) {
+        if let Some(index) = self.fold_at(line) {
+            self.unfold(index);
+        }
+    }
+
+    /// Restores the given selections, moving their positions with the
+    /// given function.
+    fn restore_selections(
+        &mut self,
+        selections: Vec<Selection>,
+        f: impl Fn((usize, usize)) -> (usize, usize),
+    ) {
+        let mut selections = selections.into_iter().map(|selection| {
+            let position = f(selection.position);
+
+            Selection {
+                position,
+                anchor: selection
+                    .anchor
+                    .map(&f)
+                    .filter(|anchor| *anchor != position),
+            }
+        });
+
+        if let Some(main) = selections.next() {
+            self.select(main);
+        }
+
+        self.cursors = selections.collect();
+        self.cursors.dedup_by_key(|cursor| cursor.position);
+    }
+
+    /// Performs the given [`Action`] at every cursor, returning the
+    /// [`Revision`]s of the text in the order they were made.
+    fn perform_all(&mut self, action: &Action) -> Vec<Revision> {
+        if self.cursors.is_empty() {
+            return self.perform_one(action.clone()).into_iter().collect();
+        }
+
+        let mut selections: Vec<(Selection, bool)> =
+            iter::once((self.selection(), true))
+                .chain(self.cursors.drain(..).map(|cursor| (cursor, false)))
+                .collect();
+
+        // Later cursors are processed first, so the positions of the
+        // earlier ones stay valid
+        selections.sort_by_key(|(selection, _)| Reverse(selection.start()));
+
+        // Positions are tracked from the end of the text, since edits at
+        // the earlier cursors do not change them
+        let mut results = Vec::with_capacity(selections.len());
+        let mut revisions = Vec::new();
+
+        for (selection, is_main) in selections {
+            self.select(selection);
+            revisions.extend(self.perform_one(action.clone()));
+
+            results.push((self.offset_from_end(self.selection()), is_main));
+        }
+
+        let mut main = None;
+
+        for (selection, is_main) in results {
+            let selection = self.offset_to_position(selection);
+
+            if is_main {
+                main = Some(selection);
+            } else {
+                self.cursors.push(selection);
+            }
+        }
+
+        self.cursors.sort_by_key(Selection::start);
+        self.cursors.dedup_by_key(|cursor| cursor.position);
+
+        if let Some(main) = main {
+            self.cursors
+                .retain(|cursor| cursor.position != main.position);
+            self.select(main);
+        }
+
+        revisions
+    }
+
+    /// Turns the positions of a [`Selection`] into offsets from the end of
+    /// the text.
+    fn offset_from_end(&self, selection: Selection) -> Selection {
+        let last_line = self.editor.line_count().saturating_sub(1);
+
+        let offset = |(line, column): (usize, usize)| {
+            let length =
+                self.editor.line(line).map_or(0, |line| line.text.len());
+
+            (
+                last_line.saturating_sub(line),
+                length.saturating_sub(column),
+            )
+        };
+
+        Selection {
+            position: offset(selection.position),
+            anchor: selection.anchor.map(offset),
+        }
+    }
+
+    /// Turns the offsets of a [`Selection`] computed with
+    /// [`Internal::offset_from_end`] back into positions.
+    fn offset_to_position(&self, selection: Selection) -> Selection {
+        let last_line = self.editor.line_count().saturating_sub(1);
+
+        let position = |(line, column): (usize, usize)| {
+            let line = last_line.saturating_sub(line);
+            let length =
+                self.editor.line(line).map_or(0, |line| line.text.len());
+
+            (line, length.saturating_sub(column))
+        };
+
+        Selection {
+            position: position(selection.position),
+            anchor: selection.anchor.map(position),
+        }
+    }
+
+    fn add_cursor(&mut self, point: Point) {
+        let main = self.selection();
+
+        self.editor.perform(Action::Click(point));
+
+        let position = self.editor.cursor_position();
+
+        // Adding a cursor where there is one already removes it
+        if let Some(i) = self
+            .cursors
+            .iter()
+            .position(|cursor| cursor.position == position)
+        {
+            let _ = self.cursors.remove(i);
+            self.select(main);
+        } else if position != main.position {
+            self.cursors.push(main);
+        }
+    }
+
+    fn select_next_occurrence(&mut self) {
+        let main = self.selection();
+
+        if main.anchor.is_none() {
+            self.editor.perform(Action::SelectWord);
+            return;
+        }
+
+        let selections = self.selections();
+
+        let text =
+            self.text_between(selections[0].start(), selections[0].end());
+
+        // Occurrences are searched line by line
+        if text.contains(['\n', '\r']) {
+            return;
+        }
+
+        let Some(matcher) =
+            Matcher::new(&Query::new(text).case_sensitive(true))
+        else {
+            return;
+        };
+
+        let mut position = selections[0].end();
+
+        // Every cursor may be skipped once before wrapping around
+        for _ in 0..=selections.len() {
+            let Some(found) = self.next_match(&matcher, position) else {
+                return;
+            };
+
+            let start = (found.line, found.range.start);
+            let end = (found.line, found.range.end);
+
+            let is_selected = selections
+                .iter()
+                .any(|cursor| cursor.start() == start && cursor.end() == end);
+
+            if !is_selected {
+                self.cursors.push(main);
+
+                self.reveal(found.line);
+                self.editor.set_cursor(end, Some(start));
+
+                return;
+            }
+
+            position = end;
+        }
+    }
+
+    fn select_box(&mut self, from: Point, to: Point) {
+        let base = self
+            .box_base
+            .get_or_insert_with(|| self.cursors.clone())
+            .clone();
+
+        // Boxes cannot be mapped to lines that are not laid out
+        let (Some(start), Some(end)) =
+            (self.line_at(from.y), self.line_at(to.y))
+        else {
+            return;
+        };
+
+        let start_column =
+            self.char_column((start, self.index_at(start, from.x, 0)));
+        let end_column = self.char_column((end, self.index_at(end, to.x, 0)));
+
+        let lines: Vec<usize> = if start <= end {
+            (start..=end).collect()
+        } else {
+            (end..=start).rev().collect()
+        };
+
+        let mut selections = Vec::with_capacity(lines.len());
+
+        // Folded lines are skipped
+        for line in lines
+            .into_iter()
+            .filter(|line| self.fold_at(*line).is_none())
+        {
+            let anchor = self.index_at(line, from.x, start_column);
+            let position = self.index_at(line, to.x, end_column);
+
+            selections.push(Selection {
+                position: (line, position),
+                anchor: (anchor != position).then_some((line, anchor)),
+            });
+        }
+
+        // The line where the box ends holds the main cursor
+        let Some(main) = selections.pop() else {
+            return;
+        };
+
+        self.cursors = base;
+        self.cursors.extend(selections);
+        self.cursors
+            .retain(|cursor| cursor.position != main.position);
+        self.select(main);
+    }
+
+    /// Returns the amount of characters before the given position in its
+    /// line.
+    fn char_column(&self, (line, index): (usize, usize)) -> usize {
+        self.editor.line(line).map_or(0, |line| {
+            line.text.get(..index).unwrap_or_default().chars().count()
+        })
+    }
+
+    /// Returns the visible line at the given vertical position; or the
+    /// closest one, if there is none.
+    ///
+    /// Returns `None` if no lines are visible.
+    fn line_at(&self, y: f32) -> Option<usize> {
+        let lines = self.editor.visible_lines();
+
+        lines
+            .iter()
+            .find(|(_, bounds)| y < bounds.y + bounds.height)
+            .or(lines.last())
+            .map(|(line, _)| *line)
+    }
+
+    /// Returns the byte index closest to the given horizontal position in
+    /// a line; falling back to the given character column if the line is
+    /// not laid out.
+    fn index_at(&self, line: usize, x: f32, column: usize) -> usize {
+        let Some(text) = self.editor.line(line).map(|line| line.text) else {
+            return 0;
+        };
+
+        let caret = |index| match self.editor.cursor_at((line, index), None) {
+            Some(Cursor::Caret(position)) => Some(position),
+            Some(Cursor::Selection(_)) | None => None,
+        };
+
+        // Only the first visual line of a wrapped line is considered
+        if let Some(start) = caret(0)
+            && let Some((index, _)) = text
+                .char_indices()
+                .map(|(index, _)| index)
+                .chain(iter::once(text.len()))
+                .filter_map(|index| {
+                    let position = caret(index)?;
+
+                    (position.y == start.y)
+                        .then_some((index, (position.x - x).abs()))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        {
+            return index;
+        }
+
+        text.char_indices()
+            .nth(column)
+            .map_or(text.len(), |(index, _)| index)
+    }
+
+    fn edit(&mut self, edit: Edit) {
+        let now = Instant::now();
+
+        let is_replacing = self
+            .selections()
+            .iter()
+            .any(|selection| selection.anchor.is_some());
+
+        let continues = !is_replacing && self.history.continues(&edit, now);
+        let before = self.selections();
+        let revisions = self.perform_all(&Action::Edit(edit.clone()));
+
+        if revisions.is_empty() {
+            return;
+        }
+
+        self.history.push(
+            Entry {
+                revisions,
+                before,
+                after: self.selections(),
+            },
+            continues,
+        );
+        self.history.extend(&edit, now);
+    }
+
+    fn replace(
+        &mut self,
+        matcher: Option<&Matcher>,
+        targets: &[Match],
+        replacement: &str,
+    ) {
+        if targets.is_empty() {
+            return;
+        }
+
+        let before = self.selections();
+        let mut revisions = Vec::new();
+
+        self.cursors.clear();
+
+        // Later matches are replaced first, so the earlier ones stay valid
+        for target in targets.iter().rev() {
+            let line = target.line;
+
+            self.reveal(line);
+
+            let text = match (matcher, self.editor.line(line)) {
+                (Some(matcher), Some(line)) => matcher.replacement(
+                    &line.text,
+                    target.range.clone(),
+                    replacement,
+                ),
+                _ => replacement.to_owned(),
+            };
+
+            self.editor.set_cursor(
+                (line, target.range.end),
+                Some((line, target.range.start)),
+            );
+            revisions.extend(
+                self.perform_one(Action::Edit(Edit::Paste(Arc::new(text)))),
+            );
+        }
+
+        self.history.group = None;
+
+        if !revisions.is_empty() {
+            self.history.push(
+                Entry {
+                    revisions,
+                    before,
+                    after: self.selections(),
+                },
+                false,
+            );
+        }
+    }
+
+    fn jump(&mut self, motion: Motion, is_selecting: bool) {
+        let Some(matcher) = self.search.as_ref().and_then(Matcher::new) else {
+            return;
+        };
+
+        let Selection {
+            position: cursor,
+            anchor,
+        } = self.selection();
+
+        let found = match motion.direction() {
+            Direction::Right => self.next_match(&matcher, cursor),
+            Direction::Left => self.previous_match(
+                &matcher,
+                anchor.map_or(cursor, |anchor| anchor.min(cursor)),
+            ),
+        };
+
+        let Some(found) = found else {
+            return;
+        };
+
+        let start = (found.line, found.range.start);
+        let end = (found.line, found.range.end);
+
+        let (position, match_anchor) = match motion.direction() {
+            Direction::Right => (end, start),
+            Direction::Left => (start, end),
+        };
+
+        self.reveal(found.line);
+
+        self.select(Selection {
+            position,
+            anchor: Some(if is_selecting {
+                anchor.unwrap_or(cursor)
+            } else {
+                match_anchor
+            }),
+        });
+    }
+
+    /// Finds the first match starting after the given position; wrapping
+    /// around the end of the text.
+    fn next_match(
+        &self,
+        matcher: &Matcher,
+        (line, column): (usize, usize),
+    ) -> Option<Match> {
+        let line_count = self.line_count();
+
+        (line..line_count)
+            .chain(0..=line)
+            .enumerate()
+            .find_map(|(i, index)| {
+                let text = self.line(index)?.text;
+                let is_wrapped = i >= line_count.saturating_sub(line);
+
+                matcher
+                    .find(&text)
+                    .into_iter()
+                    .find(|range| {
+                        index != line
+                            || if is_wrapped {
+                                range.start < column
+                            } else {
+                                range.start >= column
+                            }
+                    })
+                    .map(|range| Match { line: index, range })
+            })
+    }
+
+    /// Finds the last match ending before the given position; wrapping
+    /// around the start of the text.
+    fn previous_match(
+        &self,
+        matcher: &Matcher,
+        (line, column): (usize, usize),
+    ) -> Option<Match> {
+        let line_count = self.line_count();
+
+        (0..=line)
+            .rev()
+            .chain((line..line_count).rev())
+            .enumerate()
+            .find_map(|(i, index)| {
+                let text = self.line(index)?.text;
+                let is_wrapped = i > line;
+
+                matcher
+                    .find(&text)
+                    .into_iter()
+                    .rev()
+                    .find(|range| {
+                        index != line
+                            || if is_wrapped {
+                                range.end > column
+                            } else {
+                                range.end <= column
+                            }
+                    })
+                    .map(|range| Match { line: index, range })
+            })
+    }
+
+    fn undo(&mut self) {
+        let Some(entry) = self.history.undo.pop_back() else {
+            return;
+        };
+
+        let entry = self.revert(entry);
+
+        self.history.redo.push(entry);
+        self.history.group = None;
+    }
+
+    fn redo(&mut self) {
+        let Some(entry) = self.history.redo.pop() else {
+            return;
+        };
+
+        let entry = self.revert(entry);
+
+        self.history.undo.push_back(entry);
+        self.history.group = None;
+    }
+
+    /// Reverts the [`Revision`]s of the given [`Entry`], returning the
+    /// [`Entry`] that reverts them back.
+    fn revert(&mut self, entry: Entry) -> Entry {
+        self.cursors.clear();
+
+        let revisions = entry
+            .revisions
+            .into_iter()
+            .rev()
+            .filter_map(|revision| {
+                self.select(Selection {
+                    position: revision.end,
+                    anchor: Some(revision.start),
+                });
+
+                self.perform_one(Action::Edit(Edit::Paste(Arc::new(
+                    revision.removed,
+                ))))
+            })
+            .collect();
+
+        for selection in &entry.before {
+            self.reveal(selection.position.0);
+        }
+
+        self.restore_selections(entry.before.clone(), |position| position);
+
+        Entry {
+            revisions,
+            before: entry.after,
+            after: entry.before,
+        }
+    }
+}
+
+/// The edit history of some [`Content`].
+#[derive(Debug)]
+struct History {
+    undo: VecDeque<Entry>,
+    redo: Vec<Entry>,
+    depth: usize,
+    group: Option<Group>,
+}
+
+/// A group of edits of some [`Content`] that is undone at once.
+#[derive(Debug)]
+struct Entry {
+    /// The changes of the text, in the order they were made.
+    revisions: Vec<Revision>,
+    /// The selections of the cursors before the edits.
+    before: Vec<Selection>,
+    /// The selections of the cursors after the edits.
+    after: Vec<Selection>,
+}
+
+/// A change of the text of some [`Content`]; with the range of the new
+/// text and the old text it replaced, so it can be reverted.
+#[derive(Debug)]
+struct Revision {
+    start: (usize, usize),
+    end: (usize, usize),
+    removed: String,
+}
+
+/// The position and selection anchor of a cursor of some [`Content`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Selection {
+    position: (usize, usize),
+    anchor: Option<(usize, usize)>,
+}
+
+impl Selection {
+    fn start(&self) -> (usize, usize) {
+        self.anchor
+            .map_or(self.position, |anchor| anchor.min(self.position))
+    }
+
+    fn end(&self) -> (usize, usize) {
+        self.anchor
+            .map_or(self.position, |anchor| anchor.max(self.position))
+    }
+}
+
+/// The group of edits that is currently being recorded.
+#[derive(Debug)]
+struct Group {
+    kind: EditKind,
+    last_char: Option<char>,
+    updated_at: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+impl EditKind {
+    fn of(edit: &Edit) -> Self {
+        match edit {
+            Edit::Insert(_) => Self::Insert,
+            Edit::Backspace | Edit::Delete => Self::Delete,
+            Edit::Paste(_) | Edit::Enter | Edit::Indent | Edit::Unindent => {
+                Self::Other
+            }
+        }
+    }
+}
+
+impl History {
+    const DEFAULT_DEPTH: usize = 100;
+
+    /// The time after which a new edit starts a new group.
+    const GROUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+    fn new(depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+            group: None,
+        }
+    }
+
+    fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+
+        while self.undo.len() > depth {
+            let _ = self.undo.pop_front();
+        }
+
+        self.redo.truncate(depth);
+        self.group = None;
+    }
+
+    /// Returns whether the given edit can be grouped with the
+    /// previous ones.
+    fn continues(&self, edit: &Edit, now: Instant) -> bool {
+        let Some(group) = &self.group else {
+            return false;
+        };
+
+        let kind = EditKind::of(edit);
+
+        if kind == EditKind::Other
+            || kind != group.kind
+            || now.duration_since(group.updated_at) > Self::GROUP_TIMEOUT
+        {
+            return false;
+        }
+
+        // Typing starts a new group at the beginning of every word
+        match (edit, group.last_char) {
+            (Edit::Insert(c), Some(last)) => {
+                c.is_whitespace() || !last.is_whitespace()
+            }
+            _ => true,
+        }
+    }
+
+    /// Pushes the given [`Entry`]; merging it into the last one if it
+    /// continues its group.
+    fn push(&mut self, entry: Entry, continues: bool) {
+        self.redo.clear();
+
+        if continues && let Some(last) = self.undo.back_mut() {
+            last.revisions.extend(entry.revisions);
+            last.after = entry.after;
+
+            return;
+        }
+
+        if self.undo.len() == self.depth {
+            let _ = self.undo.pop_front();
+        }
+
+        self.undo.push_back(entry);
+    }
+
+    fn extend(&mut self, edit: &Edit, now: Instant) {
+        if self.depth == 0 {
+            return;
+        }
+
+        self.group = Some(Group {
+            kind: EditKind::of(edit),
+            last_char: match edit {
+                Edit::Insert(c) => Some(*c),
+                _ => None,
+            },
+            updated_at: now,
+        });
+    }
+}
+
+impl<Renderer> Clone for Content<Renderer>
+where
+    Renderer: text::Renderer,
+{
+    fn clone(&self) -> Self {
+        Self::with_text(&self.text())
+    }
+}
+
+impl<Renderer> Default for Content<Renderer>
+where
+    Renderer: text::Renderer,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Renderer> fmt::Debug for Content<Renderer>
+where
+    Renderer: text::Renderer,
+    Renderer::Editor: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let internal = self.0.borrow();
+
+        f.debug_struct("Content")
+            .field("editor", &internal.editor)
+            .field("cursors", &internal.cursors)
+            .field("folds", &internal.folds)
+            .field("history", &internal.history)
+            .field("is_dirty", &internal.is_dirty)
+            .finish()
+    }
+}
+
+/// The state of a [`TextEditor`].
+#[derive(Debug)]
+pub struct State<Highlighter: text::Highlighter> {
+    focus: Option<Focus>,
+    preedit: Option<input_method::Preedit>,
+    last_click: Option<mouse::Click>,
+    drag_click: Option<mouse::click::Kind>,
+    box_origin: Option<Point>,
+    modifiers: keyboard::Modifiers,
+    hover: Option<(usize, Point)>,
+    partial_scroll: f32,
+    gutter: Gutter,
+    highlighter: RefCell<search::Highlighter<Highlighter>>,
+    highlighter_settings: Highlighter::Settings,
+    highlighter_format_address: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Focus {
+    updated_at: Instant,
+    now: Instant,
+    is_window_focused: bool,
+}
+
+impl Focus {
+    const CURSOR_BLINK_INTERVAL_MILLIS: u128 = 500;
+
+    fn now() -> Self {
+        let now = Instant::now();
+
+        Self {
+            updated_at: now,
+            now,
+            is_window_focused: true,
+        }
     }
 
     fn is_cursor_visible(&self) -> bool {
@@ -587,8 +2110,12 @@ where
             preedit: None,
             last_click: None,
             drag_click: None,
+            box_origin: None,
+            modifiers: keyboard::Modifiers::default(),
+            hover: None,
             partial_scroll: 0.0,
-            highlighter: RefCell::new(Highlighter::new(
+            gutter: Gutter::default(),
+            highlighter: RefCell::new(search::Highlighter::<Highlighter>::new(
                 &self.highlighter_settings,
             )),
             highlighter_settings: self.highlighter_settings.clone(),
@@ -628,16 +2155,59 @@ where
             state.highlighter_settings = self.highlighter_settings.clone();
         }
 
+        if state.highlighter.borrow().query() != internal.search.as_ref() {
+            state
+                .highlighter
+                .borrow_mut()
+                .search(internal.search.clone());
+        }
+
         let limits = limits
             .width(self.width)
             .height(self.height)
             .min_height(self.min_height)
             .max_height(self.max_height);
 
+        let font = self.font.unwrap_or_else(|| renderer.default_font());
+        let text_size =
+            self.text_size.unwrap_or_else(|| renderer.default_size());
+
+        state.gutter = Gutter {
+            markers: if self.marker.is_some() {
+                text_size.0
+            } else {
+                0.0
+            },
+            numbers: self.line_numbers.map_or(0.0, |_| {
+                let digits =
+                    internal.line_count().max(10).ilog10() as usize + 1;
+
+                let label = Renderer::Paragraph::with_text(Text {
+                    content: &"0".repeat(digits),
+                    bounds: Size::INFINITE,
+                    size: text_size,
+                    line_height: self.line_height,
+                    font,
+                    align_x: text::Alignment::Default,
+                    align_y: alignment::Vertical::Top,
+                    shaping: text::Shaping::Basic,
+                    wrapping: Wrapping::None,
+                });
+
+                label.min_width() + text_size.0 / 2.0
+            }),
+            folds: if self.folding.is_some() {
+                text_size.0
+            } else {
+                0.0
+            },
+            spacing: text_size.0 / 2.0,
+        };
+
         internal.editor.update(
-            limits.shrink(self.padding).max(),
-            self.font.unwrap_or_else(|| renderer.default_font()),
-            self.text_size.unwrap_or_else(|| renderer.default_size()),
+            limits.shrink(self.text_padding(state.gutter)).max(),
+            font,
+            text_size,
             self.line_height,
             self.wrapping,
             state.highlighter.borrow_mut().deref_mut(),
@@ -671,11 +2241,27 @@ where
         shell: &mut Shell<'_, Message>,
         _viewport: &Rectangle,
     ) {
+        let state = tree.state.downcast_mut::<State<Highlighter>>();
+
+        if let Event::Mouse(
+            mouse::Event::CursorMoved { .. } | mouse::Event::CursorLeft,
+        ) = event
+        {
+            let hover = cursor.position().and_then(|position| {
+                self.hovered_decoration(state, layout.bounds(), position)
+                    .map(|(i, _)| (i, position))
+            });
+
+            if hover.map(|(i, _)| i) != state.hover.map(|(i, _)| i) {
+                shell.request_redraw();
+            }
+
+            state.hover = hover;
+        }
+
         let Some(on_edit) = self.on_edit.as_ref() else {
             return;
         };
-
-        let state = tree.state.downcast_mut::<State<Highlighter>>();
         let is_redraw = matches!(
             event,
             Event::Window(window::Event::RedrawRequested(_now)),
@@ -712,20 +2298,65 @@ where
                     );
                 }
             }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = *modifiers;
+            }
             _ => {}
         }
 
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) =
+            event
+            && let Some(position) = cursor.position_in(layout.bounds())
+            && position.x < state.gutter.width()
+        {
+            let internal = self.content.0.borrow();
+            let y = position.y - self.padding.top;
+
+            let line = internal
+                .editor
+                .visible_lines()
+                .into_iter()
+                .find(|(_, bounds)| {
+                    y >= bounds.y && y < bounds.y + bounds.height
+                })
+                .map(|(line, _)| line);
+
+            if let Some(line) = line {
+                if state.gutter.is_fold_column(position.x) {
+                    if internal.folds.iter().any(|lines| lines.start == line) {
+                        shell.publish(on_edit(Action::Unfold(line)));
+                    } else if let Some(lines) = self
+                        .fold_ranges(state)
+                        .into_iter()
+                        .find(|lines| lines.start == line)
+                    {
+                        shell.publish(on_edit(Action::Fold(lines)));
+                    }
+                } else if let Some(on_gutter_click) = &self.on_gutter_click {
+                    shell.publish(on_gutter_click(line));
+                }
+            }
+
+            shell.capture_event();
+            return;
+        }
+
         if let Some(update) = Update::from_event(
             event,
             state,
             layout.bounds(),
-            self.padding,
+            self.text_padding(state.gutter),
             cursor,
             self.key_binding.as_deref(),
         ) {
             match update {
                 Update::Click(click) => {
                     let action = match click.kind() {
+                        mouse::click::Kind::Single if state.modifiers.alt() => {
+                            state.box_origin = Some(click.position());
+
+                            Action::AddCursor(click.position())
+                        }
                         mouse::click::Kind::Single => {
                             Action::Click(click.position())
                         }
@@ -741,10 +2372,16 @@ where
                     shell.capture_event();
                 }
                 Update::Drag(position) => {
-                    shell.publish(on_edit(Action::Drag(position)));
+                    let action = match state.box_origin {
+                        Some(from) => Action::SelectBox { from, to: position },
+                        None => Action::Drag(position),
+                    };
+
+                    shell.publish(on_edit(action));
                 }
                 Update::Release => {
                     state.drag_click = None;
+                    state.box_origin = None;
                 }
                 Update::Scroll(lines) => {
                     let bounds = self.content.0.borrow().editor.bounds();
@@ -784,6 +2421,15 @@ where
                     }
                 },
                 Update::Binding(binding) => {
+                    let binding = match binding {
+                        Binding::Unfocus
+                            if !self.content.0.borrow().cursors.is_empty() =>
+                        {
+                            Binding::ClearCursors
+                        }
+                        binding => binding,
+                    };
+
                     fn apply_binding<
                         H: text::Highlighter,
                         R: text::Renderer,
@@ -846,6 +2492,12 @@ where
                             Binding::SelectAll => {
                                 publish(Action::SelectAll);
                             }
+                            Binding::SelectNextOccurrence => {
+                                publish(Action::SelectNextOccurrence);
+                            }
+                            Binding::ClearCursors => {
+                                publish(Action::ClearCursors);
+                            }
                             Binding::Insert(c) => {
                                 publish(Action::Edit(Edit::Insert(c)));
                             }
@@ -858,6 +2510,12 @@ where
                             Binding::Delete => {
                                 publish(Action::Edit(Edit::Delete));
                             }
+                            Binding::Undo => {
+                                publish(Action::Undo);
+                            }
+                            Binding::Redo => {
+                                publish(Action::Redo);
+                            }
                             Binding::Sequence(sequence) => {
                                 for binding in sequence {
                                     apply_binding(
@@ -938,15 +2596,23 @@ where
 
         let font = self.font.unwrap_or_else(|| renderer.default_font());
 
+        let style = theme
+            .style(&self.class, self.last_status.unwrap_or(Status::Active));
+
         internal.editor.highlight(
             font,
             state.highlighter.borrow_mut().deref_mut(),
-            |highlight| (self.highlighter_format)(highlight, theme),
+            |highlight| match highlight {
+                search::Highlight::Match => highlighter::Format {
+                    color: Some(style.search_match),
+                    font: None,
+                },
+                search::Highlight::Inner(highlight) => {
+                    (self.highlighter_format)(highlight, theme)
+                }
+            },
         );
 
-        let style = theme
-            .style(&self.class, self.last_status.unwrap_or(Status::Active));
-
         renderer.fill_quad(
             renderer::Quad {
                 bounds,
@@ -956,7 +2622,21 @@ where
             style.background,
         );
 
-        let text_bounds = bounds.shrink(self.padding);
+        let text_bounds = bounds.shrink(self.text_padding(state.gutter));
+
+        if self.highlight_current_line || state.gutter.width() > 0.0 {
+            self.draw_gutter(
+                state,
+                &internal,
+                renderer,
+                &style,
+                bounds,
+                text_bounds,
+                font,
+            );
+        }
+
+        draw_decorations(&internal, renderer, text_bounds);
 
         if internal.editor.is_empty() {
             if let Some(placeholder) = self.placeholder.clone() {
@@ -991,10 +2671,16 @@ where
         let translation = text_bounds.position() - Point::ORIGIN;
 
         if let Some(focus) = state.focus.as_ref() {
-            match internal.editor.cursor() {
-                Cursor::Caret(position) if focus.is_cursor_visible() => {
-                    let cursor =
-                        Rectangle::new(
+            let cursors = iter::once(internal.editor.cursor()).chain(
+                internal.cursors.iter().filter_map(|cursor| {
+                    internal.editor.cursor_at(cursor.position, cursor.anchor)
+                }),
+            );
+
+            for cursor in cursors {
+                match cursor {
+                    Cursor::Caret(position) if focus.is_cursor_visible() => {
+                        let cursor = Rectangle::new(
                             position + translation,
                             Size::new(
                                 1.0,
@@ -1006,32 +2692,33 @@ where
                             ),
                         );
 
-                    if let Some(clipped_cursor) =
-                        text_bounds.intersection(&cursor)
-                    {
-                        renderer.fill_quad(
-                            renderer::Quad {
-                                bounds: clipped_cursor,
-                                ..renderer::Quad::default()
-                            },
-                            style.value,
-                        );
+                        if let Some(clipped_cursor) =
+                            text_bounds.intersection(&cursor)
+                        {
+                            renderer.fill_quad(
+                                renderer::Quad {
+                                    bounds: clipped_cursor,
+                                    ..renderer::Quad::default()
+                                },
+                                style.value,
+                            );
+                        }
                     }
-                }
-                Cursor::Selection(ranges) => {
-                    for range in ranges.into_iter().filter_map(|range| {
-                        text_bounds.intersection(&(range + translation))
-                    }) {
-                        renderer.fill_quad(
-                            renderer::Quad {
-                                bounds: range,
-                                ..renderer::Quad::default()
-                            },
-                            style.selection,
-                        );
+                    Cursor::Selection(ranges) => {
+                        for range in ranges.into_iter().filter_map(|range| {
+                            text_bounds.intersection(&(range + translation))
+                        }) {
+                            renderer.fill_quad(
+                                renderer::Quad {
+                                    bounds: range,
+                                    ..renderer::Quad::default()
+                                },
+                                style.selection,
+                            );
+                        }
                     }
+                    Cursor::Caret(_) => {}
                 }
-                Cursor::Caret(_) => {}
             }
         }
     }
@@ -1068,6 +2755,131 @@ where
 
         operation.focusable(self.id.as_ref(), layout.bounds(), state);
     }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut widget::Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        _viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let state = tree.state.downcast_ref::<State<Highlighter>>();
+        let (_, position) = state.hover?;
+
+        let (index, anchor) =
+            self.hovered_decoration(state, layout.bounds(), position)?;
+
+        let content =
+            self.content.0.borrow().decorations[index].tooltip.clone()?;
+
+        Some(overlay::Element::new(Box::new(Tooltip {
+            content,
+            anchor: anchor + translation,
+            font: self.font.unwrap_or_else(|| renderer.default_font()),
+            text_size: self
+                .text_size
+                .unwrap_or_else(|| renderer.default_size()),
+            line_height: self.line_height,
+            padding: self.padding,
+            class: &self.class,
+        })))
+    }
+}
+
+/// The tooltip of a hovered [`Decoration`].
+struct Tooltip<'a, 'b, Theme, Renderer>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer,
+{
+    content: String,
+    anchor: Rectangle,
+    font: Renderer::Font,
+    text_size: Pixels,
+    line_height: LineHeight,
+    padding: Padding,
+    class: &'b Theme::Class<'a>,
+}
+
+impl<Theme, Renderer> Tooltip<'_, '_, Theme, Renderer>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer,
+{
+    fn text(&self, bounds: Size) -> Text<&str, Renderer::Font> {
+        Text {
+            content: &self.content,
+            bounds,
+            size: self.text_size,
+            line_height: self.line_height,
+            font: self.font,
+            align_x: text::Alignment::Default,
+            align_y: alignment::Vertical::Top,
+            shaping: text::Shaping::Advanced,
+            wrapping: Wrapping::WordOrGlyph,
+        }
+    }
+}
+
+impl<Message, Theme, Renderer> overlay::Overlay<Message, Theme, Renderer>
+    for Tooltip<'_, '_, Theme, Renderer>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer,
+{
+    fn layout(&mut self, _renderer: &Renderer, bounds: Size) -> layout::Node {
+        let max_width = (bounds.width / 2.0).max(self.anchor.width);
+
+        let paragraph = Renderer::Paragraph::with_text(
+            self.text(Size::new(max_width, f32::INFINITY)),
+        );
+
+        let size = paragraph.min_bounds().expand(self.padding);
+
+        let y = if self.anchor.y + self.anchor.height + size.height
+            > bounds.height
+        {
+            self.anchor.y - size.height
+        } else {
+            self.anchor.y + self.anchor.height
+        };
+
+        let x = self.anchor.x.min(bounds.width - size.width).max(0.0);
+
+        layout::Node::new(size).move_to(Point::new(x, y.max(0.0)))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let style = theme.style(self.class, Status::Active);
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: style.border,
+                ..renderer::Quad::default()
+            },
+            style.background,
+        );
+
+        let text_bounds = bounds.shrink(self.padding);
+
+        renderer.fill_text(
+            self.text(text_bounds.size())
+                .with_content(self.content.clone()),
+            text_bounds.position(),
+            style.value,
+            bounds,
+        );
+    }
 }
 
 impl<'a, Highlighter, Message, Theme, Renderer>
@@ -1090,6 +2902,9 @@ where
 #[derive(Debug, Clone, PartialEq)]
 pub enum Binding<Message> {
     /// Unfocus the [`TextEditor`].
+    ///
+    /// If the [`TextEditor`] has multiple cursors, they are cleared
+    /// instead; see [`Binding::ClearCursors`].
     Unfocus,
     /// Copy the selection of the [`TextEditor`].
     Copy,
@@ -1107,6 +2922,10 @@ pub enum Binding<Message> {
     SelectLine,
     /// Select the entire buffer.
     SelectAll,
+    /// Select the next occurrence of the selection with a new cursor.
+    SelectNextOccurrence,
+    /// Remove all the cursors but the main one.
+    ClearCursors,
     /// Insert the given character.
     Insert(char),
     /// Break the current line.
@@ -1115,6 +2934,10 @@ pub enum Binding<Message> {
     Backspace,
     /// Delete the next character.
     Delete,
+    /// Undo the last group of edits.
+    Undo,
+    /// Redo the last undone group of edits.
+    Redo,
     /// A sequence of bindings to execute.
     Sequence(Vec<Self>),
     /// Produce the given message.
@@ -1173,6 +2996,19 @@ impl<Message> Binding<Message> {
             keyboard::Key::Character("a") if modifiers.command() => {
                 Some(Self::SelectAll)
             }
+            keyboard::Key::Character("d") if modifiers.command() => {
+                Some(Self::SelectNextOccurrence)
+            }
+            keyboard::Key::Character("z" | "Z") if modifiers.command() => {
+                Some(if modifiers.shift() {
+                    Self::Redo
+                } else {
+                    Self::Undo
+                })
+            }
+            keyboard::Key::Character("y") if modifiers.command() => {
+                Some(Self::Redo)
+            }
             _ => {
                 if let Some(text) = text {
                     let c = text.chars().find(|c| !c.is_control())?;
@@ -1342,6 +3178,80 @@ impl<Message> Update<Message> {
     }
 }
 
+/// Draws the background tints and underlines of the [`Decoration`]s of
+/// the given [`Internal`] content.
+fn draw_decorations<Renderer>(
+    internal: &Internal<Renderer>,
+    renderer: &mut Renderer,
+    text_bounds: Rectangle,
+) where
+    Renderer: text::Renderer,
+{
+    let translation = text_bounds.position() - Point::ORIGIN;
+
+    for decoration in &internal.decorations {
+        if decoration.background.is_none() && decoration.underline.is_none() {
+            continue;
+        }
+
+        for bounds in internal
+            .decoration_bounds(decoration)
+            .into_iter()
+            .filter_map(|bounds| {
+                text_bounds.intersection(&(bounds + translation))
+            })
+        {
+            if let Some(background) = decoration.background {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds,
+                        ..renderer::Quad::default()
+                    },
+                    background,
+                );
+            }
+
+            let bottom = bounds.y + bounds.height;
+
+            match decoration.underline {
+                Some(Underline::Solid(color)) => {
+                    renderer.fill_quad(
+                        renderer::Quad {
+                            bounds: Rectangle {
+                                y: bottom - 1.0,
+                                height: 1.0,
+                                ..bounds
+                            },
+                            ..renderer::Quad::default()
+                        },
+                        color,
+                    );
+                }
+                Some(Underline::Squiggly(color)) => {
+                    // A triangle wave, one pixel at a time
+                    for step in 0..bounds.width.ceil() as usize {
+                        let offset = [2.0, 1.0, 0.0, 1.0][step % 4];
+
+                        renderer.fill_quad(
+                            renderer::Quad {
+                                bounds: Rectangle {
+                                    x: bounds.x + step as f32,
+                                    y: bottom - 3.0 + offset,
+                                    width: 1.0,
+                                    height: 1.0,
+                                },
+                                ..renderer::Quad::default()
+                            },
+                            color,
+                        );
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+}
+
 fn motion(key: key::Named) -> Option<Motion> {
     match key {
         key::Named::ArrowLeft => Some(Motion::Left),
@@ -1385,6 +3295,17 @@ pub struct Style {
     pub value: Color,
     /// The [`Color`] of the selection of the text input.
     pub selection: Color,
+    /// The [`Color`] of the text matching the active search of the
+    /// text input.
+    pub search_match: Color,
+    /// The [`Background`] of the gutter of the text input.
+    pub gutter: Background,
+    /// The [`Color`] of the line numbers of the text input.
+    pub line_number: Color,
+    /// The [`Color`] of the line number of the line with the cursor.
+    pub current_line_number: Color,
+    /// The [`Background`] of the line with the cursor, if highlighted.
+    pub current_line: Background,
 }
 
 /// The theme catalog of a [`TextEditor`].
@@ -1428,6 +3349,11 @@ pub fn default(theme: &Theme, status: Status) -> Style {
         placeholder: palette.secondary.base.color,
         value: palette.background.base.text,
         selection: palette.primary.weak.color,
+        search_match: palette.primary.strong.color,
+        gutter: Background::Color(palette.background.weakest.color),
+        line_number: palette.background.strongest.color,
+        current_line_number: palette.background.base.text,
+        current_line: Background::Color(palette.background.weaker.color),
     };
 
     match status {
@@ -1454,3 +3380,437 @@ pub fn default(theme: &Theme, status: Status) -> Style {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Font, Pixels};
+
+    type Content = super::Content<crate::Renderer>;
+
+    fn lines(content: &Content) -> Vec<String> {
+        content.lines().map(|line| line.text.into_owned()).collect()
+    }
+
+    fn folds(content: &Content) -> Vec<(usize, usize)> {
+        content
+            .folds()
+            .into_iter()
+            .map(|lines| (lines.start, lines.end))
+            .collect()
+    }
+
+    /// Lays out the given [`Content`] with lines 10 units tall.
+    fn lay_out(content: &mut Content) {
+        content.0.get_mut().editor.update(
+            Size::new(400.0, 400.0),
+            Font::default(),
+            Pixels(10.0),
+            LineHeight::Absolute(Pixels(10.0)),
+            Wrapping::None,
+            &mut highlighter::PlainText,
+        );
+    }
+
+    fn caret(content: &Content, position: (usize, usize)) -> Point {
+        match content.0.borrow().editor.cursor_at(position, None) {
+            Some(Cursor::Caret(caret)) => caret,
+            cursor => panic!("{position:?} is not laid out: {cursor:?}"),
+        }
+    }
+
+    fn type_text(content: &mut Content, text: &str) {
+        for c in text.chars() {
+            content.perform(Action::Edit(Edit::Insert(c)));
+        }
+    }
+
+    #[test]
+    fn edits_are_undone_in_groups() {
+        let mut content = Content::with_text("");
+
+        type_text(&mut content, "hello world");
+        content.perform(Action::Edit(Edit::Backspace));
+        content.perform(Action::Edit(Edit::Backspace));
+        assert_eq!(content.text(), "hello wor");
+
+        // Typing is grouped by word, and deleting apart from it
+        content.perform(Action::Undo);
+        assert_eq!(content.text(), "hello world");
+        assert_eq!(content.cursor_position(), (0, 11));
+
+        content.perform(Action::Undo);
+        assert_eq!(content.text(), "hello ");
+
+        content.perform(Action::Undo);
+        assert_eq!(content.text(), "");
+        assert!(!content.can_undo());
+
+        content.perform(Action::Redo);
+        content.perform(Action::Redo);
+        assert_eq!(content.text(), "hello world");
+
+        content.perform(Action::Redo);
+        assert_eq!(content.text(), "hello wor");
+        assert_eq!(content.cursor_position(), (0, 9));
+        assert!(!content.can_redo());
+    }
+
+    #[test]
+    fn new_edits_clear_redo() {
+        let mut content = Content::with_text("abc");
+
+        content.perform(Action::Move(Motion::DocumentEnd));
+        content.perform(Action::Edit(Edit::Enter));
+        type_text(&mut content, "d");
+        assert_eq!(content.text(), "abc\nd");
+
+        content.perform(Action::Undo);
+        assert_eq!(content.text(), "abc\n");
+        assert!(content.can_redo());
+
+        type_text(&mut content, "e");
+        assert!(!content.can_redo());
+
+        content.perform(Action::Redo);
+        assert_eq!(content.text(), "abc\ne");
+
+        content.perform(Action::Undo);
+        content.perform(Action::Undo);
+        assert_eq!(content.text(), "abc");
+        assert!(!content.can_undo());
+    }
+
+    #[test]
+    fn edits_at_every_cursor_are_undone_at_once() {
+        let mut content = Content::with_text("foo\nbar foo\nfoo");
+
+        content.perform(Action::SelectNextOccurrence);
+        content.perform(Action::SelectNextOccurrence);
+        content.perform(Action::SelectNextOccurrence);
+
+        let cursors = content.cursor_positions();
+
+        type_text(&mut content, "baz");
+        assert_eq!(content.text(), "baz\nbar baz\nbaz");
+
+        content.perform(Action::Undo);
+        assert_eq!(content.text(), "foo\nbar foo\nfoo");
+        assert_eq!(content.cursor_positions(), cursors);
+
+        content.perform(Action::Redo);
+        assert_eq!(content.text(), "baz\nbar baz\nbaz");
+        assert_eq!(content.cursor_positions().len(), 3);
+    }
+
+    #[test]
+    fn folded_lines_stay_in_the_text() {
+        let mut content = Content::with_text("a\nb\nc\nd\ne");
+
+        content.perform(Action::Move(Motion::Down));
+        content.perform(Action::Move(Motion::Down));
+        content.perform(Action::Fold(1..4));
+
+        assert_eq!(folds(&content), [(1, 4)]);
+        assert_eq!(content.text(), "a\nb\nc\nd\ne");
+        assert_eq!(content.line(2).unwrap().text, "c");
+
+        // The cursor leaves the hidden lines
+        assert_eq!(content.cursor_position(), (1, 1));
+
+        // Motions skip the hidden lines
+        content.perform(Action::Move(Motion::Down));
+        assert_eq!(content.cursor_position().0, 4);
+
+        content.perform(Action::Move(Motion::Up));
+        assert_eq!(content.cursor_position().0, 1);
+
+        content.perform(Action::Move(Motion::End));
+        content.perform(Action::Move(Motion::Right));
+        assert_eq!(content.cursor_position(), (4, 0));
+
+        content.perform(Action::Unfold(1));
+        assert!(content.folds().is_empty());
+        assert_eq!(content.text(), "a\nb\nc\nd\ne");
+    }
+
+    #[test]
+    fn folded_lines_are_not_laid_out() {
+        let mut content = Content::with_text("a\nb\nc\nd\ne");
+
+        content.perform(Action::Fold(1..4));
+        lay_out(&mut content);
+
+        let visible: Vec<_> = content
+            .0
+            .get_mut()
+            .editor
+            .visible_lines()
+            .into_iter()
+            .map(|(line, bounds)| (line, bounds.y))
+            .collect();
+
+        assert_eq!(visible, [(0, 0.0), (1, 10.0), (4, 20.0)]);
+    }
+
+    #[test]
+    fn edits_move_and_unfold_folds() {
+        let mut content = Content::with_text("a\nb\nc\nd\ne");
+
+        content.perform(Action::Fold(1..4));
+
+        // Edits before a fold move it
+        content.perform(Action::Move(Motion::DocumentStart));
+        content.perform(Action::Edit(Edit::Enter));
+        assert_eq!(folds(&content), [(2, 5)]);
+
+        // Edits after a fold leave it in place
+        content.perform(Action::Move(Motion::DocumentEnd));
+        content.perform(Action::Edit(Edit::Insert('!')));
+        assert_eq!(folds(&content), [(2, 5)]);
+        assert_eq!(lines(&content), ["", "a", "b", "c", "d", "e!"]);
+
+        // Edits touching its hidden lines unfold it
+        content.perform(Action::Move(Motion::Home));
+        content.perform(Action::Edit(Edit::Backspace));
+        assert!(content.folds().is_empty());
+        assert_eq!(lines(&content), ["", "a", "b", "c", "de!"]);
+
+        content.perform(Action::Fold(2..4));
+        content.perform(Action::Move(Motion::Up));
+        content.perform(Action::Move(Motion::End));
+        assert_eq!(content.cursor_position(), (2, 1));
+
+        content.perform(Action::Edit(Edit::Delete));
+        assert!(content.folds().is_empty());
+        assert_eq!(lines(&content), ["", "a", "bc", "de!"]);
+    }
+
+    #[test]
+    fn nested_folds_are_merged() {
+        let mut content = Content::with_text("a\nb\nc\nd\ne\nf");
+
+        content.perform(Action::Fold(2..4));
+        content.perform(Action::Fold(4..6));
+        assert_eq!(folds(&content), [(2, 4), (4, 6)]);
+
+        content.perform(Action::Fold(1..5));
+        assert_eq!(folds(&content), [(1, 5)]);
+
+        // Unfolding a hidden line unfolds its fold
+        content.perform(Action::Unfold(3));
+        assert!(content.folds().is_empty());
+        assert_eq!(content.text(), "a\nb\nc\nd\ne\nf");
+    }
+
+    #[test]
+    fn offsets_from_end_follow_earlier_edits() {
+        let mut content = Content::with_text("ab\ncde\nf");
+        let internal = content.0.get_mut();
+
+        let selection = Selection {
+            position: (1, 1),
+            anchor: Some((0, 2)),
+        };
+
+        let offset = internal.offset_from_end(selection);
+
+        assert_eq!(
+            offset,
+            Selection {
+                position: (1, 2),
+                anchor: Some((2, 0)),
+            }
+        );
+        assert_eq!(internal.offset_to_position(offset), selection);
+
+        internal.editor.set_cursor((0, 0), None);
+        internal
+            .editor
+            .perform(Action::Edit(Edit::Paste(Arc::new("x\nyy".to_owned()))));
+
+        assert_eq!(
+            internal.offset_to_position(offset),
+            Selection {
+                position: (2, 1),
+                anchor: Some((1, 4)),
+            }
+        );
+    }
+
+    #[test]
+    fn actions_are_performed_at_every_cursor() {
+        let mut content = Content::with_text("one\ntwo\nthree");
+        let internal = content.0.get_mut();
+
+        internal.editor.set_cursor((2, 5), None);
+        internal.cursors = vec![
+            Selection {
+                position: (0, 3),
+                anchor: None,
+            },
+            Selection {
+                position: (1, 3),
+                anchor: Some((1, 1)),
+            },
+        ];
+
+        content.perform(Action::Edit(Edit::Insert('!')));
+        assert_eq!(content.text(), "one!\nt!\nthree!");
+        assert_eq!(content.cursor_positions(), [(2, 6), (0, 4), (1, 2)]);
+
+        content.perform(Action::Edit(Edit::Enter));
+        assert_eq!(content.text(), "one!\n\nt!\n\nthree!\n");
+        assert_eq!(content.cursor_positions(), [(5, 0), (1, 0), (3, 0)]);
+
+        // Cursors that meet are merged
+        content.perform(Action::Move(Motion::DocumentStart));
+        assert_eq!(content.cursor_positions(), [(0, 0)]);
+    }
+
+    #[test]
+    fn extra_cursors_can_be_cleared() {
+        let mut content = Content::with_text("foo foo");
+
+        content.perform(Action::SelectNextOccurrence);
+        content.perform(Action::SelectNextOccurrence);
+        assert_eq!(content.cursor_positions(), [(0, 7), (0, 0)]);
+
+        content.perform(Action::ClearCursors);
+        assert_eq!(content.cursor_positions(), [(0, 7)]);
+        assert_eq!(content.selection().as_deref(), Some("foo"));
+    }
+
+    #[test]
+    fn index_at_horizontal_position() {
+        let mut content = Content::with_text("abcdef\nxé");
+
+        // Lines that are not laid out fall back to the column
+        assert_eq!(content.0.get_mut().index_at(1, 100.0, 2), 3);
+
+        lay_out(&mut content);
+
+        for index in 0..=6 {
+            let x = caret(&content, (0, index)).x;
+
+            assert_eq!(content.0.get_mut().index_at(0, x + 0.1, 0), index);
+        }
+
+        assert_eq!(content.0.get_mut().index_at(1, 1000.0, 0), 3);
+        assert_eq!(content.0.get_mut().index_at(1, -10.0, 0), 0);
+    }
+
+    #[test]
+    fn box_selection() {
+        let mut content = Content::with_text("abcdef\nab\nabcdef\nabcdef");
+
+        lay_out(&mut content);
+
+        let from = Point::new(caret(&content, (0, 1)).x, 5.0);
+        let to = Point::new(caret(&content, (0, 4)).x, 25.0);
+
+        content.perform(Action::SelectBox { from, to });
+
+        assert_eq!(content.cursor_positions(), [(2, 4), (0, 4), (1, 2)]);
+        assert_eq!(content.selection().as_deref(), Some("bcd\nb\nbcd"));
+
+        // The box can be dragged upwards; the main cursor follows
+        content.perform(Action::SelectBox {
+            from: to,
+            to: Point::new(from.x, 15.0),
+        });
+
+        assert_eq!(content.cursor_positions(), [(1, 1), (2, 1)]);
+        assert_eq!(content.selection().as_deref(), Some("b\nbcd"));
+
+        // Folded lines are skipped
+        content.perform(Action::Move(Motion::DocumentStart));
+        content.perform(Action::Fold(0..2));
+        lay_out(&mut content);
+
+        content.perform(Action::SelectBox {
+            from: Point::new(from.x, 5.0),
+            to: Point::new(to.x, 15.0),
+        });
+
+        assert_eq!(content.selection().as_deref(), Some("bcd\nbcd"));
+        assert_eq!(content.cursor_positions(), [(2, 4), (0, 4)]);
+    }
+
+    #[test]
+    fn box_selection_without_visible_lines() {
+        let mut content = super::Content::<()>::with_text("abcdef\nabcdef");
+        let before = content.cursor_positions();
+
+        assert_eq!(content.0.borrow().line_at(5.0), None);
+
+        content.perform(Action::SelectBox {
+            from: Point::new(0.0, 5.0),
+            to: Point::new(20.0, 15.0),
+        });
+
+        assert_eq!(content.cursor_positions(), before);
+        assert_eq!(content.selection(), None);
+    }
+
+    #[test]
+    fn matches_are_replaced_at_once() {
+        let mut content = Content::with_text("foo bar\nFoo\nfood");
+
+        content.set_search(Some(Query::new("foo").whole_word(true)));
+        assert_eq!(content.replace_all("baz"), 2);
+        assert_eq!(content.text(), "baz bar\nbaz\nfood");
+
+        content.perform(Action::Undo);
+        assert_eq!(content.text(), "foo bar\nFoo\nfood");
+
+        content.set_search(Some(Query::new("o")));
+        assert_eq!(content.replace_all(""), 6);
+        assert_eq!(content.text(), "f bar\nF\nfd");
+    }
+
+    #[test]
+    fn jumping_between_matches() {
+        let mut content = Content::with_text("foo\nbar foo\nfoo");
+        let selection = |content: &Content| {
+            let selection = content.0.borrow().selection();
+
+            (selection.anchor, selection.position)
+        };
+
+        // Without a search, nothing happens
+        content.perform(Action::Move(Motion::NextMatch));
+        assert_eq!(selection(&content), (None, (0, 0)));
+
+        content.set_search(Some(Query::new("foo")));
+
+        content.perform(Action::Move(Motion::NextMatch));
+        assert_eq!(selection(&content), (Some((0, 0)), (0, 3)));
+
+        content.perform(Action::Move(Motion::NextMatch));
+        assert_eq!(selection(&content), (Some((1, 4)), (1, 7)));
+
+        content.perform(Action::Move(Motion::NextMatch));
+        content.perform(Action::Move(Motion::NextMatch));
+        assert_eq!(selection(&content), (Some((0, 0)), (0, 3)));
+
+        // Searching backwards wraps around the start
+        content.perform(Action::Move(Motion::PreviousMatch));
+        assert_eq!(selection(&content), (Some((2, 3)), (2, 0)));
+
+        // Selecting extends the current selection
+        content.perform(Action::Move(Motion::DocumentStart));
+        content.perform(Action::Select(Motion::NextMatch));
+        content.perform(Action::Select(Motion::NextMatch));
+        assert_eq!(selection(&content), (Some((0, 0)), (1, 7)));
+        assert_eq!(content.selection().as_deref(), Some("foo\nbar foo"));
+
+        // Folded matches are revealed
+        content.perform(Action::Move(Motion::DocumentStart));
+        content.perform(Action::Fold(0..3));
+        content.perform(Action::Move(Motion::NextMatch));
+        content.perform(Action::Move(Motion::NextMatch));
+        assert_eq!(selection(&content), (Some((1, 4)), (1, 7)));
+        assert!(content.folds().is_empty());
+    }
+}
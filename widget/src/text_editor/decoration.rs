@@ -0,0 +1,342 @@
+use crate::core::Color;
+use crate::core::text::editor::LineEnding;
+
+/// A decoration of a range of text in the [`Content`] of a [`TextEditor`];
+/// like a diagnostic.
+///
+/// Decorations move along with the text as the [`Content`] is edited,
+/// and disappear once their text is removed.
+///
+/// [`Content`]: super::Content
+/// [`TextEditor`]: super::TextEditor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoration {
+    /// The start of the decorated text, as a line and a column.
+    pub start: (usize, usize),
+    /// The end of the decorated text, as a line and a column.
+    pub end: (usize, usize),
+    /// The [`Underline`] of the decorated text, if any.
+    pub underline: Option<Underline>,
+    /// The background tint of the decorated text, if any.
+    pub background: Option<Color>,
+    /// The tooltip shown when hovering the decorated text, if any.
+    pub tooltip: Option<String>,
+}
+
+impl Decoration {
+    /// Creates a new [`Decoration`] of the text between the given
+    /// positions, without any underline, background, or tooltip.
+    pub fn new(start: (usize, usize), end: (usize, usize)) -> Self {
+        Self {
+            start: start.min(end),
+            end: start.max(end),
+            underline: None,
+            background: None,
+            tooltip: None,
+        }
+    }
+
+    /// Sets the [`Underline`] of the [`Decoration`].
+    pub fn underline(mut self, underline: Underline) -> Self {
+        self.underline = Some(underline);
+        self
+    }
+
+    /// Sets the background tint of the [`Decoration`].
+    pub fn background(mut self, color: impl Into<Color>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+
+    /// Sets the tooltip of the [`Decoration`].
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+}
+
+/// The underline of a [`Decoration`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Underline {
+    /// A straight line.
+    Solid(Color),
+    /// A wavy line; commonly used for errors and warnings.
+    Squiggly(Color),
+}
+
+impl Underline {
+    /// Returns the [`Color`] of the [`Underline`].
+    pub fn color(self) -> Color {
+        match self {
+            Self::Solid(color) | Self::Squiggly(color) => color,
+        }
+    }
+}
+
+/// The replacement of some consecutive lines of text, used to move
+/// [`Decoration`]s along with an edit.
+#[derive(Debug)]
+pub(super) struct Change {
+    first: usize,
+    old: Region,
+    new: Region,
+    prefix: usize,
+    suffix: usize,
+}
+
+impl Change {
+    /// Creates the [`Change`] that replaces the `old` lines starting at
+    /// `first` with the `new` ones, after an edit at the given position.
+    ///
+    /// The position disambiguates repeated text; like removing one of
+    /// two equal words.
+    pub fn new(
+        first: usize,
+        position: (usize, usize),
+        old: &[(String, LineEnding)],
+        new: &[(String, LineEnding)],
+    ) -> Self {
+        let old = Region::new(old);
+        let new = Region::new(new);
+
+        let limit = position
+            .0
+            .checked_sub(first)
+            .map_or(0, |line| old.offset((line, position.1)));
+
+        let prefix: usize = old
+            .text
+            .chars()
+            .zip(new.text.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .scan(0, |total, length| {
+                *total += length;
+                (*total <= limit).then_some(length)
+            })
+            .sum();
+
+        let limit = old.text.len().min(new.text.len()) - prefix;
+
+        let suffix: usize = old.text[prefix..]
+            .chars()
+            .rev()
+            .zip(new.text[prefix..].chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(c, _)| c.len_utf8())
+            .scan(0, |total, length| {
+                *total += length;
+                (*total <= limit).then_some(length)
+            })
+            .sum();
+
+        Self {
+            first,
+            old,
+            new,
+            prefix,
+            suffix,
+        }
+    }
+
+    /// Returns the start and the end of the new text of the [`Change`],
+    /// along with the old text it replaced; or `None` if nothing changed.
+    pub fn replaced(&self) -> Option<((usize, usize), (usize, usize), &str)> {
+        let old_end = self.old.text.len() - self.suffix;
+        let new_end = self.new.text.len() - self.suffix;
+
+        if self.prefix == old_end && self.prefix == new_end {
+            return None;
+        }
+
+        let position = |offset| {
+            let (line, column) = self.new.position(offset);
+
+            (self.first + line, column)
+        };
+
+        Some((
+            position(self.prefix),
+            position(new_end),
+            &self.old.text[self.prefix..old_end],
+        ))
+    }
+
+    /// Moves the given [`Decoration`] along with the [`Change`], returning
+    /// `false` if its text was removed.
+    pub fn apply(&self, decoration: &mut Decoration) -> bool {
+        let was_empty = decoration.start == decoration.end;
+
+        decoration.start = self.position(decoration.start, false);
+        decoration.end = self.position(decoration.end, true);
+
+        was_empty || decoration.start < decoration.end
+    }
+
+    /// Moves the given position along with the [`Change`].
+    ///
+    /// Positions inside of the replaced text move to the end of the new
+    /// one. Insertions right at the end of a range are kept out of it.
+    fn position(
+        &self,
+        position: (usize, usize),
+        is_end: bool,
+    ) -> (usize, usize) {
+        let old_lines = self.old.lines.len();
+        let new_lines = self.new.lines.len();
+
+        if position.0 < self.first {
+            return position;
+        }
+
+        if position.0 >= self.first + old_lines {
+            return (position.0 + new_lines - old_lines, position.1);
+        }
+
+        let offset = self.old.offset((position.0 - self.first, position.1));
+        let old_end = self.old.text.len() - self.suffix;
+        let new_end = self.new.text.len() - self.suffix;
+
+        let offset = if offset < self.prefix || offset == self.prefix && is_end
+        {
+            offset
+        } else if offset >= old_end {
+            offset - old_end + new_end
+        } else {
+            new_end
+        };
+
+        let (line, column) = self.new.position(offset);
+
+        (self.first + line, column)
+    }
+}
+
+/// Some consecutive lines of text joined together.
+#[derive(Debug)]
+struct Region {
+    text: String,
+    lines: Vec<usize>,
+}
+
+impl Region {
+    fn new(lines: &[(String, LineEnding)]) -> Self {
+        let mut text = String::new();
+        let mut starts = Vec::with_capacity(lines.len());
+
+        for (i, (line, ending)) in lines.iter().enumerate() {
+            starts.push(text.len());
+            text.push_str(line);
+
+            if i + 1 < lines.len() {
+                text.push_str(if *ending == LineEnding::None {
+                    LineEnding::default().as_str()
+                } else {
+                    ending.as_str()
+                });
+            }
+        }
+
+        Self {
+            text,
+            lines: starts,
+        }
+    }
+
+    fn offset(&self, (line, column): (usize, usize)) -> usize {
+        let Some(&start) = self.lines.get(line) else {
+            return self.text.len();
+        };
+
+        let end = self.lines.get(line + 1).copied().unwrap_or(self.text.len());
+
+        (start + column).min(end)
+    }
+
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self
+            .lines
+            .partition_point(|start| *start <= offset)
+            .saturating_sub(1);
+
+        (line, offset - self.lines.get(line).copied().unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<(String, LineEnding)> {
+        text.split('\n')
+            .map(|line| (line.to_owned(), LineEnding::Lf))
+            .collect()
+    }
+
+    fn apply(
+        change: &Change,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> Option<((usize, usize), (usize, usize))> {
+        let mut decoration = Decoration::new(start, end);
+
+        change
+            .apply(&mut decoration)
+            .then_some((decoration.start, decoration.end))
+    }
+
+    #[test]
+    fn replaced_text() {
+        let change =
+            Change::new(4, (4, 4), &lines("foo bar baz"), &lines("foo x\nbaz"));
+
+        assert_eq!(change.replaced(), Some(((4, 4), (5, 0), "bar ")));
+
+        let change = Change::new(4, (4, 0), &lines("foo"), &lines("foo"));
+
+        assert_eq!(change.replaced(), None);
+    }
+
+    #[test]
+    fn decorations_follow_edits() {
+        // Typing right before and right after `bar`, on line 4
+        let before = lines("foo bar baz");
+        let change = Change::new(4, (4, 4), &before, &lines("foo xbar baz"));
+
+        assert_eq!(apply(&change, (4, 4), (4, 7)), Some(((4, 5), (4, 8))));
+        assert_eq!(apply(&change, (3, 0), (3, 2)), Some(((3, 0), (3, 2))));
+        assert_eq!(apply(&change, (5, 1), (6, 2)), Some(((5, 1), (6, 2))));
+
+        let change = Change::new(4, (4, 7), &before, &lines("foo bary baz"));
+
+        assert_eq!(apply(&change, (4, 4), (4, 7)), Some(((4, 4), (4, 7))));
+
+        // Splitting a line in two
+        let change =
+            Change::new(0, (0, 3), &lines("foo bar"), &lines("foo\n bar"));
+
+        assert_eq!(apply(&change, (0, 4), (0, 7)), Some(((1, 1), (1, 4))));
+        assert_eq!(apply(&change, (1, 0), (1, 3)), Some(((2, 0), (2, 3))));
+        assert_eq!(apply(&change, (0, 0), (0, 7)), Some(((0, 0), (1, 4))));
+
+        // Joining two lines together
+        let change =
+            Change::new(1, (1, 3), &lines("foo\nbar"), &lines("foobar"));
+
+        assert_eq!(apply(&change, (2, 0), (2, 3)), Some(((1, 3), (1, 6))));
+        assert_eq!(apply(&change, (3, 0), (3, 3)), Some(((2, 0), (2, 3))));
+
+        // Removing the decorated text
+        let change = Change::new(0, (0, 4), &before, &lines("foo baz"));
+
+        assert_eq!(apply(&change, (0, 4), (0, 7)), None);
+        assert_eq!(apply(&change, (0, 8), (0, 11)), Some(((0, 4), (0, 7))));
+        assert_eq!(apply(&change, (0, 2), (0, 6)), Some(((0, 2), (0, 4))));
+
+        // Removing one of two equal words
+        let before = lines("bar bar");
+        let change = Change::new(0, (0, 0), &before, &lines("bar"));
+
+        assert_eq!(apply(&change, (0, 4), (0, 7)), Some(((0, 0), (0, 3))));
+    }
+}
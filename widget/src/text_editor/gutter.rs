@@ -0,0 +1,84 @@
+use crate::core::Color;
+
+/// The kind of line numbers shown in the gutter of a [`TextEditor`].
+///
+/// [`TextEditor`]: super::TextEditor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineNumbers {
+    /// The number of every line, starting at 1.
+    #[default]
+    Absolute,
+    /// The distance of every line to the line of the cursor; which shows
+    /// its absolute number.
+    Relative,
+}
+
+impl LineNumbers {
+    /// Returns the label of the given line, with the cursor at the given
+    /// line.
+    pub(super) fn label(self, line: usize, cursor: usize) -> String {
+        match self {
+            Self::Relative if line != cursor => line.abs_diff(cursor),
+            Self::Absolute | Self::Relative => line + 1,
+        }
+        .to_string()
+    }
+}
+
+/// A decoration in the gutter of a [`TextEditor`] next to a line; like a
+/// breakpoint or a diagnostic.
+///
+/// [`TextEditor`]: super::TextEditor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Marker {
+    /// A filled circle.
+    Dot(Color),
+    /// A vertical bar along the line.
+    Bar(Color),
+}
+
+/// The widths of the columns of the gutter of a [`TextEditor`].
+///
+/// [`TextEditor`]: super::TextEditor
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct Gutter {
+    pub markers: f32,
+    pub numbers: f32,
+    pub folds: f32,
+    pub spacing: f32,
+}
+
+impl Gutter {
+    /// Returns the total width of the [`Gutter`].
+    pub fn width(&self) -> f32 {
+        let columns = self.markers + self.numbers + self.folds;
+
+        if columns > 0.0 {
+            columns + self.spacing
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns whether the given horizontal position is inside the column
+    /// of the fold indicators.
+    pub fn is_fold_column(&self, x: f32) -> bool {
+        let start = self.markers + self.numbers;
+
+        self.folds > 0.0 && x >= start && x < start + self.folds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_number_labels() {
+        assert_eq!(LineNumbers::Absolute.label(0, 4), "1");
+        assert_eq!(LineNumbers::Absolute.label(4, 4), "5");
+        assert_eq!(LineNumbers::Relative.label(1, 4), "3");
+        assert_eq!(LineNumbers::Relative.label(4, 4), "5");
+        assert_eq!(LineNumbers::Relative.label(7, 4), "3");
+    }
+}
@@ -0,0 +1,384 @@
+use crate::core::text::highlighter;
+
+use std::iter;
+use std::ops::Range;
+use std::vec;
+
+/// A search query for the [`Content`] of a [`TextEditor`].
+///
+/// [`Content`]: super::Content
+/// [`TextEditor`]: super::TextEditor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pattern: String,
+    is_regex: bool,
+    is_case_sensitive: bool,
+    is_whole_word: bool,
+}
+
+impl Query {
+    /// Creates a new [`Query`] that matches the given text.
+    ///
+    /// By default, a [`Query`] is case insensitive and matches
+    /// partial words.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            pattern: text.into(),
+            is_regex: false,
+            is_case_sensitive: false,
+            is_whole_word: false,
+        }
+    }
+
+    /// Creates a new [`Query`] that matches the given regular expression.
+    ///
+    /// An invalid regular expression matches nothing.
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: impl Into<String>) -> Self {
+        Self {
+            is_regex: true,
+            ..Self::new(pattern)
+        }
+    }
+
+    /// Sets whether the [`Query`] is case sensitive.
+    pub fn case_sensitive(mut self, is_case_sensitive: bool) -> Self {
+        self.is_case_sensitive = is_case_sensitive;
+        self
+    }
+
+    /// Sets whether the [`Query`] only matches whole words.
+    pub fn whole_word(mut self, is_whole_word: bool) -> Self {
+        self.is_whole_word = is_whole_word;
+        self
+    }
+
+    /// Returns the pattern of the [`Query`].
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+}
+
+/// A match of a [`Query`] in the [`Content`] of a [`TextEditor`].
+///
+/// [`Content`]: super::Content
+/// [`TextEditor`]: super::TextEditor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The index of the line of the [`Match`].
+    pub line: usize,
+    /// The byte range of the [`Match`] in its line.
+    pub range: Range<usize>,
+}
+
+/// A compiled [`Query`].
+#[derive(Debug)]
+pub(super) enum Matcher {
+    Plain {
+        pattern: Vec<char>,
+        is_case_sensitive: bool,
+        is_whole_word: bool,
+    },
+    #[cfg(feature = "regex")]
+    Regex {
+        regex: regex::Regex,
+        is_whole_word: bool,
+    },
+}
+
+impl Matcher {
+    /// Compiles the given [`Query`], returning `None` if it matches nothing.
+    pub fn new(query: &Query) -> Option<Self> {
+        if query.pattern.is_empty() {
+            return None;
+        }
+
+        #[cfg(feature = "regex")]
+        if query.is_regex {
+            return regex::RegexBuilder::new(&query.pattern)
+                .case_insensitive(!query.is_case_sensitive)
+                .build()
+                .ok()
+                .map(|regex| Self::Regex {
+                    regex,
+                    is_whole_word: query.is_whole_word,
+                });
+        }
+
+        Some(Self::Plain {
+            pattern: query.pattern.chars().collect(),
+            is_case_sensitive: query.is_case_sensitive,
+            is_whole_word: query.is_whole_word,
+        })
+    }
+
+    /// Finds all the non-overlapping matches in the given line.
+    pub fn find(&self, line: &str) -> Vec<Range<usize>> {
+        let mut matches = Vec::new();
+
+        match self {
+            Self::Plain {
+                pattern,
+                is_case_sensitive,
+                is_whole_word,
+            } => {
+                let mut start = 0;
+
+                while start < line.len() {
+                    match match_at(line, start, pattern, *is_case_sensitive)
+                        .filter(|end| {
+                            !is_whole_word || is_word(line, start..*end)
+                        }) {
+                        Some(end) => {
+                            matches.push(start..end);
+                            start = end;
+                        }
+                        None => {
+                            start += line[start..]
+                                .chars()
+                                .next()
+                                .map_or(1, char::len_utf8);
+                        }
+                    }
+                }
+            }
+            #[cfg(feature = "regex")]
+            Self::Regex {
+                regex,
+                is_whole_word,
+            } => {
+                matches.extend(
+                    regex.find_iter(line).map(|found| found.range()).filter(
+                        |range| {
+                            !range.is_empty()
+                                && (!is_whole_word
+                                    || is_word(line, range.clone()))
+                        },
+                    ),
+                );
+            }
+        }
+
+        matches
+    }
+
+    /// Returns the replacement of the match at the given range of a line.
+    ///
+    /// Regular expressions expand any captures in the replacement; like
+    /// `$1` or `${name}`.
+    #[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+    pub fn replacement(
+        &self,
+        line: &str,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> String {
+        match self {
+            Self::Plain { .. } => replacement.to_owned(),
+            #[cfg(feature = "regex")]
+            Self::Regex { regex, .. } => {
+                let mut expanded = String::new();
+
+                if let Some(captures) = regex.captures_at(line, range.start) {
+                    captures.expand(replacement, &mut expanded);
+                }
+
+                expanded
+            }
+        }
+    }
+}
+
+/// Returns the end of the pattern, if it matches the line at the given start.
+fn match_at(
+    line: &str,
+    start: usize,
+    pattern: &[char],
+    is_case_sensitive: bool,
+) -> Option<usize> {
+    let mut chars = line[start..].char_indices();
+
+    for expected in pattern {
+        let (_, c) = chars.next()?;
+
+        let is_equal = if is_case_sensitive {
+            c == *expected
+        } else {
+            c.to_lowercase().eq(expected.to_lowercase())
+        };
+
+        if !is_equal {
+            return None;
+        }
+    }
+
+    Some(chars.next().map_or(line.len(), |(i, _)| start + i))
+}
+
+/// Returns whether the given range of a line is a whole word.
+fn is_word(line: &str, range: Range<usize>) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    !line[..range.start]
+        .chars()
+        .next_back()
+        .is_some_and(is_word_char)
+        && !line[range.end..].chars().next().is_some_and(is_word_char)
+}
+
+/// A [`highlighter::Highlighter`] that highlights the matches of a [`Query`]
+/// on top of another one.
+#[derive(Debug)]
+pub(super) struct Highlighter<H> {
+    inner: H,
+    query: Option<Query>,
+    matcher: Option<Matcher>,
+    current_line: usize,
+    is_active: bool,
+}
+
+/// The highlight of a [`Highlighter`].
+#[derive(Debug)]
+pub(super) enum Highlight<T> {
+    Match,
+    Inner(T),
+}
+
+impl<H: highlighter::Highlighter> Highlighter<H> {
+    /// Returns the current [`Query`] of the [`Highlighter`].
+    pub fn query(&self) -> Option<&Query> {
+        self.query.as_ref()
+    }
+
+    /// Highlights the matches of the given [`Query`], if any.
+    pub fn search(&mut self, query: Option<Query>) {
+        self.matcher = query.as_ref().and_then(Matcher::new);
+        self.query = query;
+
+        // Every line needs to be highlighted again
+        self.is_active = true;
+        self.current_line = 0;
+        self.inner.change_line(0);
+    }
+}
+
+impl<H: highlighter::Highlighter> highlighter::Highlighter for Highlighter<H> {
+    type Settings = H::Settings;
+    type Highlight = Highlight<H::Highlight>;
+
+    type Iterator<'a> = iter::Chain<
+        iter::Map<
+            H::Iterator<'a>,
+            fn((Range<usize>, H::Highlight)) -> (Range<usize>, Self::Highlight),
+        >,
+        vec::IntoIter<(Range<usize>, Self::Highlight)>,
+    >;
+
+    fn new(settings: &Self::Settings) -> Self {
+        Self {
+            inner: H::new(settings),
+            query: None,
+            matcher: None,
+            current_line: 0,
+            is_active: false,
+        }
+    }
+
+    fn update(&mut self, new_settings: &Self::Settings) {
+        self.inner.update(new_settings);
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.inner.change_line(line);
+        self.current_line = self.current_line.min(line);
+    }
+
+    fn highlight_line(&mut self, line: &str) -> Self::Iterator<'_> {
+        self.current_line = self.current_line().saturating_add(1);
+
+        let matches = self
+            .matcher
+            .as_ref()
+            .map(|matcher| matcher.find(line))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|range| (range, Highlight::Match))
+            .collect::<Vec<_>>();
+
+        let inner: fn(_) -> _ =
+            |(range, highlight)| (range, Highlight::Inner(highlight));
+
+        self.inner.highlight_line(line).map(inner).chain(matches)
+    }
+
+    fn current_line(&self) -> usize {
+        if self.is_active {
+            self.inner.current_line().min(self.current_line)
+        } else {
+            self.inner.current_line()
+        }
+    }
+
+    fn fold_ranges(&self) -> Vec<Range<usize>> {
+        self.inner.fold_ranges()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_matches() {
+        let find =
+            |query: Query, line| Matcher::new(&query).unwrap().find(line);
+
+        assert_eq!(
+            find(Query::new("foo"), "Foo food foo"),
+            [0..3, 4..7, 9..12]
+        );
+        assert_eq!(
+            find(Query::new("foo").case_sensitive(true), "Foo food foo"),
+            [4..7, 9..12]
+        );
+        assert_eq!(
+            find(Query::new("foo").whole_word(true), "Foo food foo"),
+            [0..3, 9..12]
+        );
+        assert_eq!(find(Query::new("é"), "cafÉ é"), [3..5, 6..8]);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_matches() {
+        let find =
+            |query: Query, line| Matcher::new(&query).unwrap().find(line);
+
+        assert_eq!(
+            find(Query::regex(r"fo+"), "Foo fo food"),
+            [0..3, 4..6, 7..10]
+        );
+        assert_eq!(
+            find(Query::regex(r"fo+").whole_word(true), "Foo fo food"),
+            [0..3, 4..6]
+        );
+        assert!(find(Query::regex(r"x*"), "abc").is_empty());
+        assert!(Matcher::new(&Query::regex("(")).is_none());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_replacements() {
+        let matcher =
+            Matcher::new(&Query::regex(r"(?<key>\w+)=(\d+)")).unwrap();
+        let line = "a=1, b=2";
+
+        let replaced: Vec<_> = matcher
+            .find(line)
+            .into_iter()
+            .map(|range| matcher.replacement(line, range, "$2:${key}"))
+            .collect();
+
+        assert_eq!(replaced, ["1:a", "2:b"]);
+    }
+}
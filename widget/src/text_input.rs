@@ -31,14 +31,18 @@
 //! }
 //! ```
 mod editor;
+mod history;
+mod mask;
 mod value;
 
 pub mod cursor;
 
 pub use cursor::Cursor;
+pub use mask::Mask;
 pub use value::Value;
 
 use editor::Editor;
+use history::History;
 
 use crate::core::alignment;
 use crate::core::clipboard::{self, Clipboard};
@@ -116,6 +120,7 @@ pub struct TextInput<
     on_input: Option<Box<dyn Fn(String) -> Message + 'a>>,
     on_paste: Option<Box<dyn Fn(String) -> Message + 'a>>,
     on_submit: Option<Message>,
+    mask: Option<Mask>,
     icon: Option<Icon<Renderer::Font>>,
     class: Theme::Class<'a>,
     last_status: Option<Status>,
@@ -147,6 +152,7 @@ where
             on_input: None,
             on_paste: None,
             on_submit: None,
+            mask: None,
             icon: None,
             class: Theme::default(),
             last_status: None,
@@ -223,6 +229,15 @@ where
         self
     }
 
+    /// Sets the [`Mask`] of the [`TextInput`].
+    ///
+    /// Any edit that breaks the constraints of the [`Mask`] is rejected
+    /// before producing a message.
+    pub fn mask(mut self, mask: Mask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
     /// Sets the [`Font`] of the [`TextInput`].
     ///
     /// [`Font`]: text::Renderer::Font
@@ -914,12 +929,17 @@ where
                                 );
                             }
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
-                            editor.delete();
+                            if let Some(contents) = edit(
+                                &mut self.value,
+                                &mut state.cursor,
+                                &mut state.history,
+                                self.mask.as_ref(),
+                                history::Kind::Other,
+                                Editor::delete,
+                            ) {
+                                shell.publish((on_input)(contents));
+                            }
 
-                            let message = (on_input)(editor.contents());
-                            shell.publish(message);
                             shell.capture_event();
 
                             focus.updated_at = Instant::now();
@@ -948,16 +968,24 @@ where
                                 }
                             };
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
-                            editor.paste(content.clone());
+                            if let Some(contents) = edit(
+                                &mut self.value,
+                                &mut state.cursor,
+                                &mut state.history,
+                                self.mask.as_ref(),
+                                history::Kind::Other,
+                                |editor| editor.paste(content.clone()),
+                            ) {
+                                let message =
+                                    if let Some(paste) = &self.on_paste {
+                                        (paste)(contents)
+                                    } else {
+                                        (on_input)(contents)
+                                    };
+
+                                shell.publish(message);
+                            }
 
-                            let message = if let Some(paste) = &self.on_paste {
-                                (paste)(editor.contents())
-                            } else {
-                                (on_input)(editor.contents())
-                            };
-                            shell.publish(message);
                             shell.capture_event();
 
                             state.is_pasting = Some(content);
@@ -965,6 +993,41 @@ where
                             update_cache(state, &self.value);
                             return;
                         }
+                        keyboard::Key::Character(c @ ("z" | "Z" | "y"))
+                            if state.keyboard_modifiers.command() =>
+                        {
+                            let Some(on_input) = &self.on_input else {
+                                return;
+                            };
+
+                            let current = history::Snapshot {
+                                value: self.value.clone(),
+                                cursor: state.cursor,
+                            };
+
+                            let snapshot = if c == "y"
+                                || state.keyboard_modifiers.shift()
+                            {
+                                state.history.redo(current)
+                            } else {
+                                state.history.undo(current)
+                            };
+
+                            if let Some(snapshot) = snapshot {
+                                self.value = snapshot.value;
+                                state.cursor = snapshot.cursor;
+
+                                shell.publish((on_input)(
+                                    self.value.to_string(),
+                                ));
+
+                                focus.updated_at = Instant::now();
+                                update_cache(state, &self.value);
+                            }
+
+                            shell.capture_event();
+                            return;
+                        }
                         keyboard::Key::Character("a")
                             if state.keyboard_modifiers.command() =>
                         {
@@ -994,13 +1057,17 @@ where
                         if let Some(c) =
                             text.chars().next().filter(|c| !c.is_control())
                         {
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
-
-                            editor.insert(c);
+                            if let Some(contents) = edit(
+                                &mut self.value,
+                                &mut state.cursor,
+                                &mut state.history,
+                                self.mask.as_ref(),
+                                history::Kind::Insert(c),
+                                |editor| editor.insert(c),
+                            ) {
+                                shell.publish((on_input)(contents));
+                            }
 
-                            let message = (on_input)(editor.contents());
-                            shell.publish(message);
                             shell.capture_event();
 
                             focus.updated_at = Instant::now();
@@ -1035,12 +1102,17 @@ where
                                 }
                             }
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
-                            editor.backspace();
+                            if let Some(contents) = edit(
+                                &mut self.value,
+                                &mut state.cursor,
+                                &mut state.history,
+                                self.mask.as_ref(),
+                                history::Kind::Delete,
+                                Editor::backspace,
+                            ) {
+                                shell.publish((on_input)(contents));
+                            }
 
-                            let message = (on_input)(editor.contents());
-                            shell.publish(message);
                             shell.capture_event();
 
                             focus.updated_at = Instant::now();
@@ -1068,12 +1140,17 @@ where
                                 }
                             }
 
-                            let mut editor =
-                                Editor::new(&mut self.value, &mut state.cursor);
-                            editor.delete();
+                            if let Some(contents) = edit(
+                                &mut self.value,
+                                &mut state.cursor,
+                                &mut state.history,
+                                self.mask.as_ref(),
+                                history::Kind::Delete,
+                                Editor::delete,
+                            ) {
+                                shell.publish((on_input)(contents));
+                            }
 
-                            let message = (on_input)(editor.contents());
-                            shell.publish(message);
                             shell.capture_event();
 
                             focus.updated_at = Instant::now();
@@ -1280,15 +1357,20 @@ where
                             return;
                         };
 
-                        let mut editor =
-                            Editor::new(&mut self.value, &mut state.cursor);
-                        editor.paste(Value::new(text));
+                        if let Some(contents) = edit(
+                            &mut self.value,
+                            &mut state.cursor,
+                            &mut state.history,
+                            self.mask.as_ref(),
+                            history::Kind::Other,
+                            |editor| editor.paste(Value::new(text)),
+                        ) {
+                            shell.publish((on_input)(contents));
+                        }
 
                         focus.updated_at = Instant::now();
                         state.is_pasting = None;
 
-                        let message = (on_input)(editor.contents());
-                        shell.publish(message);
                         shell.capture_event();
 
                         update_cache(state, &self.value);
@@ -1453,6 +1535,7 @@ pub struct State<P: text::Paragraph> {
     preedit: Option<input_method::Preedit>,
     last_click: Option<mouse::Click>,
     cursor: Cursor,
+    history: History,
     keyboard_modifiers: keyboard::Modifiers,
     // TODO: Add stateful horizontal scrolling offset
 }
@@ -1630,6 +1713,51 @@ fn find_cursor_position<P: text::Paragraph>(
     )
 }
 
+/// Performs an edit on the [`Value`] of a [`TextInput`], applying its
+/// [`Mask`] and recording the edit in its [`History`].
+///
+/// Returns the new contents of the [`TextInput`], unless the edit is
+/// rejected by the [`Mask`].
+fn edit<'a>(
+    value: &'a mut Value,
+    cursor: &'a mut Cursor,
+    history: &mut History,
+    mask: Option<&Mask>,
+    kind: history::Kind,
+    f: impl FnOnce(&mut Editor<'a>),
+) -> Option<String> {
+    let before = history::Snapshot {
+        value: value.clone(),
+        cursor: *cursor,
+    };
+
+    let mut editor = Editor::new(value, cursor);
+    f(&mut editor);
+
+    let (value, cursor) = editor.into_parts();
+
+    if let Some(mask) = mask {
+        match mask.format(value, cursor.end(value)) {
+            Some((formatted, position)) => {
+                *value = formatted;
+                cursor.move_to(position);
+            }
+            // Edits that remove text are never rejected
+            None if value.len() < before.value.len() => {}
+            None => {
+                *value = before.value;
+                *cursor = before.cursor;
+
+                return None;
+            }
+        }
+    }
+
+    history.record(before, value, kind);
+
+    Some(value.to_string())
+}
+
 fn replace_paragraph<Renderer>(
     renderer: &Renderer,
     state: &mut State<Renderer::Paragraph>,
@@ -10,8 +10,8 @@ impl<'a> Editor<'a> {
         Editor { value, cursor }
     }
 
-    pub fn contents(&self) -> String {
-        self.value.to_string()
+    pub fn into_parts(self) -> (&'a mut Value, &'a mut Cursor) {
+        (self.value, self.cursor)
     }
 
     pub fn insert(&mut self, character: char) {
@@ -0,0 +1,230 @@
+use crate::core::time::{Duration, Instant};
+use crate::text_input::{Cursor, Value};
+
+/// The edit history of a [`TextInput`].
+///
+/// [`TextInput`]: super::TextInput
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    group: Option<Group>,
+}
+
+/// The value and cursor of a [`TextInput`] at some point in time.
+///
+/// [`TextInput`]: super::TextInput
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub value: Value,
+    pub cursor: Cursor,
+}
+
+/// The kind of an edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Insert(char),
+    Delete,
+    Other,
+}
+
+/// The group of edits that is currently being recorded.
+#[derive(Debug, Clone)]
+struct Group {
+    kind: Kind,
+    updated_at: Instant,
+    value: String,
+}
+
+impl History {
+    /// The maximum amount of groups of edits that can be undone.
+    const DEPTH: usize = 100;
+
+    /// The time after which a new edit starts a new group.
+    const GROUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// Records an edit that turned the `before` [`Snapshot`] into the
+    /// given [`Value`].
+    ///
+    /// Consecutive edits of the same kind are grouped together, until a
+    /// new word is typed.
+    pub fn record(&mut self, before: Snapshot, after: &Value, kind: Kind) {
+        self.record_at(before, after, kind, Instant::now());
+    }
+
+    fn record_at(
+        &mut self,
+        before: Snapshot,
+        after: &Value,
+        kind: Kind,
+        now: Instant,
+    ) {
+        let value = before.value.to_string();
+        let new_value = after.to_string();
+
+        if value == new_value {
+            return;
+        }
+
+        let continues = self.group.as_ref().is_some_and(|group| {
+            group.value == value
+                && now.duration_since(group.updated_at) <= Self::GROUP_TIMEOUT
+                && before.cursor.selection(&before.value).is_none()
+                && match (group.kind, kind) {
+                    (Kind::Insert(last), Kind::Insert(c)) => {
+                        c.is_whitespace() || !last.is_whitespace()
+                    }
+                    (Kind::Delete, Kind::Delete) => true,
+                    _ => false,
+                }
+        });
+
+        if !continues {
+            if self.undo.len() == Self::DEPTH {
+                let _ = self.undo.remove(0);
+            }
+
+            self.undo.push(before);
+        }
+
+        self.redo.clear();
+        self.group = Some(Group {
+            kind,
+            updated_at: now,
+            value: new_value,
+        });
+    }
+
+    /// Undoes the last group of edits, returning the [`Snapshot`] to
+    /// restore, if any.
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.undo.pop()?;
+
+        self.redo.push(current);
+        self.group = None;
+
+        Some(snapshot)
+    }
+
+    /// Redoes the last undone group of edits, returning the [`Snapshot`]
+    /// to restore, if any.
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.redo.pop()?;
+
+        self.undo.push(current);
+        self.group = None;
+
+        Some(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(value: &str) -> Snapshot {
+        let value = Value::new(value);
+        let mut cursor = Cursor::default();
+        cursor.move_to(value.len());
+
+        Snapshot { value, cursor }
+    }
+
+    /// Records typing the given character at the end of the given text,
+    /// returning the new text.
+    fn type_char(
+        history: &mut History,
+        text: &str,
+        c: char,
+        now: Instant,
+    ) -> String {
+        let after = format!("{text}{c}");
+
+        history.record_at(
+            snapshot(text),
+            &Value::new(&after),
+            Kind::Insert(c),
+            now,
+        );
+
+        after
+    }
+
+    fn undo(history: &mut History, current: &str) -> Option<String> {
+        history
+            .undo(snapshot(current))
+            .map(|snapshot| snapshot.value.to_string())
+    }
+
+    #[test]
+    fn consecutive_typing_is_undone_at_once() {
+        let mut history = History::default();
+        let now = Instant::now();
+
+        let mut text = String::new();
+
+        for c in "abc".chars() {
+            text = type_char(&mut history, &text, c, now);
+        }
+
+        assert_eq!(undo(&mut history, &text), Some(String::new()));
+        assert_eq!(undo(&mut history, ""), None);
+    }
+
+    #[test]
+    fn pauses_split_groups() {
+        let mut history = History::default();
+        let now = Instant::now();
+
+        let text = type_char(&mut history, "", 'a', now);
+        let text = type_char(
+            &mut history,
+            &text,
+            'b',
+            now + History::GROUP_TIMEOUT + Duration::from_millis(1),
+        );
+
+        assert_eq!(undo(&mut history, &text), Some("a".to_owned()));
+        assert_eq!(undo(&mut history, "a"), Some(String::new()));
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut history = History::default();
+        let now = Instant::now();
+
+        for i in 0..History::DEPTH + 10 {
+            history.record_at(
+                snapshot(&i.to_string()),
+                &Value::new(&(i + 1).to_string()),
+                Kind::Other,
+                now,
+            );
+        }
+
+        let mut current = (History::DEPTH + 10).to_string();
+        let mut steps = 0;
+
+        while let Some(previous) = undo(&mut history, &current) {
+            current = previous;
+            steps += 1;
+        }
+
+        assert_eq!(steps, History::DEPTH);
+        assert_eq!(current, "10");
+    }
+
+    #[test]
+    fn new_edits_clear_redo() {
+        let mut history = History::default();
+        let now = Instant::now();
+
+        let text = type_char(&mut history, "", 'a', now);
+
+        assert_eq!(undo(&mut history, &text), Some(String::new()));
+
+        let _ = type_char(&mut history, "", 'b', now);
+
+        assert!(history.redo(snapshot("b")).is_none());
+    }
+}
@@ -0,0 +1,326 @@
+use crate::text_input::Value;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use std::fmt;
+use std::sync::Arc;
+
+/// A set of constraints on the value of a [`TextInput`].
+///
+/// Every edit is checked against the [`Mask`] before the `on_input` message
+/// of the [`TextInput`] is produced. Edits that break the constraints are
+/// rejected, unless they remove text; so invalid text can always be erased.
+///
+/// # Example
+/// ```no_run
+/// # mod iced { pub mod widget { pub use iced_widget::*; } pub use iced_widget::Renderer; pub use iced_widget::core::*; }
+/// # pub type Element<'a, Message> = iced_widget::core::Element<'a, Message, iced_widget::Theme, iced_widget::Renderer>;
+/// #
+/// use iced::widget::text_input;
+///
+/// struct State {
+///    date: String,
+/// }
+///
+/// #[derive(Debug, Clone)]
+/// enum Message {
+///     DateChanged(String)
+/// }
+///
+/// fn view(state: &State) -> Element<'_, Message> {
+///     text_input("YYYY-MM-DD", &state.date)
+///         .mask(text_input::Mask::pattern("____-__-__").numeric())
+///         .on_input(Message::DateChanged)
+///         .into()
+/// }
+/// ```
+///
+/// [`TextInput`]: super::TextInput
+#[derive(Clone, Default)]
+pub struct Mask {
+    pattern: Option<Vec<Slot>>,
+    filter: Option<Arc<dyn Fn(char) -> bool + Send + Sync>>,
+    validator: Option<Arc<dyn Fn(&str) -> bool + Send + Sync>>,
+    max_length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Placeholder,
+    Literal(char),
+}
+
+impl Mask {
+    /// The character used for placeholders in a [`Mask::pattern`].
+    pub const PLACEHOLDER: char = '_';
+
+    /// Creates a new [`Mask`] without any constraints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`Mask`] that follows a fixed format.
+    ///
+    /// Every [`Mask::PLACEHOLDER`] in the pattern can be filled with a
+    /// single character, while any other character is inserted
+    /// automatically as the user types. For instance, `____-__-__`
+    /// turns `20240521` into `2024-05-21`.
+    pub fn pattern(pattern: &str) -> Self {
+        Self {
+            pattern: Some(
+                pattern
+                    .chars()
+                    .map(|c| {
+                        if c == Self::PLACEHOLDER {
+                            Slot::Placeholder
+                        } else {
+                            Slot::Literal(c)
+                        }
+                    })
+                    .collect(),
+            ),
+            ..Self::default()
+        }
+    }
+
+    /// Only accepts ASCII digits in the [`Mask`].
+    pub fn numeric(self) -> Self {
+        self.filter(|c| c.is_ascii_digit())
+    }
+
+    /// Only accepts the characters for which the given function returns
+    /// `true` in the [`Mask`].
+    ///
+    /// The literal characters of a [`Mask::pattern`] are not filtered.
+    pub fn filter(
+        mut self,
+        filter: impl Fn(char) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Only accepts the values for which the given function returns `true`
+    /// in the [`Mask`]; like the matches of a regular expression.
+    ///
+    /// The validator is called with every intermediate value while typing,
+    /// so it should accept incomplete values as well.
+    pub fn validate(
+        mut self,
+        validator: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.validator = Some(Arc::new(validator));
+        self
+    }
+
+    /// Sets the maximum amount of characters of the [`Mask`].
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Applies the [`Mask`] to the given text, returning its formatted
+    /// version; or `None` if the text breaks the constraints.
+    pub fn apply(&self, text: &str) -> Option<String> {
+        let value = Value::new(text);
+
+        self.format(&value, value.len())
+            .map(|(value, _cursor)| value.to_string())
+    }
+
+    /// Applies the [`Mask`] to the given [`Value`], returning its formatted
+    /// version and the new position of the given cursor.
+    pub(super) fn format(
+        &self,
+        value: &Value,
+        cursor: usize,
+    ) -> Option<(Value, usize)> {
+        let text = value.to_string();
+        let graphemes: Vec<&str> = text.graphemes(true).collect();
+
+        let is_literal = match &self.pattern {
+            Some(pattern) => literals(pattern, &graphemes),
+            None => vec![false; graphemes.len()],
+        };
+
+        // Literals are inserted again by the pattern
+        let cursor = is_literal[..cursor.min(graphemes.len())]
+            .iter()
+            .filter(|is_literal| !**is_literal)
+            .count();
+
+        let input: Vec<&str> = graphemes
+            .iter()
+            .zip(&is_literal)
+            .filter(|(_, is_literal)| !**is_literal)
+            .map(|(grapheme, _)| *grapheme)
+            .collect();
+
+        if let Some(filter) = &self.filter
+            && !input
+                .iter()
+                .all(|grapheme| grapheme.chars().all(|c| filter(c)))
+        {
+            return None;
+        }
+
+        let (text, cursor) = match &self.pattern {
+            Some(pattern) => layout(pattern, &input, cursor)?,
+            None => (input.concat(), cursor),
+        };
+
+        let value = Value::new(&text);
+
+        if self.max_length.is_some_and(|max| value.len() > max) {
+            return None;
+        }
+
+        if let Some(validator) = &self.validator
+            && !validator(&text)
+        {
+            return None;
+        }
+
+        Some((value, cursor))
+    }
+}
+
+/// Returns whether each of the given graphemes is a literal of the pattern.
+///
+/// The pattern is walked along the graphemes; so only a grapheme matching
+/// the literal at its own position is a literal, while any other one fills
+/// the next placeholder.
+fn literals(pattern: &[Slot], graphemes: &[&str]) -> Vec<bool> {
+    let mut slot = 0;
+
+    graphemes
+        .iter()
+        .map(|grapheme| {
+            if let Some(Slot::Literal(literal)) = pattern.get(slot) {
+                let mut chars = grapheme.chars();
+
+                if chars.next() == Some(*literal) && chars.next().is_none() {
+                    slot += 1;
+
+                    return true;
+                }
+            }
+
+            // Literals that were not typed are inserted by the pattern
+            while let Some(Slot::Literal(_)) = pattern.get(slot) {
+                slot += 1;
+            }
+
+            slot += 1;
+
+            false
+        })
+        .collect()
+}
+
+/// Lays out the given input in a pattern, returning the formatted text and
+/// the position of the cursor after the given amount of input graphemes.
+fn layout(
+    pattern: &[Slot],
+    input: &[&str],
+    cursor: usize,
+) -> Option<(String, usize)> {
+    let mut text = String::new();
+    let mut consumed = 0;
+    let mut length = 0;
+    let mut position = 0;
+
+    for slot in pattern {
+        if consumed == input.len() {
+            break;
+        }
+
+        match slot {
+            Slot::Literal(c) => {
+                text.push(*c);
+            }
+            Slot::Placeholder => {
+                text.push_str(input[consumed]);
+                consumed += 1;
+            }
+        }
+
+        length += 1;
+
+        if consumed == cursor && position == 0 {
+            position = length;
+        }
+    }
+
+    if consumed < input.len() {
+        return None;
+    }
+
+    Some((text, if cursor == 0 { 0 } else { position }))
+}
+
+impl fmt::Debug for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mask")
+            .field("pattern", &self.pattern)
+            .field("has_filter", &self.filter.is_some())
+            .field("has_validator", &self.validator.is_some())
+            .field("max_length", &self.max_length)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_inserts_literals() {
+        let mask = Mask::pattern("____-__-__").numeric();
+
+        assert_eq!(mask.apply("2024"), Some("2024".to_owned()));
+        assert_eq!(mask.apply("20240"), Some("2024-0".to_owned()));
+        assert_eq!(mask.apply("2024-05-21"), Some("2024-05-21".to_owned()));
+        assert_eq!(mask.apply("202405211"), None);
+        assert_eq!(mask.apply("2024a"), None);
+    }
+
+    #[test]
+    fn literals_are_only_skipped_in_place() {
+        let mask = Mask::pattern("+1 (___) ___-____").numeric();
+
+        assert_eq!(mask.apply("1"), Some("+1 (1".to_owned()));
+        assert_eq!(mask.apply("111"), Some("+1 (111".to_owned()));
+        assert_eq!(
+            mask.apply("+1 (555) 123-4567"),
+            Some("+1 (555) 123-4567".to_owned())
+        );
+        assert_eq!(mask.apply("5551"), Some("+1 (555) 1".to_owned()));
+        assert_eq!(mask.apply("+1 (5-"), None);
+    }
+
+    #[test]
+    fn cursor_skips_literals() {
+        let mask = Mask::pattern("____-__");
+        let value = Value::new("20240");
+
+        let (value, cursor) = mask.format(&value, 5).unwrap();
+
+        assert_eq!(value.to_string(), "2024-0");
+        assert_eq!(cursor, 6);
+    }
+
+    #[test]
+    fn constraints() {
+        let mask = Mask::new().numeric().max_length(3);
+
+        assert_eq!(mask.apply("123"), Some("123".to_owned()));
+        assert_eq!(mask.apply("1234"), None);
+        assert_eq!(mask.apply("12a"), None);
+
+        let mask = Mask::new().validate(|text| text.starts_with('#'));
+
+        assert_eq!(mask.apply("#fff"), Some("#fff".to_owned()));
+        assert_eq!(mask.apply("fff"), None);
+    }
+}
@@ -0,0 +1,1656 @@
+//! Display hierarchical data as a tree of expandable nodes.
+//!
+//! # Example
+//! ```no_run
+//! # mod iced { pub mod widget { pub use iced_widget::*; } }
+//! # pub type Element<'a, Message> = iced_widget::core::Element<'a, Message, iced_widget::Theme, iced_widget::Renderer>;
+//! use iced::widget::{text, tree_view};
+//! use iced::widget::tree_view::Node;
+//!
+//! struct State {
+//!    folders: Vec<Folder>,
+//! }
+//!
+//! struct Folder {
+//!     name: String,
+//!     files: Vec<String>,
+//!     is_expanded: bool,
+//! }
+//!
+//! #[derive(Debug, Clone)]
+//! enum Message {
+//!     Toggled((usize, Option<usize>), bool),
+//! }
+//!
+//! fn view(state: &State) -> Element<'_, Message> {
+//!     tree_view(state.folders.iter().enumerate().map(|(i, folder)| {
+//!         Node::new((i, None), text(&folder.name))
+//!             .expanded(folder.is_expanded)
+//!             .children(move || {
+//!                 folder.files.iter().enumerate().map(move |(j, file)| {
+//!                     Node::new((i, Some(j)), text(file))
+//!                 })
+//!             })
+//!     }))
+//!     .on_toggle(Message::Toggled)
+//!     .into()
+//! }
+//! ```
+use crate::core::keyboard;
+use crate::core::keyboard::key;
+use crate::core::layout;
+use crate::core::mouse;
+use crate::core::overlay;
+use crate::core::renderer;
+use crate::core::touch;
+use crate::core::widget;
+use crate::core::widget::operation;
+use crate::core::widget::tree::{self, Tree};
+use crate::core::{
+    self, Background, Border, Clipboard, Color, Element, Event, Layout, Length,
+    Padding, Pixels, Point, Rectangle, Shell, Size, Vector, Widget,
+};
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Creates a new [`TreeView`] with the given root nodes.
+pub fn tree_view<'a, Key, Message, Theme, Renderer>(
+    nodes: impl IntoIterator<Item = Node<'a, Key, Message, Theme, Renderer>>,
+) -> TreeView<'a, Key, Message, Theme, Renderer>
+where
+    Key: Hash,
+    Theme: Catalog,
+    Renderer: core::Renderer,
+{
+    TreeView::new(nodes)
+}
+
+/// A hierarchical list of nodes that can be expanded, collapsed, selected,
+/// and reordered.
+///
+/// Every [`Node`] is identified by a key, which is used in the messages
+/// produced by the [`TreeView`]. The expanded and selected nodes are part of
+/// your application state; so the [`TreeView`] only notifies you when they
+/// should change.
+pub struct TreeView<
+    'a,
+    Key,
+    Message,
+    Theme = crate::Theme,
+    Renderer = crate::Renderer,
+> where
+    Theme: Catalog,
+{
+    id: Option<widget::Id>,
+    entries: Vec<Entry<Key>>,
+    contents: Vec<Element<'a, Message, Theme, Renderer>>,
+    width: Length,
+    indent: f32,
+    padding: Padding,
+    multi_select: bool,
+    selection: Option<Vec<(u64, Key)>>,
+    on_toggle: Option<Box<dyn Fn(Key, bool) -> Message + 'a>>,
+    on_select: Option<Box<dyn Fn(Vec<Key>) -> Message + 'a>>,
+    on_reorder: Option<Box<dyn Fn(Reorder<Key>) -> Message + 'a>>,
+    class: Theme::Class<'a>,
+}
+
+/// A visible [`Node`] of a [`TreeView`].
+#[derive(Debug)]
+struct Entry<Key> {
+    id: u64,
+    key: Key,
+    depth: usize,
+    parent: Option<usize>,
+    is_branch: bool,
+    is_expanded: bool,
+    is_selected: bool,
+}
+
+impl<'a, Key, Message, Theme, Renderer>
+    TreeView<'a, Key, Message, Theme, Renderer>
+where
+    Theme: Catalog,
+    Renderer: core::Renderer,
+{
+    /// Creates a new [`TreeView`] with the given root nodes.
+    pub fn new(
+        nodes: impl IntoIterator<Item = Node<'a, Key, Message, Theme, Renderer>>,
+    ) -> Self
+    where
+        Key: Hash,
+    {
+        let mut entries = Vec::new();
+        let mut contents = Vec::new();
+
+        flatten(nodes, 0, None, &mut entries, &mut contents);
+
+        Self {
+            id: None,
+            entries,
+            contents,
+            width: Length::Shrink,
+            indent: 20.0,
+            padding: Padding::from([2, 5]),
+            multi_select: false,
+            selection: None,
+            on_toggle: None,
+            on_select: None,
+            on_reorder: None,
+            class: Theme::default(),
+        }
+    }
+
+    /// Sets the [`widget::Id`] of the [`TreeView`]; which can be used to
+    /// focus it.
+    pub fn id(mut self, id: impl Into<widget::Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the width of the [`TreeView`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Sets the indentation of each level of the [`TreeView`].
+    pub fn indent(mut self, indent: impl Into<Pixels>) -> Self {
+        self.indent = indent.into().0;
+        self
+    }
+
+    /// Sets the [`Padding`] of each node of the [`TreeView`].
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Sets whether multiple nodes of the [`TreeView`] can be selected at
+    /// once; by holding the command key to add a node to the selection, or
+    /// the shift key to select a range of nodes.
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Sets the keys of every selected [`Node`] of the [`TreeView`];
+    /// including the ones hidden inside collapsed nodes.
+    ///
+    /// Adding a [`Node`] to the selection with the command key keeps these
+    /// selected. Otherwise, only the visible selected nodes are kept.
+    pub fn selection(mut self, keys: impl IntoIterator<Item = Key>) -> Self
+    where
+        Key: Hash,
+    {
+        self.selection =
+            Some(keys.into_iter().map(|key| (id(&key), key)).collect());
+        self
+    }
+
+    /// Sets the message that should be produced when a [`Node`] of the
+    /// [`TreeView`] is expanded or collapsed.
+    ///
+    /// The closure will receive the key of the [`Node`] and whether it
+    /// should be expanded.
+    pub fn on_toggle(
+        mut self,
+        on_toggle: impl Fn(Key, bool) -> Message + 'a,
+    ) -> Self {
+        self.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    /// Sets the message that should be produced when the selection of the
+    /// [`TreeView`] changes; either by clicking a [`Node`] or with the
+    /// arrow keys.
+    ///
+    /// The closure will receive the keys of the new selected nodes.
+    pub fn on_select(
+        mut self,
+        on_select: impl Fn(Vec<Key>) -> Message + 'a,
+    ) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Sets the message that should be produced when a [`Node`] of the
+    /// [`TreeView`] is dragged and dropped somewhere else.
+    ///
+    /// Nodes can only be dragged if this is set.
+    pub fn on_reorder(
+        mut self,
+        on_reorder: impl Fn(Reorder<Key>) -> Message + 'a,
+    ) -> Self {
+        self.on_reorder = Some(Box::new(on_reorder));
+        self
+    }
+
+    /// Sets the style of the [`TreeView`].
+    #[must_use]
+    pub fn style(mut self, style: impl Fn(&Theme) -> Style + 'a) -> Self
+    where
+        Theme::Class<'a>: From<StyleFn<'a, Theme>>,
+    {
+        self.class = (Box::new(style) as StyleFn<'a, Theme>).into();
+        self
+    }
+
+    /// Sets the style class of the [`TreeView`].
+    #[cfg(feature = "advanced")]
+    #[must_use]
+    pub fn class(mut self, class: impl Into<Theme::Class<'a>>) -> Self {
+        self.class = class.into();
+        self
+    }
+
+    /// Returns the bounds of the row of a node, given the bounds of its
+    /// contents.
+    fn row_bounds(&self, bounds: Rectangle, content: Rectangle) -> Rectangle {
+        Rectangle {
+            x: bounds.x,
+            y: content.y - self.padding.top,
+            width: bounds.width,
+            height: content.height + self.padding.vertical(),
+        }
+    }
+
+    /// Returns the bounds of the expand arrow of a node in the given row.
+    fn arrow_bounds(
+        &self,
+        bounds: Rectangle,
+        row: Rectangle,
+        depth: usize,
+    ) -> Rectangle {
+        Rectangle {
+            x: bounds.x + self.padding.left + depth as f32 * self.indent,
+            width: self.indent,
+            ..row
+        }
+    }
+
+    /// Returns the row at the given position, with its bounds.
+    fn row_at(
+        &self,
+        layout: Layout<'_>,
+        position: Point,
+    ) -> Option<(usize, Rectangle)> {
+        let bounds = layout.bounds();
+
+        layout
+            .children()
+            .map(|content| self.row_bounds(bounds, content.bounds()))
+            .enumerate()
+            .find(|(_, row)| row.contains(position))
+    }
+
+    /// Returns where the given dragged row would be dropped at the given
+    /// position, if anywhere.
+    fn drop_target(
+        &self,
+        layout: Layout<'_>,
+        position: Point,
+        dragged: usize,
+    ) -> Option<(usize, Placement)> {
+        let (row, bounds) = self.row_at(layout, position)?;
+        let relative = (position.y - bounds.y) / bounds.height;
+
+        let placement = if self.entries[row].is_branch {
+            if relative < 1.0 / 3.0 {
+                Placement::Before
+            } else if relative > 2.0 / 3.0 {
+                Placement::After
+            } else {
+                Placement::Inside
+            }
+        } else if relative < 0.5 {
+            Placement::Before
+        } else {
+            Placement::After
+        };
+
+        // A node cannot be dropped on itself or its descendants
+        let mut ancestor = Some(row);
+
+        while let Some(current) = ancestor {
+            if current == dragged {
+                return None;
+            }
+
+            ancestor = self.entries[current].parent;
+        }
+
+        Some((row, placement))
+    }
+}
+
+impl<Key, Message, Theme, Renderer> TreeView<'_, Key, Message, Theme, Renderer>
+where
+    Key: Clone,
+    Theme: Catalog,
+{
+    /// Returns the row of the node with the given id, if visible.
+    fn row_of(&self, id: u64) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == id)
+    }
+
+    /// Publishes the expansion toggle of the given row, if it is a branch.
+    fn toggle(&self, row: usize, shell: &mut Shell<'_, Message>) {
+        let entry = &self.entries[row];
+
+        if entry.is_branch
+            && let Some(on_toggle) = &self.on_toggle
+        {
+            shell.publish(on_toggle(entry.key.clone(), !entry.is_expanded));
+        }
+    }
+
+    /// Moves the cursor to the given row and selects it; extending or
+    /// toggling the current selection, if allowed.
+    fn select(
+        &self,
+        state: &mut State,
+        row: usize,
+        extend: bool,
+        toggle: bool,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        state.cursor = Some(self.entries[row].id);
+
+        let Some(on_select) = &self.on_select else {
+            return;
+        };
+
+        let entry = &self.entries[row];
+
+        let keys: Vec<Key> = if self.multi_select && toggle {
+            // Hidden nodes stay selected if the selection is known
+            let mut selection = self.selection.clone().unwrap_or_else(|| {
+                self.entries
+                    .iter()
+                    .filter(|entry| entry.is_selected)
+                    .map(|entry| (entry.id, entry.key.clone()))
+                    .collect()
+            });
+
+            if let Some(index) =
+                selection.iter().position(|(id, _)| *id == entry.id)
+            {
+                let _ = selection.remove(index);
+            } else {
+                selection.push((entry.id, entry.key.clone()));
+            }
+
+            selection.into_iter().map(|(_, key)| key).collect()
+        } else if self.multi_select
+            && extend
+            && let Some(anchor) = state.anchor.and_then(|id| self.row_of(id))
+        {
+            (anchor.min(row)..=anchor.max(row))
+                .map(|row| self.entries[row].key.clone())
+                .collect()
+        } else {
+            vec![entry.key.clone()]
+        };
+
+        if !extend || !self.multi_select {
+            state.anchor = Some(entry.id);
+        }
+
+        shell.publish(on_select(keys));
+    }
+}
+
+/// Flattens the given nodes into entries and contents; only producing the
+/// children of the expanded ones.
+fn flatten<'a, Key, Message, Theme, Renderer>(
+    nodes: impl IntoIterator<Item = Node<'a, Key, Message, Theme, Renderer>>,
+    depth: usize,
+    parent: Option<usize>,
+    entries: &mut Vec<Entry<Key>>,
+    contents: &mut Vec<Element<'a, Message, Theme, Renderer>>,
+) where
+    Key: Hash,
+{
+    for node in nodes {
+        let index = entries.len();
+        let is_branch = node.children.is_some();
+        let is_expanded = is_branch && node.is_expanded;
+
+        entries.push(Entry {
+            id: id(&node.key),
+            key: node.key,
+            depth,
+            parent,
+            is_branch,
+            is_expanded,
+            is_selected: node.is_selected,
+        });
+
+        contents.push(node.content);
+
+        if is_expanded && let Some(children) = node.children {
+            flatten(children(), depth + 1, Some(index), entries, contents);
+        }
+    }
+}
+
+/// Returns the id of the node with the given key.
+fn id<Key: Hash>(key: &Key) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The distance the cursor needs to travel to start dragging a node.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// The state of a [`TreeView`]; where nodes are identified by the hash of
+/// their keys, so they can be found after other nodes expand or collapse.
+#[derive(Debug, Default)]
+struct State {
+    is_focused: bool,
+    cursor: Option<u64>,
+    anchor: Option<u64>,
+    drag: Option<Drag>,
+    last_click: Option<mouse::Click>,
+    modifiers: keyboard::Modifiers,
+    /// The ids of the nodes owning the children of the widget tree, in order.
+    keys: Vec<u64>,
+}
+
+/// A node being dragged.
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    node: u64,
+    origin: Point,
+    is_dragging: bool,
+}
+
+impl operation::Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.is_focused = false;
+    }
+}
+
+impl<Key, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for TreeView<'_, Key, Message, Theme, Renderer>
+where
+    Key: Clone,
+    Theme: Catalog,
+    Renderer: core::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State {
+            keys: self.entries.iter().map(|entry| entry.id).collect(),
+            ..State::default()
+        })
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.contents.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        let state = tree.state.downcast_mut::<State>();
+
+        // Children follow their nodes as the nodes above expand or collapse
+        let mut trees: HashMap<u64, Tree> =
+            state.keys.drain(..).zip(tree.children.drain(..)).collect();
+
+        tree.children = self
+            .entries
+            .iter()
+            .zip(&self.contents)
+            .map(|(entry, content)| {
+                if let Some(mut tree) = trees.remove(&entry.id) {
+                    tree.diff(content.as_widget());
+                    tree
+                } else {
+                    Tree::new(content.as_widget())
+                }
+            })
+            .collect();
+
+        state.keys = self.entries.iter().map(|entry| entry.id).collect();
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: self.width,
+            height: Length::Shrink,
+        }
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let limits = limits.width(self.width);
+        let max = limits.max();
+
+        let mut y = 0.0;
+        let mut width: f32 = 0.0;
+
+        let nodes = self
+            .entries
+            .iter()
+            .zip(&mut self.contents)
+            .zip(&mut tree.children)
+            .map(|((entry, content), tree)| {
+                let x =
+                    self.padding.left + (entry.depth + 1) as f32 * self.indent;
+
+                let limits = layout::Limits::new(
+                    Size::ZERO,
+                    Size::new(
+                        (max.width - x - self.padding.right).max(0.0),
+                        f32::INFINITY,
+                    ),
+                );
+
+                let node = content
+                    .as_widget_mut()
+                    .layout(tree, renderer, &limits)
+                    .move_to((x, y + self.padding.top));
+
+                let size = node.size();
+
+                width = width.max(x + size.width + self.padding.right);
+                y += size.height + self.padding.vertical();
+
+                node
+            })
+            .collect();
+
+        let size =
+            limits.resolve(self.width, Length::Shrink, Size::new(width, y));
+
+        layout::Node::with_children(size, nodes)
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+
+        let is_dragging = state.drag.is_some_and(|drag| drag.is_dragging);
+
+        for ((content, tree), layout) in self
+            .contents
+            .iter_mut()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+        {
+            content.as_widget_mut().update(
+                tree,
+                event,
+                layout,
+                if is_dragging {
+                    cursor.levitate()
+                } else {
+                    cursor
+                },
+                renderer,
+                clipboard,
+                shell,
+                viewport,
+            );
+        }
+
+        // Clicks elsewhere unfocus the tree, even if captured
+        if let Event::Mouse(mouse::Event::ButtonPressed(_))
+        | Event::Touch(touch::Event::FingerPressed { .. }) = event
+            && !cursor.is_over(bounds)
+        {
+            state.is_focused = false;
+        }
+
+        if let Some(drag) = state.drag {
+            let Some(dragged) = self.row_of(drag.node) else {
+                state.drag = None;
+                return;
+            };
+
+            match event {
+                Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    if !drag.is_dragging
+                        && position.distance(drag.origin) > DRAG_THRESHOLD
+                    {
+                        state.drag = Some(Drag {
+                            is_dragging: true,
+                            ..drag
+                        });
+                    }
+
+                    if state.drag.is_some_and(|drag| drag.is_dragging) {
+                        shell.request_redraw();
+                        shell.capture_event();
+                    }
+
+                    return;
+                }
+                Event::Mouse(mouse::Event::ButtonReleased(
+                    mouse::Button::Left,
+                )) => {
+                    state.drag = None;
+
+                    if drag.is_dragging {
+                        if let Some(on_reorder) = &self.on_reorder
+                            && let Some(position) = cursor.position()
+                            && let Some((target, placement)) =
+                                self.drop_target(layout, position, dragged)
+                        {
+                            shell.publish(on_reorder(Reorder {
+                                key: self.entries[dragged].key.clone(),
+                                target: self.entries[target].key.clone(),
+                                placement,
+                            }));
+                        }
+
+                        shell.request_redraw();
+                        shell.capture_event();
+                    }
+
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        if let Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) =
+            event
+        {
+            state.modifiers = *modifiers;
+        }
+
+        if shell.is_event_captured() {
+            return;
+        }
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let Some(position) = cursor.position_over(bounds) else {
+                    return;
+                };
+
+                state.is_focused = true;
+
+                let Some((row, row_bounds)) = self.row_at(layout, position)
+                else {
+                    return;
+                };
+
+                let entry = &self.entries[row];
+                let click = mouse::Click::new(
+                    position,
+                    mouse::Button::Left,
+                    state.last_click,
+                );
+
+                state.last_click = Some(click);
+
+                if entry.is_branch
+                    && (self
+                        .arrow_bounds(bounds, row_bounds, entry.depth)
+                        .contains(position)
+                        || click.kind() == mouse::click::Kind::Double)
+                {
+                    self.toggle(row, shell);
+                    shell.capture_event();
+
+                    return;
+                }
+
+                self.select(
+                    state,
+                    row,
+                    state.modifiers.shift(),
+                    state.modifiers.command(),
+                    shell,
+                );
+
+                if self.on_reorder.is_some() {
+                    state.drag = Some(Drag {
+                        node: self.entries[row].id,
+                        origin: position,
+                        is_dragging: false,
+                    });
+                }
+
+                shell.capture_event();
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key),
+                modifiers,
+                ..
+            }) if state.is_focused => {
+                let Some(last) = self.entries.len().checked_sub(1) else {
+                    return;
+                };
+
+                let current =
+                    state.cursor.and_then(|id| self.row_of(id)).or_else(|| {
+                        self.entries.iter().position(|entry| entry.is_selected)
+                    });
+
+                let row = match (key, current) {
+                    (key::Named::ArrowDown, Some(row)) => (row + 1).min(last),
+                    (key::Named::ArrowUp, Some(row)) => row.saturating_sub(1),
+                    (key::Named::ArrowDown | key::Named::Home, _) => 0,
+                    (key::Named::ArrowUp | key::Named::End, _) => last,
+                    (key::Named::ArrowRight, Some(row)) => {
+                        let entry = &self.entries[row];
+
+                        if !entry.is_branch {
+                            return;
+                        } else if !entry.is_expanded {
+                            self.toggle(row, shell);
+                            shell.capture_event();
+
+                            return;
+                        } else if self
+                            .entries
+                            .get(row + 1)
+                            .is_some_and(|child| child.parent == Some(row))
+                        {
+                            row + 1
+                        } else {
+                            return;
+                        }
+                    }
+                    (key::Named::ArrowLeft, Some(row)) => {
+                        let entry = &self.entries[row];
+
+                        if entry.is_expanded {
+                            self.toggle(row, shell);
+                            shell.capture_event();
+
+                            return;
+                        }
+
+                        let Some(parent) = entry.parent else {
+                            return;
+                        };
+
+                        parent
+                    }
+                    (key::Named::Enter, Some(row)) => {
+                        self.toggle(row, shell);
+                        shell.capture_event();
+
+                        return;
+                    }
+                    (key::Named::Space, Some(row)) => {
+                        self.select(
+                            state,
+                            row,
+                            false,
+                            modifiers.command(),
+                            shell,
+                        );
+                        shell.capture_event();
+
+                        return;
+                    }
+                    _ => return,
+                };
+
+                self.select(state, row, modifiers.shift(), false, shell);
+                shell.capture_event();
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        defaults: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let style = theme.style(&self.class);
+
+        let Some(visible_bounds) = bounds.intersection(viewport) else {
+            return;
+        };
+
+        let drag = state.drag.filter(|drag| drag.is_dragging);
+
+        let hovered = cursor
+            .position_over(bounds)
+            .filter(|_| self.on_select.is_some() && drag.is_none())
+            .and_then(|position| self.row_at(layout, position))
+            .map(|(row, _)| row);
+
+        for (row, (((entry, content), tree), layout)) in self
+            .entries
+            .iter()
+            .zip(&self.contents)
+            .zip(&tree.children)
+            .zip(layout.children())
+            .enumerate()
+        {
+            let row_bounds = self.row_bounds(bounds, layout.bounds());
+
+            if !row_bounds.intersects(&visible_bounds) {
+                continue;
+            }
+
+            let background = if entry.is_selected {
+                Some(style.selected)
+            } else if hovered == Some(row) {
+                Some(style.hovered)
+            } else {
+                None
+            };
+
+            if let Some(background) = background {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: row_bounds,
+                        snap: true,
+                        ..renderer::Quad::default()
+                    },
+                    background,
+                );
+            }
+
+            if state.is_focused && state.cursor == Some(entry.id) {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: row_bounds,
+                        border: Border {
+                            color: style.focus,
+                            width: 1.0,
+                            ..Border::default()
+                        },
+                        snap: true,
+                        ..renderer::Quad::default()
+                    },
+                    Color::TRANSPARENT,
+                );
+            }
+
+            for level in 0..entry.depth {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: Rectangle {
+                            x: bounds.x
+                                + self.padding.left
+                                + (level as f32 + 0.5) * self.indent,
+                            width: 1.0,
+                            ..row_bounds
+                        },
+                        snap: true,
+                        ..renderer::Quad::default()
+                    },
+                    style.guide,
+                );
+            }
+
+            if entry.is_branch {
+                draw_arrow(
+                    renderer,
+                    entry.is_expanded,
+                    self.arrow_bounds(bounds, row_bounds, entry.depth).center(),
+                    style.arrow,
+                );
+            }
+
+            content.as_widget().draw(
+                tree,
+                renderer,
+                theme,
+                defaults,
+                layout,
+                if drag.is_some() {
+                    cursor.levitate()
+                } else {
+                    cursor
+                },
+                viewport,
+            );
+        }
+
+        if let Some(drag) = drag
+            && let Some(dragged) = self.row_of(drag.node)
+            && let Some(position) = cursor.position()
+            && let Some((target, placement)) =
+                self.drop_target(layout, position, dragged)
+            && let Some(content) = layout.children().nth(target)
+        {
+            let row_bounds = self.row_bounds(bounds, content.bounds());
+
+            let indicator = match placement {
+                Placement::Before => Rectangle {
+                    height: 2.0,
+                    ..row_bounds
+                },
+                Placement::After => Rectangle {
+                    y: row_bounds.y + row_bounds.height - 2.0,
+                    height: 2.0,
+                    ..row_bounds
+                },
+                Placement::Inside => row_bounds,
+            };
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: indicator,
+                    border: Border {
+                        color: style.drop,
+                        width: if placement == Placement::Inside {
+                            2.0
+                        } else {
+                            0.0
+                        },
+                        ..Border::default()
+                    },
+                    snap: true,
+                    ..renderer::Quad::default()
+                },
+                if placement == Placement::Inside {
+                    Color::TRANSPARENT
+                } else {
+                    style.drop
+                },
+            );
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<State>();
+
+        if state.drag.is_some_and(|drag| drag.is_dragging) {
+            return mouse::Interaction::Grabbing;
+        }
+
+        let interaction = self
+            .contents
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .map(|((content, tree), layout)| {
+                content
+                    .as_widget()
+                    .mouse_interaction(tree, layout, cursor, viewport, renderer)
+            })
+            .max()
+            .unwrap_or_default();
+
+        if interaction != mouse::Interaction::None {
+            return interaction;
+        }
+
+        let bounds = layout.bounds();
+
+        if self.on_toggle.is_some()
+            && let Some(position) = cursor.position_over(bounds)
+            && let Some((row, row_bounds)) = self.row_at(layout, position)
+            && self.entries[row].is_branch
+            && self
+                .arrow_bounds(bounds, row_bounds, self.entries[row].depth)
+                .contains(position)
+        {
+            return mouse::Interaction::Pointer;
+        }
+
+        interaction
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn operation::Operation,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+
+        if self.on_select.is_some() || self.on_toggle.is_some() {
+            operation.focusable(self.id.as_ref(), layout.bounds(), state);
+        }
+
+        operation.container(None, layout.bounds());
+        operation.traverse(&mut |operation| {
+            self.contents
+                .iter_mut()
+                .zip(&mut tree.children)
+                .zip(layout.children())
+                .for_each(|((content, tree), layout)| {
+                    content
+                        .as_widget_mut()
+                        .operate(tree, layout, renderer, operation);
+                });
+        });
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        overlay::from_children(
+            &mut self.contents,
+            tree,
+            layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<'a, Key, Message, Theme, Renderer>
+    From<TreeView<'a, Key, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Key: Clone + 'a,
+    Message: 'a,
+    Theme: Catalog + 'a,
+    Renderer: core::Renderer + 'a,
+{
+    fn from(tree_view: TreeView<'a, Key, Message, Theme, Renderer>) -> Self {
+        Element::new(tree_view)
+    }
+}
+
+/// Draws the expand arrow of a node, centered at the given position.
+fn draw_arrow<Renderer>(
+    renderer: &mut Renderer,
+    is_expanded: bool,
+    center: Point,
+    color: Color,
+) where
+    Renderer: core::Renderer,
+{
+    // A small triangle, one line at a time
+    for line in 0..4u8 {
+        let length = f32::from(7 - line * 2);
+        let offset = f32::from(line);
+
+        let bounds = if is_expanded {
+            Rectangle {
+                x: center.x - 3.5 + offset,
+                y: center.y - 2.0 + offset,
+                width: length,
+                height: 1.0,
+            }
+        } else {
+            Rectangle {
+                x: center.x - 2.0 + offset,
+                y: center.y - 3.5 + offset,
+                width: 1.0,
+                height: length,
+            }
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                snap: true,
+                ..renderer::Quad::default()
+            },
+            color,
+        );
+    }
+}
+
+/// A node of a [`TreeView`].
+pub struct Node<
+    'a,
+    Key,
+    Message,
+    Theme = crate::Theme,
+    Renderer = crate::Renderer,
+> {
+    key: Key,
+    content: Element<'a, Message, Theme, Renderer>,
+    children: Option<Children<'a, Key, Message, Theme, Renderer>>,
+    is_expanded: bool,
+    is_selected: bool,
+}
+
+/// A closure producing the children of a [`Node`].
+type Children<'a, Key, Message, Theme, Renderer> =
+    Box<dyn FnOnce() -> Vec<Node<'a, Key, Message, Theme, Renderer>> + 'a>;
+
+impl<'a, Key, Message, Theme, Renderer>
+    Node<'a, Key, Message, Theme, Renderer>
+{
+    /// Creates a new [`Node`] with the given key and content, and no
+    /// children.
+    pub fn new(
+        key: Key,
+        content: impl Into<Element<'a, Message, Theme, Renderer>>,
+    ) -> Self {
+        Self {
+            key,
+            content: content.into(),
+            children: None,
+            is_expanded: false,
+            is_selected: false,
+        }
+    }
+
+    /// Sets the closure that produces the children of the [`Node`].
+    ///
+    /// The closure is only called if the [`Node`] is expanded; so children
+    /// can be loaded lazily. A [`Node`] with children can be expanded even
+    /// if the closure produces none.
+    pub fn children<I>(mut self, children: impl FnOnce() -> I + 'a) -> Self
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        self.children =
+            Some(Box::new(move || children().into_iter().collect()));
+        self
+    }
+
+    /// Sets whether the [`Node`] is expanded.
+    pub fn expanded(mut self, is_expanded: bool) -> Self {
+        self.is_expanded = is_expanded;
+        self
+    }
+
+    /// Sets whether the [`Node`] is selected.
+    pub fn selected(mut self, is_selected: bool) -> Self {
+        self.is_selected = is_selected;
+        self
+    }
+}
+
+/// A [`Node`] of a [`TreeView`] dropped somewhere else.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reorder<Key> {
+    /// The key of the dropped [`Node`].
+    pub key: Key,
+    /// The key of the [`Node`] it was dropped on.
+    pub target: Key,
+    /// Where it was dropped, relative to the target.
+    pub placement: Placement,
+}
+
+/// Where a [`Node`] is dropped, relative to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Placement {
+    /// Right before the target, as a sibling.
+    Before,
+    /// Right after the target, as a sibling.
+    After,
+    /// Inside the target, as its last child.
+    Inside,
+}
+
+/// The appearance of a [`TreeView`].
+#[derive(Debug, Clone, Copy)]
+pub struct Style {
+    /// The background of the node under the cursor, when nodes can be
+    /// selected.
+    pub hovered: Background,
+    /// The background of the selected nodes.
+    pub selected: Background,
+    /// The [`Color`] of the indentation guides.
+    pub guide: Color,
+    /// The [`Color`] of the expand arrows.
+    pub arrow: Color,
+    /// The [`Color`] of the outline of the current node, when focused.
+    pub focus: Color,
+    /// The [`Color`] of the drop indicator, when dragging a node.
+    pub drop: Color,
+}
+
+/// The theme catalog of a [`TreeView`].
+pub trait Catalog {
+    /// The item class of the [`Catalog`].
+    type Class<'a>;
+
+    /// The default class produced by the [`Catalog`].
+    fn default<'a>() -> Self::Class<'a>;
+
+    /// The [`Style`] of a class.
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+/// A styling function for a [`TreeView`].
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl<Theme> From<Style> for StyleFn<'_, Theme> {
+    fn from(style: Style) -> Self {
+        Box::new(move |_theme| style)
+    }
+}
+
+impl Catalog for crate::Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+/// The default style of a [`TreeView`].
+pub fn default(theme: &crate::Theme) -> Style {
+    let palette = theme.extended_palette();
+
+    Style {
+        hovered: palette.background.weak.color.into(),
+        selected: palette.primary.weak.color.into(),
+        guide: palette.background.strong.color,
+        arrow: palette.background.base.text,
+        focus: palette.primary.strong.color,
+        drop: palette.primary.base.color,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clipboard;
+    use crate::core::keyboard::key::{NativeCode, Physical};
+    use crate::core::widget::Operation;
+    use crate::{Space, text_input};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Message {
+        Toggle(usize, bool),
+        Select(Vec<usize>),
+    }
+
+    type Node = super::Node<'static, usize, Message, crate::Theme, ()>;
+    type TreeView = super::TreeView<'static, usize, Message, crate::Theme, ()>;
+
+    fn node(key: usize) -> Node {
+        Node::new(key, Space::new().width(50).height(20))
+    }
+
+    // Rows are 20 tall, with 2 of padding on each side
+    const ROW: f32 = 24.0;
+
+    /// 0
+    /// ├ 1
+    /// └ 2
+    ///   └ 3
+    /// 4
+    /// 5
+    fn tree(is_expanded: bool, selected: &'static [usize]) -> TreeView {
+        let node = |key| node(key).selected(selected.contains(&key));
+
+        tree_view([
+            node(0).expanded(is_expanded).children(move || {
+                [node(1), node(2).expanded(true).children(move || [node(3)])]
+            }),
+            node(4).children(Vec::new),
+            node(5),
+        ])
+        .width(Length::Fill)
+        .multi_select(true)
+        .on_toggle(Message::Toggle)
+        .on_select(Message::Select)
+    }
+
+    struct Harness {
+        tree_view: TreeView,
+        tree: Tree,
+        node: layout::Node,
+        cursor: Point,
+    }
+
+    impl Harness {
+        fn new(tree_view: TreeView) -> Self {
+            let tree =
+                Tree::new(&tree_view as &dyn Widget<Message, crate::Theme, ()>);
+
+            let mut harness = Self {
+                tree_view,
+                tree,
+                node: layout::Node::default(),
+                cursor: Point::ORIGIN,
+            };
+
+            harness.rebuild(None);
+            harness
+        }
+
+        fn rebuild(&mut self, tree_view: Option<TreeView>) {
+            if let Some(tree_view) = tree_view {
+                self.tree_view = tree_view;
+                self.tree.diff(
+                    &self.tree_view as &dyn Widget<Message, crate::Theme, ()>,
+                );
+            }
+
+            self.node = self.tree_view.layout(
+                &mut self.tree,
+                &(),
+                &layout::Limits::new(Size::ZERO, Size::new(400.0, 400.0)),
+            );
+        }
+
+        fn state(&self) -> &State {
+            self.tree.state.downcast_ref::<State>()
+        }
+
+        fn send(&mut self, event: Event, captured: bool) -> Vec<Message> {
+            let mut messages = Vec::new();
+            let mut shell = Shell::new(&mut messages);
+
+            if captured {
+                shell.capture_event();
+            }
+
+            self.tree_view.update(
+                &mut self.tree,
+                &event,
+                Layout::new(&self.node),
+                mouse::Cursor::Available(self.cursor),
+                &(),
+                &mut clipboard::Null,
+                &mut shell,
+                &Rectangle::new(Point::ORIGIN, Size::new(400.0, 400.0)),
+            );
+
+            messages
+        }
+
+        fn click(
+            &mut self,
+            row: usize,
+            modifiers: keyboard::Modifiers,
+        ) -> Vec<Message> {
+            let _ = self.send(
+                Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)),
+                false,
+            );
+
+            self.cursor = Point::new(200.0, ROW * row as f32 + 10.0);
+
+            let messages = self.send(
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+                false,
+            );
+
+            // Avoid double clicks
+            self.tree.state.downcast_mut::<State>().last_click = None;
+
+            messages
+        }
+
+        fn press(&mut self, key: key::Named) -> Vec<Message> {
+            self.send(
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key: keyboard::Key::Named(key),
+                    modified_key: keyboard::Key::Named(key),
+                    physical_key: Physical::Unidentified(
+                        NativeCode::Unidentified,
+                    ),
+                    location: keyboard::Location::Standard,
+                    modifiers: keyboard::Modifiers::default(),
+                    text: None,
+                }),
+                false,
+            )
+        }
+    }
+
+    #[test]
+    fn drop_targets() {
+        let harness = Harness::new(tree(true, &[]));
+        let layout = Layout::new(&harness.node);
+
+        let target = |row: usize, y: f32, dragged: usize| {
+            harness.tree_view.drop_target(
+                layout,
+                Point::new(200.0, ROW * row as f32 + y),
+                dragged,
+            )
+        };
+
+        // Branches are split in thirds
+        assert_eq!(target(2, 2.0, 5), Some((2, Placement::Before)));
+        assert_eq!(target(2, 12.0, 5), Some((2, Placement::Inside)));
+        assert_eq!(target(2, 22.0, 5), Some((2, Placement::After)));
+
+        // Leaves are split in halves
+        assert_eq!(target(1, 5.0, 5), Some((1, Placement::Before)));
+        assert_eq!(target(1, 18.0, 5), Some((1, Placement::After)));
+
+        // A node cannot be dropped on itself or its descendants
+        assert_eq!(target(0, 12.0, 0), None);
+        assert_eq!(target(3, 12.0, 0), None);
+        assert_eq!(target(3, 12.0, 2), None);
+        assert_eq!(target(4, 12.0, 2), Some((4, Placement::Inside)));
+    }
+
+    #[test]
+    fn selecting_nodes() {
+        let mut harness = Harness::new(tree(true, &[1]));
+
+        let command = keyboard::Modifiers::COMMAND;
+        let shift = keyboard::Modifiers::SHIFT;
+
+        assert_eq!(harness.click(3, command), [Message::Select(vec![1, 3])]);
+        assert_eq!(harness.click(1, command), [Message::Select(vec![])]);
+
+        assert_eq!(
+            harness.click(1, keyboard::Modifiers::default()),
+            [Message::Select(vec![1])]
+        );
+        assert_eq!(
+            harness.click(4, shift),
+            [Message::Select(vec![1, 2, 3, 4])]
+        );
+
+        // The anchor follows its node when the nodes above collapse
+        let _ = harness.click(4, keyboard::Modifiers::default());
+        harness.rebuild(Some(tree(false, &[4])));
+
+        assert_eq!(harness.click(2, shift), [Message::Select(vec![4, 5])]);
+
+        // So does the cursor
+        let _ = harness.click(1, keyboard::Modifiers::default());
+        harness.rebuild(Some(tree(true, &[4])));
+
+        assert_eq!(
+            harness.press(key::Named::ArrowDown),
+            [Message::Select(vec![5])]
+        );
+
+        // Hidden nodes stay selected when toggling others
+        harness.rebuild(Some(tree(false, &[4]).selection([3, 4])));
+
+        assert_eq!(harness.click(2, command), [Message::Select(vec![3, 4, 5])]);
+        assert_eq!(harness.click(1, command), [Message::Select(vec![3])]);
+    }
+
+    #[test]
+    fn only_interactive_trees_are_focusable() {
+        fn count(harness: &mut Harness) -> usize {
+            let mut count = operation::focusable::count();
+
+            harness.tree_view.operate(
+                &mut harness.tree,
+                Layout::new(&harness.node),
+                &(),
+                &mut count,
+            );
+
+            match count.finish() {
+                operation::Outcome::Some(count) => count.total,
+                _ => 0,
+            }
+        }
+
+        assert_eq!(count(&mut Harness::new(tree(true, &[]))), 1);
+        assert_eq!(count(&mut Harness::new(tree_view([node(0)]))), 0);
+    }
+
+    #[test]
+    fn keyboard_navigation() {
+        let mut harness = Harness::new(tree(true, &[]));
+
+        assert_eq!(
+            harness.click(3, keyboard::Modifiers::default()),
+            [Message::Select(vec![3])]
+        );
+        assert!(harness.state().is_focused);
+
+        // Left goes to the parent, then collapses it
+        assert_eq!(
+            harness.press(key::Named::ArrowLeft),
+            [Message::Select(vec![2])]
+        );
+        assert_eq!(
+            harness.press(key::Named::ArrowLeft),
+            [Message::Toggle(2, false)]
+        );
+
+        // Right goes to the first child of an expanded node
+        assert_eq!(
+            harness.press(key::Named::ArrowRight),
+            [Message::Select(vec![3])]
+        );
+        assert!(harness.press(key::Named::ArrowRight).is_empty());
+
+        assert_eq!(
+            harness.press(key::Named::ArrowDown),
+            [Message::Select(vec![4])]
+        );
+        assert_eq!(
+            harness.press(key::Named::ArrowRight),
+            [Message::Toggle(4, true)]
+        );
+        assert_eq!(harness.press(key::Named::Home), [Message::Select(vec![0])]);
+        assert_eq!(
+            harness.press(key::Named::ArrowRight),
+            [Message::Select(vec![1])]
+        );
+
+        // Another widget captures a click outside of the tree
+        harness.cursor = Point::new(200.0, 1000.0);
+
+        let _ = harness.send(
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+            true,
+        );
+
+        assert!(!harness.state().is_focused);
+        assert!(harness.press(key::Named::ArrowDown).is_empty());
+    }
+
+    #[test]
+    fn children_follow_their_nodes() {
+        type Input = text_input::State<()>;
+
+        let tree = |is_expanded| {
+            tree_view([
+                node(0)
+                    .expanded(is_expanded)
+                    .children(|| [node(1), node(2)]),
+                Node::new(3, text_input("", "")),
+            ])
+        };
+
+        let mut harness = Harness::new(tree(false));
+        harness.tree.children[1]
+            .state
+            .downcast_mut::<Input>()
+            .focus();
+
+        let is_focused = |harness: &Harness, row: usize| {
+            harness.tree.children[row]
+                .state
+                .downcast_ref::<Input>()
+                .is_focused()
+        };
+
+        // The input keeps its state when the nodes above expand
+        harness.rebuild(Some(tree(true)));
+        assert!(is_focused(&harness, 3));
+
+        // And when they collapse
+        harness.rebuild(Some(tree(false)));
+        assert!(is_focused(&harness, 1));
+    }
+
+    #[test]
+    fn only_expanded_children_are_produced() {
+        let nodes = vec![
+            node(0).expanded(true).children(|| {
+                [node(1).children(|| -> Vec<Node> {
+                    panic!("collapsed children should not be produced")
+                })]
+            }),
+            node(2),
+            node(3).expanded(true).children(Vec::new),
+        ];
+
+        let mut entries = Vec::new();
+        let mut contents = Vec::new();
+
+        flatten(nodes, 0, None, &mut entries, &mut contents);
+
+        let entries: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                (entry.key, entry.depth, entry.parent, entry.is_expanded)
+            })
+            .collect();
+
+        assert_eq!(
+            entries,
+            [
+                (0, 0, None, true),
+                (1, 1, Some(0), false),
+                (2, 0, None, false),
+                (3, 0, None, true),
+            ]
+        );
+        assert_eq!(contents.len(), 4);
+    }
+}
@@ -0,0 +1,867 @@
+//! Display huge lists by only laying out their visible rows.
+//!
+//! # Example
+//! ```no_run
+//! # mod iced { pub mod widget { pub use iced_widget::*; } }
+//! # pub type Element<'a, Message> = iced_widget::core::Element<'a, Message, iced_widget::Theme, iced_widget::Renderer>;
+//! use iced::widget::{scrollable, text, virtual_list};
+//!
+//! struct State {
+//!    names: Vec<String>,
+//! }
+//!
+//! enum Message {
+//!     // ...
+//! }
+//!
+//! fn view(state: &State) -> Element<'_, Message> {
+//!     scrollable(virtual_list(state.names.len(), 30.0, |i| {
+//!         text(&state.names[i]).into()
+//!     }))
+//!     .into()
+//! }
+//! ```
+use crate::core::layout;
+use crate::core::mouse;
+use crate::core::overlay;
+use crate::core::renderer;
+use crate::core::widget;
+use crate::core::widget::operation::scrollable::AbsoluteOffset;
+use crate::core::widget::operation::{self, Operation};
+use crate::core::widget::tree::{self, Tree};
+use crate::core::{
+    self, Clipboard, Element, Event, Layout, Length, Pixels, Rectangle, Shell,
+    Size, Vector, Widget,
+};
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
+
+/// Creates a new [`VirtualList`] with the given amount of rows, their
+/// [`RowHeight`], and a closure that produces the row at an index.
+pub fn virtual_list<'a, Message, Theme, Renderer>(
+    count: usize,
+    row_height: impl Into<RowHeight>,
+    view: impl Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a,
+) -> VirtualList<'a, Message, Theme, Renderer>
+where
+    Renderer: core::Renderer,
+{
+    VirtualList::new(count, row_height, view)
+}
+
+/// A vertical list that only lays out and draws the rows in view.
+///
+/// A [`VirtualList`] is meant to be placed inside a [`Scrollable`]; which
+/// can be scrolled to a specific row with [`scroll_to`].
+///
+/// [`Scrollable`]: crate::Scrollable
+pub struct VirtualList<
+    'a,
+    Message,
+    Theme = crate::Theme,
+    Renderer = crate::Renderer,
+> {
+    id: Option<widget::Id>,
+    count: usize,
+    view: Box<dyn Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a>,
+    key: Option<Box<dyn Fn(usize) -> u64 + 'a>>,
+    row_height: RowHeight,
+    spacing: f32,
+    width: Length,
+    rows: Vec<Element<'a, Message, Theme, Renderer>>,
+}
+
+impl<'a, Message, Theme, Renderer> VirtualList<'a, Message, Theme, Renderer>
+where
+    Renderer: core::Renderer,
+{
+    /// Creates a new [`VirtualList`] with the given amount of rows, their
+    /// [`RowHeight`], and a closure that produces the row at an index.
+    ///
+    /// The closure is only called for the rows in view.
+    pub fn new(
+        count: usize,
+        row_height: impl Into<RowHeight>,
+        view: impl Fn(usize) -> Element<'a, Message, Theme, Renderer> + 'a,
+    ) -> Self {
+        Self {
+            id: None,
+            count,
+            view: Box::new(view),
+            key: None,
+            row_height: row_height.into(),
+            spacing: 0.0,
+            width: Length::Fill,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Sets the [`widget::Id`] of the [`VirtualList`].
+    pub fn id(mut self, id: impl Into<widget::Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the closure that produces the key of the row at an index.
+    ///
+    /// The state of the rows in view is kept by key; so it follows its
+    /// row when rows are inserted or removed. By default, rows are keyed
+    /// by their index.
+    pub fn key<K>(mut self, key: impl Fn(usize) -> K + 'a) -> Self
+    where
+        K: Hash,
+    {
+        self.key = Some(Box::new(move |index| {
+            let mut hasher = DefaultHasher::new();
+            key(index).hash(&mut hasher);
+            hasher.finish()
+        }));
+        self
+    }
+
+    /// Sets the vertical spacing between the rows of the [`VirtualList`].
+    pub fn spacing(mut self, spacing: impl Into<Pixels>) -> Self {
+        self.spacing = spacing.into().0;
+        self
+    }
+
+    /// Sets the width of the [`VirtualList`].
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    fn key_of(&self, index: usize) -> u64 {
+        self.key.as_ref().map_or(index as u64, |key| key(index))
+    }
+}
+
+/// The height of the rows of a [`VirtualList`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowHeight {
+    /// Every row has the same height.
+    Fixed(f32),
+    /// Rows may have different heights, which are measured once they come
+    /// into view; until then, they are assumed to have the estimated one.
+    Estimated(f32),
+}
+
+impl From<f32> for RowHeight {
+    fn from(height: f32) -> Self {
+        Self::Fixed(height)
+    }
+}
+
+impl From<Pixels> for RowHeight {
+    fn from(height: Pixels) -> Self {
+        Self::Fixed(height.0)
+    }
+}
+
+/// The vertical positions of some virtual rows.
+#[derive(Debug, Clone)]
+pub(crate) struct Rows {
+    count: usize,
+    height: RowHeight,
+    spacing: f32,
+    measured: BTreeMap<usize, f32>,
+    /// A Fenwick tree with the differences between the measured and the
+    /// estimated heights; so offsets can be found in logarithmic time.
+    deltas: Vec<f32>,
+}
+
+impl Rows {
+    /// Creates some [`Rows`] with the given amount, height, and spacing.
+    pub fn new(count: usize, height: RowHeight, spacing: f32) -> Self {
+        Self {
+            count,
+            height,
+            spacing,
+            measured: BTreeMap::new(),
+            deltas: Vec::new(),
+        }
+    }
+
+    /// Updates the amount, height, and spacing of the [`Rows`]; keeping
+    /// the measurements that are still valid.
+    pub fn update(&mut self, count: usize, height: RowHeight, spacing: f32) {
+        let has_changed = count != self.count || height != self.height;
+
+        if height != self.height {
+            self.measured.clear();
+        }
+
+        let _ = self.measured.split_off(&count);
+
+        self.count = count;
+        self.height = height;
+        self.spacing = spacing;
+
+        if has_changed {
+            self.rebuild();
+        }
+    }
+
+    /// Records the measured height of a row, if the height of the rows is
+    /// estimated.
+    pub fn measure(&mut self, row: usize, height: f32) {
+        let RowHeight::Estimated(estimate) = self.height else {
+            return;
+        };
+
+        if row >= self.count {
+            return;
+        }
+
+        let previous = self.measured.insert(row, height).unwrap_or(estimate);
+
+        if previous == height {
+            return;
+        }
+
+        if self.deltas.len() != self.count {
+            self.rebuild();
+            return;
+        }
+
+        // Fenwick trees are 1-indexed
+        let mut index = row + 1;
+
+        while index <= self.count {
+            self.deltas[index - 1] += height - previous;
+            index += index & index.wrapping_neg();
+        }
+    }
+
+    /// Rebuilds the Fenwick tree of deltas from the measured heights.
+    fn rebuild(&mut self) {
+        let RowHeight::Estimated(estimate) = self.height else {
+            self.deltas.clear();
+            return;
+        };
+
+        self.deltas.clear();
+        self.deltas.resize(self.count, 0.0);
+
+        for (row, height) in &self.measured {
+            self.deltas[*row] = height - estimate;
+        }
+
+        for index in 1..=self.count {
+            let parent = index + (index & index.wrapping_neg());
+
+            if parent <= self.count {
+                self.deltas[parent - 1] += self.deltas[index - 1];
+            }
+        }
+    }
+
+    /// Returns the sum of the deltas of the rows before the given one.
+    fn delta(&self, row: usize) -> f32 {
+        if self.deltas.is_empty() {
+            return 0.0;
+        }
+
+        let mut index = row.min(self.count);
+        let mut sum = 0.0;
+
+        while index > 0 {
+            sum += self.deltas[index - 1];
+            index -= index & index.wrapping_neg();
+        }
+
+        sum
+    }
+
+    /// Returns the amount of rows.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the height of the given row.
+    pub fn height(&self, row: usize) -> f32 {
+        match self.height {
+            RowHeight::Fixed(height) => height,
+            RowHeight::Estimated(estimate) => {
+                self.measured.get(&row).copied().unwrap_or(estimate)
+            }
+        }
+    }
+
+    /// Returns the vertical offset of the given row.
+    pub fn offset(&self, row: usize) -> f32 {
+        match self.height {
+            RowHeight::Fixed(height) => row as f32 * (height + self.spacing),
+            RowHeight::Estimated(estimate) => {
+                row as f32 * (estimate + self.spacing) + self.delta(row)
+            }
+        }
+    }
+
+    /// Returns the total height of the [`Rows`].
+    pub fn total(&self) -> f32 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.offset(self.count) - self.spacing
+        }
+    }
+
+    /// Returns the rows that intersect the given vertical range.
+    pub fn visible(&self, top: f32, bottom: f32) -> Range<usize> {
+        let start =
+            self.search(|row| self.offset(row) + self.height(row) > top);
+        let end = self.search(|row| self.offset(row) >= bottom);
+
+        start..end.max(start)
+    }
+
+    /// Returns the first row that satisfies the given monotone predicate,
+    /// or the amount of rows if none does.
+    fn search(&self, predicate: impl Fn(usize) -> bool) -> usize {
+        let mut low = 0;
+        let mut high = self.count;
+
+        while low < high {
+            let middle = low + (high - low) / 2;
+
+            if predicate(middle) {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+
+        low
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    rows: Rows,
+    visible: Range<usize>,
+    viewport: Option<Rectangle>,
+    keys: Vec<u64>,
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for VirtualList<'_, Message, Theme, Renderer>
+where
+    Renderer: core::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State {
+            rows: Rows::new(self.count, self.row_height, self.spacing),
+            visible: 0..0,
+            viewport: None,
+            keys: Vec::new(),
+        })
+    }
+
+    fn diff(&self, _tree: &mut Tree) {
+        // Diff is deferred to layout
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size {
+            width: self.width,
+            height: Length::Shrink,
+        }
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let state = tree.state.downcast_mut::<State>();
+        state.rows.update(self.count, self.row_height, self.spacing);
+
+        let limits = limits.width(self.width);
+        let max = limits.max();
+
+        // Until the list is in view, only the rows fitting its limits are laid out
+        let visible = match state.viewport {
+            Some(viewport) => {
+                state.rows.visible(viewport.y, viewport.y + viewport.height)
+            }
+            None if max.height.is_finite() => {
+                state.rows.visible(0.0, max.height)
+            }
+            None => 0..0,
+        };
+
+        self.rows = visible.clone().map(|index| (self.view)(index)).collect();
+
+        let keys: Vec<u64> =
+            visible.clone().map(|index| self.key_of(index)).collect();
+
+        let mut trees: HashMap<u64, Tree> =
+            state.keys.drain(..).zip(tree.children.drain(..)).collect();
+
+        tree.children = keys
+            .iter()
+            .zip(&self.rows)
+            .map(|(key, row)| {
+                if let Some(mut tree) = trees.remove(key) {
+                    tree.diff(row.as_widget());
+                    tree
+                } else {
+                    Tree::new(row.as_widget())
+                }
+            })
+            .collect();
+
+        state.keys = keys;
+        state.visible = visible.clone();
+
+        let row_limits = layout::Limits::new(
+            Size::ZERO,
+            Size::new(
+                max.width,
+                match self.row_height {
+                    RowHeight::Fixed(height) => height,
+                    RowHeight::Estimated(_) => f32::INFINITY,
+                },
+            ),
+        );
+
+        let mut nodes: Vec<layout::Node> = self
+            .rows
+            .iter_mut()
+            .zip(&mut tree.children)
+            .map(|(row, tree)| {
+                row.as_widget_mut().layout(tree, renderer, &row_limits)
+            })
+            .collect();
+
+        for (index, node) in visible.clone().zip(&nodes) {
+            state.rows.measure(index, node.size().height);
+        }
+
+        let mut width: f32 = 0.0;
+
+        for (index, node) in visible.zip(&mut nodes) {
+            width = width.max(node.size().width);
+            node.move_to_mut((0.0, state.rows.offset(index)));
+        }
+
+        let size = limits.resolve(
+            self.width,
+            Length::Shrink,
+            Size::new(width, state.rows.total()),
+        );
+
+        layout::Node::with_children(size, nodes)
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+        let bounds = layout.bounds();
+
+        state.viewport =
+            viewport.intersection(&bounds).map(|viewport| Rectangle {
+                x: viewport.x - bounds.x,
+                y: viewport.y - bounds.y,
+                ..viewport
+            });
+
+        let visible = state.viewport.map_or(0..0, |viewport| {
+            state.rows.visible(viewport.y, viewport.y + viewport.height)
+        });
+
+        if visible != state.visible {
+            shell.invalidate_layout();
+            shell.request_redraw();
+        }
+
+        for ((row, tree), layout) in self
+            .rows
+            .iter_mut()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+        {
+            row.as_widget_mut().update(
+                tree, event, layout, cursor, renderer, clipboard, shell,
+                viewport,
+            );
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        for ((row, tree), layout) in self
+            .rows
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .filter(|(_, layout)| layout.bounds().intersects(viewport))
+        {
+            row.as_widget()
+                .draw(tree, renderer, theme, style, layout, cursor, viewport);
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.rows
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+            .map(|((row, tree), layout)| {
+                row.as_widget()
+                    .mouse_interaction(tree, layout, cursor, viewport, renderer)
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state = tree.state.downcast_mut::<State>();
+
+        operation.custom(self.id.as_ref(), layout.bounds(), state);
+        operation.container(self.id.as_ref(), layout.bounds());
+        operation.traverse(&mut |operation| {
+            self.rows
+                .iter_mut()
+                .zip(&mut tree.children)
+                .zip(layout.children())
+                .for_each(|((row, tree), layout)| {
+                    row.as_widget_mut()
+                        .operate(tree, layout, renderer, operation);
+                });
+        });
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        overlay::from_children(
+            &mut self.rows,
+            tree,
+            layout,
+            renderer,
+            viewport,
+            translation,
+        )
+    }
+}
+
+impl<'a, Message, Theme, Renderer>
+    From<VirtualList<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a,
+    Theme: 'a,
+    Renderer: core::Renderer + 'a,
+{
+    fn from(list: VirtualList<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(list)
+    }
+}
+
+/// Produces an [`Operation`] that scrolls the closest scrollable around
+/// the [`VirtualList`] with the given [`widget::Id`], so the row at the
+/// given index is at its top.
+pub fn scroll_to<T>(
+    id: impl Into<widget::Id>,
+    index: usize,
+) -> impl Operation<T> {
+    struct Find {
+        target: widget::Id,
+        index: usize,
+        scrollables: usize,
+        pending: Option<(usize, Rectangle)>,
+        ancestors: Vec<(usize, Rectangle)>,
+        found: Option<(usize, f32)>,
+    }
+
+    impl<T> Operation<T> for Find {
+        fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation<T>)) {
+            let pending = self.pending.take();
+
+            if let Some(scrollable) = pending {
+                self.ancestors.push(scrollable);
+            }
+
+            operate(self);
+
+            if pending.is_some() {
+                let _ = self.ancestors.pop();
+            }
+        }
+
+        fn scrollable(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            content_bounds: Rectangle,
+            _translation: Vector,
+            _state: &mut dyn operation::Scrollable,
+        ) {
+            self.pending = Some((self.scrollables, content_bounds));
+            self.scrollables += 1;
+        }
+
+        fn custom(
+            &mut self,
+            id: Option<&widget::Id>,
+            bounds: Rectangle,
+            state: &mut dyn Any,
+        ) {
+            if self.found.is_some() || id != Some(&self.target) {
+                return;
+            }
+
+            let Some(state) = state.downcast_ref::<State>() else {
+                return;
+            };
+
+            if let Some((scrollable, content_bounds)) = self.ancestors.last() {
+                let row = self.index.min(state.rows.count.saturating_sub(1));
+
+                self.found = Some((
+                    *scrollable,
+                    bounds.y - content_bounds.y + state.rows.offset(row),
+                ));
+            }
+        }
+
+        fn finish(&self) -> operation::Outcome<T> {
+            match self.found {
+                Some((target, y)) => {
+                    operation::Outcome::Chain(Box::new(ScrollTo {
+                        target,
+                        y,
+                        scrollables: 0,
+                    }))
+                }
+                None => operation::Outcome::None,
+            }
+        }
+    }
+
+    struct ScrollTo {
+        target: usize,
+        y: f32,
+        scrollables: usize,
+    }
+
+    impl<T> Operation<T> for ScrollTo {
+        fn traverse(&mut self, operate: &mut dyn FnMut(&mut dyn Operation<T>)) {
+            operate(self);
+        }
+
+        fn scrollable(
+            &mut self,
+            _id: Option<&widget::Id>,
+            _bounds: Rectangle,
+            _content_bounds: Rectangle,
+            translation: Vector,
+            state: &mut dyn operation::Scrollable,
+        ) {
+            if self.scrollables == self.target {
+                state.scroll_to(AbsoluteOffset {
+                    x: translation.x,
+                    y: self.y,
+                });
+            }
+
+            self.scrollables += 1;
+        }
+    }
+
+    Find {
+        target: id.into(),
+        index,
+        scrollables: 0,
+        pending: None,
+        ancestors: Vec::new(),
+        found: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Point;
+    use crate::core::clipboard;
+    use crate::{Scrollable, Space};
+
+    #[test]
+    fn visible_rows() {
+        let rows = Rows::new(1000, RowHeight::Fixed(10.0), 2.0);
+
+        assert_eq!(rows.offset(3), 36.0);
+        assert_eq!(rows.total(), 11998.0);
+        assert_eq!(rows.visible(0.0, 30.0), 0..3);
+        assert_eq!(rows.visible(35.0, 60.0), 3..5);
+        assert_eq!(rows.visible(11990.0, 12100.0), 999..1000);
+
+        let mut rows = Rows::new(100, RowHeight::Estimated(10.0), 0.0);
+        rows.measure(1, 30.0);
+
+        assert_eq!(rows.offset(1), 10.0);
+        assert_eq!(rows.offset(2), 40.0);
+        assert_eq!(rows.total(), 1020.0);
+        assert_eq!(rows.visible(15.0, 45.0), 1..3);
+
+        rows.update(2, RowHeight::Estimated(10.0), 0.0);
+
+        assert_eq!(rows.total(), 40.0);
+    }
+
+    #[test]
+    fn scrolling_through_measured_rows() {
+        let mut rows = Rows::new(2_000, RowHeight::Estimated(20.0), 2.0);
+
+        // Every pass measures the rows in view with a different height
+        for pass in 0..3 {
+            let height =
+                |row: usize| (10 + (row * 7 + pass * 3) % 5 * 10) as f32;
+            let mut top = 0.0;
+
+            while top < rows.total() {
+                let visible = rows.visible(top, top + 300.0);
+
+                for row in visible.clone() {
+                    rows.measure(row, height(row));
+                }
+
+                let expected: f32 =
+                    (0..visible.start).map(|row| rows.height(row) + 2.0).sum();
+
+                assert_eq!(rows.offset(visible.start), expected);
+                assert!(
+                    rows.offset(visible.start) + rows.height(visible.start)
+                        > top
+                );
+
+                top += 250.0;
+            }
+
+            assert!((0..2_000).all(|row| rows.height(row) == height(row)));
+        }
+
+        let total: f32 = (0..2_000).map(|row| rows.height(row) + 2.0).sum();
+
+        assert_eq!(rows.total(), total - 2.0);
+
+        rows.update(1_500, RowHeight::Estimated(20.0), 2.0);
+
+        assert_eq!(
+            rows.total(),
+            (0..1_500).map(|row| rows.height(row) + 2.0).sum::<f32>() - 2.0
+        );
+    }
+
+    #[test]
+    fn scrolling_to_a_row() {
+        let mut content: Element<'static, (), crate::Theme, ()> =
+            Scrollable::new(
+                virtual_list(1_000, 20.0, |_| {
+                    Space::new().width(50).height(20).into()
+                })
+                .id("list"),
+            )
+            .height(100)
+            .into();
+
+        let mut tree = Tree::new(&content);
+        let bounds = Rectangle::new(Point::ORIGIN, Size::new(400.0, 400.0));
+
+        let relayout = |content: &mut Element<'static, (), _, ()>,
+                        tree: &mut Tree| {
+            content.as_widget_mut().layout(
+                tree,
+                &(),
+                &layout::Limits::new(Size::ZERO, bounds.size()),
+            )
+        };
+
+        let node = relayout(&mut content, &mut tree);
+
+        let mut operation: Box<dyn Operation> =
+            Box::new(scroll_to("list", 500));
+
+        loop {
+            content.as_widget_mut().operate(
+                &mut tree,
+                Layout::new(&node),
+                &(),
+                operation.as_mut(),
+            );
+
+            match operation.finish() {
+                operation::Outcome::Chain(next) => operation = next,
+                _ => break,
+            }
+        }
+
+        // The list finds out about its new viewport on the next event
+        let mut messages = Vec::new();
+
+        content.as_widget_mut().update(
+            &mut tree,
+            &Event::Mouse(mouse::Event::CursorMoved {
+                position: Point::ORIGIN,
+            }),
+            Layout::new(&node),
+            mouse::Cursor::Available(Point::ORIGIN),
+            &(),
+            &mut clipboard::Null,
+            &mut Shell::new(&mut messages),
+            &bounds,
+        );
+
+        let _ = relayout(&mut content, &mut tree);
+
+        let state = tree.children[0].state.downcast_ref::<State>();
+
+        assert_eq!(state.visible, 500..505);
+        assert_eq!(state.keys.first(), Some(&500));
+    }
+}
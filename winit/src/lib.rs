@@ -1745,6 +1745,14 @@ fn run_action<'a, P, C>(
                     );
                 }
             }
+            image::Action::Invalidate(path) => {
+                use core::Renderer as _;
+
+                for (_id, window) in window_manager.iter_mut() {
+                    window.renderer.invalidate_asset(&path);
+                    window.raw.request_redraw();
+                }
+            }
         },
         Action::LoadFont { bytes, channel } => {
             if let Some(compositor) = compositor {
@@ -1754,6 +1762,13 @@ fn run_action<'a, P, C>(
                 let _ = channel.send(Ok(()));
             }
         }
+        Action::ReloadFont { bytes, channel } => {
+            if let Some(compositor) = compositor {
+                compositor.reload_font(bytes);
+
+                let _ = channel.send(Ok(()));
+            }
+        }
         Action::Reload => {
             for (id, window) in window_manager.iter_mut() {
                 let Some(ui) = interfaces.remove(&id) else {